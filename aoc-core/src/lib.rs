@@ -0,0 +1,236 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::num::ParseIntError;
+
+/// A puzzle part: takes the whole puzzle input and returns its answer.
+pub trait Solution {
+    fn solve(input: &str) -> Result<Answer, SolveError>;
+}
+
+/// The answer of a puzzle part, either a number or some text (stacks of crates, CRT screen, ...)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Number(n.into())
+                }
+            }
+        )*
+    };
+}
+
+answer_from_number!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Number(n as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub enum SolveError {
+    /// The input doesn't follow the puzzle format
+    Parse(Box<dyn Error + Send + Sync>),
+    /// The input is valid but the puzzle has no answer for it
+    NoSolution(String),
+}
+
+impl SolveError {
+    pub fn parse<E: Into<Box<dyn Error + Send + Sync>>>(e: E) -> Self {
+        SolveError::Parse(e.into())
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Parse(e) => write!(f, "Invalid input: {}", e),
+            SolveError::NoSolution(reason) => write!(f, "No solution: {}", reason),
+        }
+    }
+}
+
+impl Error for SolveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SolveError::Parse(e) => Some(e.as_ref()),
+            SolveError::NoSolution(_) => None,
+        }
+    }
+}
+
+impl From<ParseIntError> for SolveError {
+    fn from(e: ParseIntError) -> Self {
+        SolveError::parse(e)
+    }
+}
+
+impl<E: Error + Send + Sync + 'static> From<Located<E>> for SolveError {
+    fn from(e: Located<E>) -> Self {
+        SolveError::parse(e)
+    }
+}
+
+/// An error found at a given place of the puzzle input, lines and columns start at 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Located<E> {
+    line: usize,
+    column: usize,
+    error: E,
+}
+
+impl<E> Located<E> {
+    pub fn new(line: usize, column: usize, error: E) -> Self {
+        Located {
+            line,
+            column,
+            error,
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Convert the error while keeping its place
+    pub fn map<F, O: FnOnce(E) -> F>(self, op: O) -> Located<F> {
+        Located::new(self.line, self.column, op(self.error))
+    }
+}
+
+impl<E: Display> Display for Located<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.error
+        )
+    }
+}
+
+impl<E: Error + 'static> Error for Located<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Attach the place of the puzzle input where a parser failed to its error
+pub trait Locate<T, E> {
+    fn at(self, line: usize, column: usize) -> Result<T, Located<E>>;
+}
+
+impl<T, E> Locate<T, E> for Result<T, E> {
+    fn at(self, line: usize, column: usize) -> Result<T, Located<E>> {
+        self.map_err(|error| Located::new(line, column, error))
+    }
+}
+
+/// Column (starting at 1) of `token` in `line`, or 1 if `token` isn't a slice of `line`
+pub fn column(line: &str, token: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let token_start = token.as_ptr() as usize;
+
+    if token_start < start || token_start + token.len() > start + line.len() {
+        return 1;
+    }
+
+    line.get(..token_start - start)
+        .map_or(1, |before| before.chars().count() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_display() {
+        assert_eq!(Answer::from(42u32).to_string(), "42");
+        assert_eq!(Answer::from(-3i64).to_string(), "-3");
+        assert_eq!(Answer::from("CMZ").to_string(), "CMZ");
+    }
+
+    #[test]
+    fn answer_from_numbers_are_equal() {
+        assert_eq!(Answer::from(24000u32), Answer::from(24000usize));
+        assert_eq!(Answer::from(24000u64), Answer::Number(24000));
+    }
+
+    #[test]
+    fn located_error() {
+        let error: Result<u32, _> = "1x".parse::<u32>().at(3, 7);
+        let error = error.unwrap_err();
+
+        assert_eq!(error.line(), 3);
+        assert_eq!(error.column(), 7);
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 7: invalid digit found in string"
+        );
+        assert!(SolveError::from(error).source().is_some());
+    }
+
+    #[test]
+    fn located_map() {
+        let error = Located::new(2, 5, 'x').map(|c| c.to_string());
+
+        assert_eq!(error, Located::new(2, 5, String::from("x")));
+    }
+
+    #[test]
+    fn token_column() {
+        let line = "move 12 from 3 to 4";
+        let tokens: Vec<&str> = line.split(' ').collect();
+
+        assert_eq!(column(line, tokens[0]), 1);
+        assert_eq!(column(line, tokens[1]), 6);
+        assert_eq!(column(line, tokens[5]), 19);
+        assert_eq!(column(line, "a token from another line"), 1);
+    }
+
+    #[test]
+    fn parse_error() {
+        let error = SolveError::from("abc".parse::<u32>().unwrap_err());
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid input: invalid digit found in string"
+        );
+        assert!(error.source().is_some());
+    }
+}
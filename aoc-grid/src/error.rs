@@ -0,0 +1,34 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading a character map into a grid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError<E> {
+    /// A character the cell parser refused
+    Cell(E),
+    /// A row that isn't as wide as the first one
+    InvalidWidth { expected: usize, found: usize },
+    /// The map has no cell
+    Empty,
+}
+
+impl<E: Display> Display for GridError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Cell(e) => write!(f, "{}", e),
+            GridError::InvalidWidth { expected, found } => {
+                write!(f, "expected {} cells in the row, found {}", expected, found)
+            }
+            GridError::Empty => write!(f, "expected a map, found nothing"),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for GridError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GridError::Cell(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,592 @@
+use std::collections::{hash_map, HashMap};
+use std::iter::Enumerate;
+use std::ops::{Index, IndexMut};
+use std::slice;
+
+use aoc_core::{Locate, Located};
+use aoc_point::Point2;
+
+pub mod error;
+
+use crate::error::GridError;
+
+/// A position in a grid: `x` grows to the right and `y` grows downward
+pub type Pos = Point2<isize>;
+
+pub const UP: Pos = Pos::new(0, -1);
+pub const RIGHT: Pos = Pos::new(1, 0);
+pub const DOWN: Pos = Pos::new(0, 1);
+pub const LEFT: Pos = Pos::new(-1, 0);
+
+/// The 4 orthogonal directions, clockwise from up
+pub const DIRECTIONS4: [Pos; 4] = [UP, RIGHT, DOWN, LEFT];
+
+/// The 8 directions including the diagonals, clockwise from up
+pub const DIRECTIONS8: [Pos; 8] = [
+    UP,
+    Pos::new(1, -1),
+    RIGHT,
+    Pos::new(1, 1),
+    DOWN,
+    Pos::new(-1, 1),
+    LEFT,
+    Pos::new(-1, -1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Cells<T> {
+    /// A value for every cell of a `width` x `height` rectangle starting at (0, 0), row by row
+    Dense {
+        cells: Vec<T>,
+        width: usize,
+        height: usize,
+    },
+    /// Values only for the cells that were inserted, anywhere on the plane
+    Sparse(HashMap<Pos, T>),
+}
+
+/// A 2D grid of values.
+///
+/// A dense grid stores every cell of a rectangle and suits the maps given as puzzle input,
+/// a sparse grid only stores the cells that were inserted and suits unbounded maps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Cells<T>,
+}
+
+impl<T> Grid<T> {
+    /// Dense grid of `width` x `height` cells, all set to `value`
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: Cells::Dense {
+                cells: vec![value; width * height],
+                width,
+                height,
+            },
+        }
+    }
+
+    /// Dense grid from its rows, `None` when they don't all have the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Grid {
+            cells: Cells::Dense {
+                cells: rows.into_iter().flatten().collect(),
+                width,
+                height,
+            },
+        })
+    }
+
+    /// Empty sparse grid
+    pub fn sparse() -> Self {
+        Grid {
+            cells: Cells::Sparse(HashMap::new()),
+        }
+    }
+
+    /// Dense grid from a rectangular character map, `cell` turning the character found
+    /// at each position into its value
+    pub fn parse<E, F>(input: &str, mut cell: F) -> Result<Self, Located<GridError<E>>>
+    where
+        F: FnMut(Pos, char) -> Result<T, E>,
+    {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            let mut row_width = 0;
+
+            for (x, c) in line.chars().enumerate() {
+                let value = cell(Pos::new(x as isize, y as isize), c)
+                    .map_err(GridError::Cell)
+                    .at(y + 1, x + 1)?;
+                cells.push(value);
+                row_width += 1;
+            }
+
+            if y == 0 {
+                width = row_width;
+            } else if row_width != width {
+                let error = GridError::InvalidWidth {
+                    expected: width,
+                    found: row_width,
+                };
+                return Err(Located::new(y + 1, 1, error));
+            }
+
+            height += 1;
+        }
+
+        if cells.is_empty() {
+            return Err(Located::new(1, 1, GridError::Empty));
+        }
+
+        Ok(Grid {
+            cells: Cells::Dense {
+                cells,
+                width,
+                height,
+            },
+        })
+    }
+
+    /// Sparse grid from a character map, `cell` returning `None` for the characters
+    /// that don't hold a value (like the ground between the elves)
+    pub fn parse_sparse<E, F>(input: &str, mut cell: F) -> Result<Self, Located<GridError<E>>>
+    where
+        F: FnMut(Pos, char) -> Result<Option<T>, E>,
+    {
+        let mut cells = HashMap::new();
+
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let pos = Pos::new(x as isize, y as isize);
+
+                if let Some(value) = cell(pos, c).map_err(GridError::Cell).at(y + 1, x + 1)? {
+                    cells.insert(pos, value);
+                }
+            }
+        }
+
+        Ok(Grid {
+            cells: Cells::Sparse(cells),
+        })
+    }
+
+    pub fn is_dense(&self) -> bool {
+        matches!(self.cells, Cells::Dense { .. })
+    }
+
+    /// Number of values stored in the grid
+    pub fn len(&self) -> usize {
+        match &self.cells {
+            Cells::Dense { cells, .. } => cells.len(),
+            Cells::Sparse(cells) => cells.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Smallest and largest positions of the rectangle holding every value, `None` when empty
+    pub fn bounds(&self) -> Option<(Pos, Pos)> {
+        match &self.cells {
+            Cells::Dense { width, height, .. } => {
+                if *width == 0 || *height == 0 {
+                    None
+                } else {
+                    Some((
+                        Pos::new(0, 0),
+                        Pos::new(*width as isize - 1, *height as isize - 1),
+                    ))
+                }
+            }
+            Cells::Sparse(cells) => {
+                let mut positions = cells.keys();
+                let first = *positions.next()?;
+
+                Some(positions.fold((first, first), |(min, max), pos| {
+                    (
+                        Pos::new(min.x.min(pos.x), min.y.min(pos.y)),
+                        Pos::new(max.x.max(pos.x), max.y.max(pos.y)),
+                    )
+                }))
+            }
+        }
+    }
+
+    /// Number of columns of the bounds
+    pub fn width(&self) -> usize {
+        self.bounds()
+            .map_or(0, |(min, max)| (max.x - min.x) as usize + 1)
+    }
+
+    /// Number of rows of the bounds
+    pub fn height(&self) -> usize {
+        self.bounds()
+            .map_or(0, |(min, max)| (max.y - min.y) as usize + 1)
+    }
+
+    /// Value at `pos`, `None` outside of a dense grid or on an empty cell of a sparse grid
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        match &self.cells {
+            Cells::Dense {
+                cells,
+                width,
+                height,
+            } => dense_index(*width, *height, pos).map(|index| &cells[index]),
+            Cells::Sparse(cells) => cells.get(&pos),
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        match &mut self.cells {
+            Cells::Dense {
+                cells,
+                width,
+                height,
+            } => dense_index(*width, *height, pos).map(|index| &mut cells[index]),
+            Cells::Sparse(cells) => cells.get_mut(&pos),
+        }
+    }
+
+    /// Whether a value is stored at `pos`
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.get(pos).is_some()
+    }
+
+    /// Store `value` at `pos` and return the value it replaces.
+    ///
+    /// Panics if `pos` is outside of a dense grid.
+    pub fn insert(&mut self, pos: Pos, value: T) -> Option<T> {
+        match &mut self.cells {
+            Cells::Dense {
+                cells,
+                width,
+                height,
+            } => match dense_index(*width, *height, pos) {
+                Some(index) => Some(std::mem::replace(&mut cells[index], value)),
+                None => panic!("{:?} is outside of the grid", pos),
+            },
+            Cells::Sparse(cells) => cells.insert(pos, value),
+        }
+    }
+
+    /// Take the value out of `pos` in a sparse grid.
+    /// Every cell of a dense grid keeps its value, so nothing is removed from it.
+    pub fn remove(&mut self, pos: Pos) -> Option<T> {
+        match &mut self.cells {
+            Cells::Dense { .. } => None,
+            Cells::Sparse(cells) => cells.remove(&pos),
+        }
+    }
+
+    /// Every stored value with its position, row by row for a dense grid and in no
+    /// particular order for a sparse one
+    pub fn iter(&self) -> Iter<'_, T> {
+        match &self.cells {
+            Cells::Dense { cells, width, .. } => Iter::Dense {
+                cells: cells.iter().enumerate(),
+                width: *width,
+            },
+            Cells::Sparse(cells) => Iter::Sparse(cells.iter()),
+        }
+    }
+
+    /// Values of the 4 orthogonal neighbours of `pos`
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        pos.neighbours4()
+            .filter_map(|neighbour| self.get(neighbour).map(|value| (neighbour, value)))
+    }
+
+    /// Values of the 8 neighbours of `pos`, including the diagonals
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        pos.neighbours8()
+            .filter_map(|neighbour| self.get(neighbour).map(|value| (neighbour, value)))
+    }
+
+    /// Values of the row `y`, from left to right
+    pub fn row(&self, y: isize) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        let (min, max) = self.bounds().unwrap_or((Pos::new(0, 0), Pos::new(-1, -1)));
+
+        (min.x..=max.x).filter_map(move |x| {
+            let pos = Pos::new(x, y);
+            self.get(pos).map(|value| (pos, value))
+        })
+    }
+
+    /// Values of the column `x`, from top to bottom
+    pub fn column(&self, x: isize) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        let (min, max) = self.bounds().unwrap_or((Pos::new(0, 0), Pos::new(-1, -1)));
+
+        (min.y..=max.y).filter_map(move |y| {
+            let pos = Pos::new(x, y);
+            self.get(pos).map(|value| (pos, value))
+        })
+    }
+
+    /// Values met when walking from `from` (excluded) in the direction `dir`
+    /// until leaving the bounds of the grid
+    pub fn ray(&self, from: Pos, dir: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        let bounds = self.bounds();
+        let inside = move |pos: &Pos| {
+            bounds.is_some_and(|(min, max)| {
+                (min.x..=max.x).contains(&pos.x) && (min.y..=max.y).contains(&pos.y)
+            })
+        };
+
+        std::iter::successors(Some(from + dir), move |&pos| Some(pos + dir))
+            .take_while(inside)
+            .filter_map(move |pos| self.get(pos).map(|value| (pos, value)))
+    }
+
+    /// Grid of the same shape with `f` applied to every value
+    pub fn map<U, F: FnMut(&T) -> U>(&self, mut f: F) -> Grid<U> {
+        let cells = match &self.cells {
+            Cells::Dense {
+                cells,
+                width,
+                height,
+            } => Cells::Dense {
+                cells: cells.iter().map(&mut f).collect(),
+                width: *width,
+                height: *height,
+            },
+            Cells::Sparse(cells) => {
+                Cells::Sparse(cells.iter().map(|(&pos, value)| (pos, f(value))).collect())
+            }
+        };
+
+        Grid { cells }
+    }
+
+    /// Text of the grid within its bounds, one line per row, `cell` giving the character
+    /// of each position (`None` for the empty cells of a sparse grid)
+    pub fn render<F: FnMut(Option<&T>) -> char>(&self, mut cell: F) -> String {
+        let Some((min, max)) = self.bounds() else {
+            return String::new();
+        };
+
+        (min.y..=max.y)
+            .map(|y| {
+                (min.x..=max.x)
+                    .map(|x| cell(self.get(Pos::new(x, y))))
+                    .collect()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+/// Index of `pos` in the cells of a dense grid of `width` x `height`
+fn dense_index(width: usize, height: usize, pos: Pos) -> Option<usize> {
+    let Point2 { x, y } = pos;
+
+    if x < 0 || y < 0 || x as usize >= width || y as usize >= height {
+        None
+    } else {
+        Some(y as usize * width + x as usize)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        match self.get(pos) {
+            Some(value) => value,
+            None => panic!("no value at {:?}", pos),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        match self.get_mut(pos) {
+            Some(value) => value,
+            None => panic!("no value at {:?}", pos),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Grid<T> {
+    type Item = (Pos, &'a T);
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the values of a grid with their positions
+pub enum Iter<'a, T> {
+    Dense {
+        cells: Enumerate<slice::Iter<'a, T>>,
+        width: usize,
+    },
+    Sparse(hash_map::Iter<'a, Pos, T>),
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (Pos, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Iter::Dense { cells, width } => cells.next().map(|(index, value)| {
+                (
+                    Pos::new((index % *width) as isize, (index / *width) as isize),
+                    value,
+                )
+            }),
+            Iter::Sparse(cells) => cells.next().map(|(&pos, value)| (pos, value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Grid<u32> {
+        Grid::parse("123\n456\n789\n", |_, c| c.to_digit(10).ok_or(c)).unwrap()
+    }
+
+    #[test]
+    fn parse_dense() {
+        let grid = digits();
+
+        assert!(grid.is_dense());
+        assert_eq!(grid.len(), 9);
+        assert_eq!(grid.bounds(), Some((Pos::new(0, 0), Pos::new(2, 2))));
+        assert_eq!(grid.get(Pos::new(1, 2)), Some(&8));
+        assert_eq!(grid[Pos::new(2, 0)], 3);
+    }
+
+    #[test]
+    fn from_rows() {
+        let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+
+        assert_eq!(grid, Some(digits()));
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), None);
+    }
+
+    #[test]
+    fn get_out_of_bounds() {
+        let grid = digits();
+
+        assert_eq!(grid.get(Pos::new(-1, 0)), None);
+        assert_eq!(grid.get(Pos::new(3, 0)), None);
+        assert_eq!(grid.get(Pos::new(0, 3)), None);
+    }
+
+    #[test]
+    fn parse_invalid_cell() {
+        let error = Grid::parse("123\n4x6\n", |_, c| c.to_digit(10).ok_or(c)).unwrap_err();
+
+        assert_eq!((error.line(), error.column()), (2, 2));
+        assert_eq!(error.error(), &GridError::Cell('x'));
+    }
+
+    #[test]
+    fn parse_invalid_width() {
+        let error = Grid::parse("123\n45\n", |_, c| c.to_digit(10).ok_or(c)).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: expected 3 cells in the row, found 2"
+        );
+    }
+
+    #[test]
+    fn parse_empty() {
+        let error = Grid::parse("", |_, c| c.to_digit(10).ok_or(c)).unwrap_err();
+
+        assert_eq!(error.error(), &GridError::Empty);
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = digits();
+
+        let corner: Vec<u32> = grid.neighbours4(Pos::new(0, 0)).map(|(_, &v)| v).collect();
+        let center: Vec<u32> = grid.neighbours8(Pos::new(1, 1)).map(|(_, &v)| v).collect();
+
+        assert_eq!(corner, vec![2, 4]);
+        assert_eq!(center, vec![2, 3, 6, 9, 8, 7, 4, 1]);
+    }
+
+    #[test]
+    fn rows_columns_and_rays() {
+        let grid = digits();
+
+        let row: Vec<u32> = grid.row(1).map(|(_, &v)| v).collect();
+        let column: Vec<u32> = grid.column(2).map(|(_, &v)| v).collect();
+        let ray: Vec<u32> = grid
+            .ray(Pos::new(2, 2), Pos::new(-1, -1))
+            .map(|(_, &v)| v)
+            .collect();
+
+        assert_eq!(row, vec![4, 5, 6]);
+        assert_eq!(column, vec![3, 6, 9]);
+        assert_eq!(ray, vec![5, 1]);
+        assert_eq!(grid.ray(Pos::new(0, 1), LEFT).count(), 0);
+    }
+
+    #[test]
+    fn iter_row_by_row() {
+        let grid = digits();
+
+        let cells: Vec<(Pos, u32)> = grid.iter().take(4).map(|(p, &v)| (p, v)).collect();
+
+        assert_eq!(
+            cells,
+            vec![
+                (Pos::new(0, 0), 1),
+                (Pos::new(1, 0), 2),
+                (Pos::new(2, 0), 3),
+                (Pos::new(0, 1), 4)
+            ]
+        );
+    }
+
+    #[test]
+    fn sparse_grid() {
+        let mut grid = Grid::sparse();
+
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(Pos::new(-2, 5), 'a');
+        grid.insert(Pos::new(3, -1), 'b');
+
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.bounds(), Some((Pos::new(-2, -1), Pos::new(3, 5))));
+        assert_eq!((grid.width(), grid.height()), (6, 7));
+        assert_eq!(grid.remove(Pos::new(3, -1)), Some('b'));
+        assert!(!grid.contains(Pos::new(3, -1)));
+    }
+
+    #[test]
+    fn parse_sparse_and_render() {
+        let input = "..#\n#..\n.#.";
+        let grid = Grid::parse_sparse(input, |_, c| match c {
+            '#' => Ok(Some(())),
+            '.' => Ok(None),
+            _ => Err(c),
+        })
+        .unwrap();
+
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.neighbours8(Pos::new(1, 1)).count(), 3);
+        assert_eq!(
+            grid.render(|cell| if cell.is_some() { '#' } else { '.' }),
+            input
+        );
+    }
+
+    #[test]
+    fn insert_and_map_dense() {
+        let mut grid = Grid::new(2, 2, 0);
+
+        assert_eq!(grid.insert(Pos::new(1, 1), 5), Some(0));
+
+        let doubled = grid.map(|v| v * 2);
+
+        assert_eq!(doubled[Pos::new(1, 1)], 10);
+        assert_eq!(
+            doubled.render(|v| char::from_digit(*v.unwrap(), 16).unwrap()),
+            "00\n0a"
+        );
+    }
+}
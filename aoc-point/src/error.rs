@@ -0,0 +1,36 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading a point written as "x,y" or "x,y,z"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParsePointError {
+    /// A point without one coordinate per dimension
+    InvalidCount { expected: usize, found: usize },
+    /// A coordinate that isn't a number of the coordinate type, starting at `column` (from 1)
+    InvalidCoordinate { column: usize, found: String },
+}
+
+impl ParsePointError {
+    /// Column (starting at 1) of the error in the point
+    pub fn column(&self) -> usize {
+        match self {
+            ParsePointError::InvalidCount { .. } => 1,
+            ParsePointError::InvalidCoordinate { column, .. } => *column,
+        }
+    }
+}
+
+impl Display for ParsePointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParsePointError::InvalidCount { expected, found } => {
+                write!(f, "expected {} coordinates, found {}", expected, found)
+            }
+            ParsePointError::InvalidCoordinate { found, .. } => {
+                write!(f, "expected a coordinate, found '{}'", found)
+            }
+        }
+    }
+}
+
+impl Error for ParsePointError {}
@@ -0,0 +1,448 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+pub mod error;
+
+use crate::error::ParsePointError;
+
+/// Integer usable as the coordinate of a point
+pub trait Coordinate: Copy + Ord + Add<Output = Self> + Sub<Output = Self> + FromStr {
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! coordinate {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+coordinate!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Offsets to the 4 orthogonal neighbours of a 2D point, clockwise from (0, -1)
+const OFFSETS4: [(i8, i8); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets to the 8 neighbours of a 2D point including the diagonals, clockwise from (0, -1)
+const OFFSETS8: [(i8, i8); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// Offsets to the 6 neighbours of a 3D point sharing a face with it
+const OFFSETS6: [(i8, i8, i8); 6] = [
+    (1, 0, 0),
+    (-1, 0, 0),
+    (0, 1, 0),
+    (0, -1, 0),
+    (0, 0, 1),
+    (0, 0, -1),
+];
+
+/// A point of the plane
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T: Coordinate> Point2<T> {
+    /// Sum of the distances along each axis
+    pub fn manhattan(&self, other: &Self) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y)
+    }
+
+    /// Largest of the distances along each axis, the number of king moves between both points
+    pub fn chebyshev(&self, other: &Self) -> T {
+        distance(self.x, other.x).max(distance(self.y, other.y))
+    }
+
+    /// The 4 orthogonal neighbours, clockwise from (x, y - 1), leaving out those that overflow `T`
+    pub fn neighbours4(&self) -> impl Iterator<Item = Self> {
+        let point = *self;
+
+        OFFSETS4
+            .into_iter()
+            .filter_map(move |(dx, dy)| point.offset(dx, dy))
+    }
+
+    /// The 8 neighbours including the diagonals, clockwise from (x, y - 1), leaving out those that overflow `T`
+    pub fn neighbours8(&self) -> impl Iterator<Item = Self> {
+        let point = *self;
+
+        OFFSETS8
+            .into_iter()
+            .filter_map(move |(dx, dy)| point.offset(dx, dy))
+    }
+
+    fn offset(&self, dx: i8, dy: i8) -> Option<Self> {
+        Some(Point2::new(step(self.x, dx)?, step(self.y, dy)?))
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T: FromStr> FromStr for Point2<T> {
+    type Err = ParsePointError;
+
+    /// Read a point written as "x,y"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y] = coordinates(s)?;
+
+        Ok(Point2::new(x, y))
+    }
+}
+
+/// A point of the space
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Coordinate> Point3<T> {
+    /// Sum of the distances along each axis
+    pub fn manhattan(&self, other: &Self) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y) + distance(self.z, other.z)
+    }
+
+    /// Largest of the distances along each axis
+    pub fn chebyshev(&self, other: &Self) -> T {
+        distance(self.x, other.x)
+            .max(distance(self.y, other.y))
+            .max(distance(self.z, other.z))
+    }
+
+    /// The 6 neighbours sharing a face, leaving out those that overflow `T`
+    pub fn neighbours6(&self) -> impl Iterator<Item = Self> {
+        let point = *self;
+
+        OFFSETS6
+            .into_iter()
+            .filter_map(move |(dx, dy, dz)| point.offset(dx, dy, dz))
+    }
+
+    /// The 26 neighbours sharing a face, an edge or a corner, leaving out those that overflow `T`
+    pub fn neighbours26(&self) -> impl Iterator<Item = Self> {
+        let point = *self;
+
+        (-1..=1)
+            .flat_map(|dx| (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| (dx, dy, dz))))
+            .filter(|&offset| offset != (0, 0, 0))
+            .filter_map(move |(dx, dy, dz)| point.offset(dx, dy, dz))
+    }
+
+    fn offset(&self, dx: i8, dy: i8, dz: i8) -> Option<Self> {
+        Some(Point3::new(
+            step(self.x, dx)?,
+            step(self.y, dy)?,
+            step(self.z, dz)?,
+        ))
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+impl<T: FromStr> FromStr for Point3<T> {
+    type Err = ParsePointError;
+
+    /// Read a point written as "x,y,z"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y, z] = coordinates(s)?;
+
+        Ok(Point3::new(x, y, z))
+    }
+}
+
+/// Distance between two coordinates, without going below zero for unsigned types
+fn distance<T: Coordinate>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+/// Coordinate one unit away from `value` in the direction of the sign of `delta`
+fn step<T: Coordinate>(value: T, delta: i8) -> Option<T> {
+    match delta.cmp(&0) {
+        Ordering::Less => value.checked_sub(T::ONE),
+        Ordering::Equal => Some(value),
+        Ordering::Greater => value.checked_add(T::ONE),
+    }
+}
+
+/// Read the `N` comma separated coordinates of a point
+fn coordinates<T: FromStr, const N: usize>(s: &str) -> Result<[T; N], ParsePointError> {
+    let values: Vec<&str> = s.split(',').collect();
+
+    if values.len() != N {
+        return Err(ParsePointError::InvalidCount {
+            expected: N,
+            found: values.len(),
+        });
+    }
+
+    let mut column = 1;
+    let mut coordinates = Vec::with_capacity(N);
+
+    for value in values {
+        let coordinate = value
+            .parse()
+            .map_err(|_| ParsePointError::InvalidCoordinate {
+                column,
+                found: value.to_string(),
+            })?;
+
+        coordinates.push(coordinate);
+        column += value.chars().count() + 1;
+    }
+
+    match coordinates.try_into() {
+        Ok(coordinates) => Ok(coordinates),
+        Err(_) => unreachable!("a coordinate was read for each value"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_and_sub() {
+        let a = Point2::new(2, 3);
+        let b = Point2::new(-1, 5);
+
+        assert_eq!(a + b, Point2::new(1, 8));
+        assert_eq!(a - b, Point2::new(3, -2));
+        assert_eq!(-a, Point2::new(-2, -3));
+        assert_eq!(
+            Point3::new(1, 2, 3) + Point3::new(1, 1, 1),
+            Point3::new(2, 3, 4)
+        );
+    }
+
+    #[test]
+    fn add_assign() {
+        let mut a = Point2::new(0, 0);
+
+        a += Point2::new(1, -1);
+        a += Point2::new(1, -1);
+        a -= Point2::new(0, 1);
+
+        assert_eq!(a, Point2::new(2, -3));
+    }
+
+    #[test]
+    fn manhattan() {
+        assert_eq!(Point2::new(2, 18).manhattan(&Point2::new(-2, 15)), 7);
+        assert_eq!(Point2::new(0usize, 5).manhattan(&Point2::new(3, 1)), 7);
+        assert_eq!(Point3::new(1, 1, 1).manhattan(&Point3::new(2, 0, 3)), 4);
+    }
+
+    #[test]
+    fn chebyshev() {
+        assert_eq!(Point2::new(2, 2).chebyshev(&Point2::new(2, 2)), 0);
+        assert_eq!(Point2::new(2, 2).chebyshev(&Point2::new(0, 3)), 2);
+        assert_eq!(Point3::new(1, 1, 1).chebyshev(&Point3::new(2, 0, 4)), 3);
+    }
+
+    #[test]
+    fn neighbours4() {
+        let neighbours: Vec<_> = Point2::new(1, 1).neighbours4().collect();
+
+        assert_eq!(
+            neighbours,
+            vec![
+                Point2::new(1, 0),
+                Point2::new(2, 1),
+                Point2::new(1, 2),
+                Point2::new(0, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn neighbours8() {
+        let point = Point2::new(-4, 7);
+
+        assert_eq!(point.neighbours8().count(), 8);
+        assert!(point.neighbours8().all(|n| n.chebyshev(&point) == 1));
+    }
+
+    #[test]
+    fn neighbours_overflow() {
+        assert_eq!(Point2::new(0usize, 0).neighbours4().count(), 2);
+        assert_eq!(Point2::new(0usize, 0).neighbours8().count(), 3);
+        assert_eq!(Point3::new(0u8, 0, 0).neighbours6().count(), 3);
+    }
+
+    #[test]
+    fn neighbours3d() {
+        let point = Point3::new(1, 2, 3);
+
+        assert_eq!(point.neighbours6().count(), 6);
+        assert!(point.neighbours6().all(|n| n.manhattan(&point) == 1));
+        assert_eq!(point.neighbours26().count(), 26);
+        assert!(point.neighbours26().all(|n| n.chebyshev(&point) == 1));
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("498,4".parse(), Ok(Point2::new(498, 4)));
+        assert_eq!("-2,15".parse(), Ok(Point2::new(-2, 15)));
+        assert_eq!("5,2,12".parse(), Ok(Point3::new(5, 2, 12)));
+    }
+
+    #[test]
+    fn parse_invalid_count() {
+        assert_eq!(
+            "1,2,3".parse::<Point2<i32>>(),
+            Err(ParsePointError::InvalidCount {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            "12".parse::<Point3<i32>>().unwrap_err().to_string(),
+            "expected 3 coordinates, found 1"
+        );
+    }
+
+    #[test]
+    fn parse_invalid_coordinate() {
+        let error = "2,1,300".parse::<Point3<i8>>().unwrap_err();
+
+        assert_eq!(error.column(), 5);
+        assert_eq!(error.to_string(), "expected a coordinate, found '300'");
+    }
+
+    #[test]
+    fn display() {
+        let point = Point2::new(498, 6);
+
+        assert_eq!(point.to_string().parse(), Ok(point));
+        assert_eq!(Point3::new(1, -2, 3).to_string(), "1,-2,3");
+    }
+}
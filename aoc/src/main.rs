@@ -0,0 +1,299 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Read};
+use std::process;
+use std::str::FromStr;
+
+use aoc_core::Solution;
+
+const USAGE: &str = "Usage: aoc --day <N> [--part <1|2|both>] [--input <PATH>]";
+
+const SOLVED_DAYS: [u32; 25] = [
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+];
+
+fn main() {
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(ArgsError::Help) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    // Read the whole input once so that both parts can be run on it
+    let input = match read_input(args.input.as_deref()) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let parts = match args.part {
+        Part::One => &[Part::One][..],
+        Part::Two => &[Part::Two],
+        Part::Both => &[Part::One, Part::Two],
+    };
+
+    for &part in parts {
+        if let Err(e) = run(args.day, part, &input) {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Read the input from the file, or from the standard input without one
+fn read_input(path: Option<&str>) -> io::Result<String> {
+    match path {
+        Some(path) => fs::read_to_string(path),
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+    }
+}
+
+fn run(day: u32, part: Part, input: &str) -> Result<(), Box<dyn Error>> {
+    let answer = match (day, part) {
+        (1, Part::One) => day_1::part_one::PartOne::solve(input),
+        (1, Part::Two) => day_1::part_two::PartTwo::solve(input),
+        (2, Part::One) => day_2::part_one::PartOne::solve(input),
+        (2, Part::Two) => day_2::part_two::PartTwo::solve(input),
+        (3, Part::One) => day_3::part1::PartOne::solve(input),
+        (3, Part::Two) => day_3::part2::PartTwo::solve(input),
+        (4, Part::One) => day_4::part_one::PartOne::solve(input),
+        (4, Part::Two) => day_4::part_two::PartTwo::solve(input),
+        (5, Part::One) => day_5::part_one::PartOne::solve(input),
+        (5, Part::Two) => day_5::part_two::PartTwo::solve(input),
+        (6, Part::One) => day_6::part_one::PartOne::solve(input),
+        (6, Part::Two) => day_6::part_two::PartTwo::solve(input),
+        (7, Part::One) => day_7::part_one::PartOne::solve(input),
+        (7, Part::Two) => day_7::part_two::PartTwo::solve(input),
+        (8, Part::One) => day_8::part_one::PartOne::solve(input),
+        (8, Part::Two) => day_8::part_two::PartTwo::solve(input),
+        (9, Part::One) => day_9::part_one::PartOne::solve(input),
+        (9, Part::Two) => day_9::part_two::PartTwo::solve(input),
+        (10, Part::One) => day_10::part_one::PartOne::solve(input),
+        (10, Part::Two) => day_10::part_two::PartTwo::solve(input),
+        (11, Part::One) => day_11::part_one::PartOne::solve(input),
+        (11, Part::Two) => day_11::part_two::PartTwo::solve(input),
+        (12, Part::One) => day_12::part_one::PartOne::solve(input),
+        (12, Part::Two) => day_12::part_two::PartTwo::solve(input),
+        (13, Part::One) => day_13::part_one::PartOne::solve(input),
+        (13, Part::Two) => day_13::part_two::PartTwo::solve(input),
+        (14, Part::One) => day_14::part_one::PartOne::solve(input),
+        (14, Part::Two) => day_14::part_two::PartTwo::solve(input),
+        (15, Part::One) => day_15::part_one::PartOne::solve(input),
+        (15, Part::Two) => day_15::part_two::PartTwo::solve(input),
+        (16, Part::One) => day_16::part_one::PartOne::solve(input),
+        (16, Part::Two) => day_16::part_two::PartTwo::solve(input),
+        (17, Part::One) => day_17::part_one::PartOne::solve(input),
+        (17, Part::Two) => day_17::part_two::PartTwo::solve(input),
+        (18, Part::One) => day_18::part_one::PartOne::solve(input),
+        (18, Part::Two) => day_18::part_two::PartTwo::solve(input),
+        (19, Part::One) => day_19::part_one::PartOne::solve(input),
+        (19, Part::Two) => day_19::part_two::PartTwo::solve(input),
+        (20, Part::One) => day_20::part_one::PartOne::solve(input),
+        (20, Part::Two) => day_20::part_two::PartTwo::solve(input),
+        (21, Part::One) => day_21::part_one::PartOne::solve(input),
+        (21, Part::Two) => day_21::part_two::PartTwo::solve(input),
+        (22, Part::One) => day_22::part_one::PartOne::solve(input),
+        (22, Part::Two) => day_22::part_two::PartTwo::solve(input),
+        (23, Part::One) => day_23::part_one::PartOne::solve(input),
+        (23, Part::Two) => day_23::part_two::PartTwo::solve(input),
+        (24, Part::One) => day_24::part_one::PartOne::solve(input),
+        (24, Part::Two) => day_24::part_two::PartTwo::solve(input),
+        (25, Part::One) => day_25::part_one::PartOne::solve(input),
+        (25, Part::Two) => day_25::part_two::PartTwo::solve(input),
+        (day, _) => return Err(Box::new(ArgsError::UnknownDay(day))),
+    }?;
+
+    let part = if part == Part::One { 1 } else { 2 };
+    let answer = answer.to_string();
+
+    // Multi-line answers (like a CRT screen) are easier to read on their own lines
+    if answer.contains('\n') {
+        println!("Day {} - Part {}:\n{}", day, part, answer);
+    } else {
+        println!("Day {} - Part {}: {}", day, part, answer);
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part {
+    One,
+    Two,
+    Both,
+}
+
+impl FromStr for Part {
+    type Err = ArgsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            "both" => Ok(Part::Both),
+            _ => Err(ArgsError::InvalidValue("--part", s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Args {
+    day: u32,
+    part: Part,
+    input: Option<String>,
+}
+
+impl Args {
+    fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Self, ArgsError> {
+        let mut day = None;
+        let mut part = Part::Both;
+        let mut input = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Err(ArgsError::Help),
+                "-d" | "--day" => {
+                    let value = args.next().ok_or(ArgsError::MissingValue("--day"))?;
+                    let value = value
+                        .parse()
+                        .map_err(|_| ArgsError::InvalidValue("--day", value))?;
+                    day = Some(value);
+                }
+                "-p" | "--part" => {
+                    let value = args.next().ok_or(ArgsError::MissingValue("--part"))?;
+                    part = value.parse()?;
+                }
+                "-i" | "--input" => {
+                    input = Some(args.next().ok_or(ArgsError::MissingValue("--input"))?);
+                }
+                _ => return Err(ArgsError::UnknownArgument(arg)),
+            }
+        }
+
+        let day = day.ok_or(ArgsError::MissingDay)?;
+        if !SOLVED_DAYS.contains(&day) {
+            return Err(ArgsError::UnknownDay(day));
+        }
+
+        Ok(Args { day, part, input })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ArgsError {
+    Help,
+    MissingDay,
+    MissingValue(&'static str),
+    InvalidValue(&'static str, String),
+    UnknownArgument(String),
+    UnknownDay(u32),
+}
+
+impl Display for ArgsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArgsError::Help => write!(f, "{}", USAGE),
+            ArgsError::MissingDay => write!(f, "Missing required argument --day"),
+            ArgsError::MissingValue(arg) => write!(f, "Missing value for argument {}", arg),
+            ArgsError::InvalidValue(arg, value) => {
+                write!(f, "Invalid value '{}' for argument {}", value, arg)
+            }
+            ArgsError::UnknownArgument(arg) => write!(f, "Unknown argument '{}'", arg),
+            ArgsError::UnknownDay(day) => write!(f, "Day {} is not solved yet", day),
+        }
+    }
+}
+
+impl Error for ArgsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, ArgsError> {
+        Args::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn day_only() {
+        let args = parse(&["--day", "7"]);
+
+        assert_eq!(
+            args,
+            Ok(Args {
+                day: 7,
+                part: Part::Both,
+                input: None
+            })
+        );
+    }
+
+    #[test]
+    fn day_part_and_input() {
+        let args = parse(&["--day", "12", "--part", "2", "--input", "day_12/input.txt"]);
+
+        assert_eq!(
+            args,
+            Ok(Args {
+                day: 12,
+                part: Part::Two,
+                input: Some(String::from("day_12/input.txt"))
+            })
+        );
+    }
+
+    #[test]
+    fn short_arguments() {
+        let args = parse(&["-p", "1", "-d", "3"]);
+
+        assert_eq!(
+            args,
+            Ok(Args {
+                day: 3,
+                part: Part::One,
+                input: None
+            })
+        );
+    }
+
+    #[test]
+    fn missing_day() {
+        assert_eq!(parse(&["--part", "1"]), Err(ArgsError::MissingDay));
+    }
+
+    #[test]
+    fn missing_value() {
+        assert_eq!(parse(&["--day"]), Err(ArgsError::MissingValue("--day")));
+    }
+
+    #[test]
+    fn invalid_part() {
+        assert_eq!(
+            parse(&["--day", "1", "--part", "3"]),
+            Err(ArgsError::InvalidValue("--part", String::from("3")))
+        );
+    }
+
+    #[test]
+    fn unknown_day() {
+        let error = parse(&["--day", "26"]).unwrap_err();
+
+        assert_eq!(error, ArgsError::UnknownDay(26));
+        assert_eq!(error.to_string(), "Day 26 is not solved yet");
+    }
+}
@@ -0,0 +1,21 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading the calories list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The line isn't a number of calories
+    InvalidCalories(String),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidCalories(found) => {
+                write!(f, "expected a number of calories, found '{}'", found)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
@@ -1,21 +1,29 @@
-use std::error::Error;
-use std::io::{self, BufRead, Lines};
+use std::io::{BufRead, Lines};
+use std::iter::Enumerate;
 
-pub fn main_p1() -> Result<(), Box<dyn Error>> {
-    let mut lines = io::stdin().lines();
+use aoc_core::{Answer, Locate, Located, Solution, SolveError};
 
-    let calories = parse_max_calories(&mut lines);
+use crate::error::ParseError;
 
-    println!("Max calories: {}", calories);
+pub struct PartOne;
 
-    Ok(())
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut lines = input.as_bytes().lines().enumerate();
+
+        let calories = parse_max_calories(&mut lines)?;
+
+        Ok(calories.into())
+    }
 }
 
-fn parse_max_calories<B: BufRead>(lines: &mut Lines<B>) -> u32 {
+fn parse_max_calories<B: BufRead>(
+    lines: &mut Enumerate<Lines<B>>,
+) -> Result<u32, Located<ParseError>> {
     let mut max_calories: u32 = 0;
 
     loop {
-        let calories = parse_calories(lines);
+        let calories = parse_calories(lines)?;
         max_calories = max_calories.max(calories);
 
         if calories == 0 {
@@ -23,35 +31,61 @@ fn parse_max_calories<B: BufRead>(lines: &mut Lines<B>) -> u32 {
         }
     }
 
-    max_calories
+    Ok(max_calories)
 }
 
-fn parse_calories<B: BufRead>(lines: &mut Lines<B>) -> u32 {
+fn parse_calories<B: BufRead>(lines: &mut Enumerate<Lines<B>>) -> Result<u32, Located<ParseError>> {
     let mut calories: u32 = 0;
 
-    for line in lines {
+    for (index, line) in lines {
+        // The lines come from a `&str`, they can't hold invalid UTF-8
         let line = line.unwrap();
 
         if line.is_empty() {
             break; // Empty line between two elves
         }
 
-        calories += line.parse::<u32>().unwrap();
+        calories += line
+            .parse::<u32>()
+            .map_err(|_| ParseError::InvalidCalories(line.clone()))
+            .at(index + 1, 1)?;
     }
 
-    calories
+    Ok(calories)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io;
+
+    #[test]
+    fn example() {
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(24000u32));
+    }
+
+    #[test]
+    fn invalid_calories() {
+        let calories = String::from("20\n\n4O\n60");
+        let line = io::IoSlice::new(calories.as_bytes());
+
+        let error = parse_max_calories(&mut line.lines().enumerate()).unwrap_err();
+
+        assert_eq!(error.line(), 3);
+        assert_eq!(
+            error.error(),
+            &ParseError::InvalidCalories(String::from("4O"))
+        );
+    }
 
     #[test]
     fn zero_calory() {
         let zero = String::from("0");
         let line = io::IoSlice::new(zero.as_bytes());
 
-        assert_eq!(parse_calories(&mut line.lines()), 0);
+        assert_eq!(parse_calories(&mut line.lines().enumerate()), Ok(0));
     }
 
     #[test]
@@ -59,7 +93,7 @@ mod tests {
         let calory = String::from("1000");
         let line = io::IoSlice::new(calory.as_bytes());
 
-        assert_eq!(parse_calories(&mut line.lines()), 1000);
+        assert_eq!(parse_calories(&mut line.lines().enumerate()), Ok(1000));
     }
 
     #[test]
@@ -67,7 +101,7 @@ mod tests {
         let calories = String::from("20\n50\n30");
         let line = io::IoSlice::new(calories.as_bytes());
 
-        assert_eq!(parse_calories(&mut line.lines()), 100);
+        assert_eq!(parse_calories(&mut line.lines().enumerate()), Ok(100));
     }
 
     #[test]
@@ -75,7 +109,7 @@ mod tests {
         let calories = String::from("20\n50\n30\n\n40\n60");
         let line = io::IoSlice::new(calories.as_bytes());
 
-        assert_eq!(parse_max_calories(&mut line.lines()), 100);
+        assert_eq!(parse_max_calories(&mut line.lines().enumerate()), Ok(100));
     }
 
     #[test]
@@ -83,7 +117,7 @@ mod tests {
         let calories = String::from("20\n50\n30\n\n40\n60\n10");
         let line = io::IoSlice::new(calories.as_bytes());
 
-        assert_eq!(parse_max_calories(&mut line.lines()), 110);
+        assert_eq!(parse_max_calories(&mut line.lines().enumerate()), Ok(110));
     }
 
     #[test]
@@ -91,6 +125,6 @@ mod tests {
         let calories = String::from("20\n50\n30\n\n40\n\n10");
         let line = io::IoSlice::new(calories.as_bytes());
 
-        assert_eq!(parse_max_calories(&mut line.lines()), 100);
+        assert_eq!(parse_max_calories(&mut line.lines().enumerate()), Ok(100));
     }
 }
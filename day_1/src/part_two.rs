@@ -1,23 +1,36 @@
 use std::collections::BinaryHeap;
-use std::error::Error;
-use std::io::{self, BufRead, Lines};
+use std::io::{BufRead, Lines};
+use std::iter::Enumerate;
 
-pub fn main_p2() -> Result<(), Box<dyn Error>> {
-    let mut lines = io::stdin().lines();
+use aoc_core::{Answer, Locate, Located, Solution, SolveError};
 
-    let calories = parse_max_calories(&mut lines);
+use crate::error::ParseError;
 
-    assert_eq!(calories.len(), 3);
-    println!("Max calories: {}", calories.iter().sum::<u32>());
+pub struct PartTwo;
 
-    Ok(())
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut lines = input.as_bytes().lines().enumerate();
+
+        let calories = parse_max_calories(&mut lines)?;
+
+        if calories.len() < 3 {
+            return Err(SolveError::NoSolution(String::from(
+                "there are less than three elves",
+            )));
+        }
+
+        Ok(calories.iter().sum::<u32>().into())
+    }
 }
 
-fn parse_max_calories<B: BufRead>(lines: &mut Lines<B>) -> Vec<u32> {
+fn parse_max_calories<B: BufRead>(
+    lines: &mut Enumerate<Lines<B>>,
+) -> Result<Vec<u32>, Located<ParseError>> {
     let mut heap = BinaryHeap::<u32>::new();
 
     loop {
-        let calories = parse_calories(lines);
+        let calories = parse_calories(lines)?;
         heap.push(calories);
 
         if calories == 0 {
@@ -28,38 +41,62 @@ fn parse_max_calories<B: BufRead>(lines: &mut Lines<B>) -> Vec<u32> {
     let mut max_calories = Vec::new();
 
     for _ in 0..3 {
-        max_calories.push(heap.pop().unwrap());
+        if let Some(calories) = heap.pop() {
+            max_calories.push(calories);
+        }
     }
 
-    max_calories
+    Ok(max_calories)
 }
 
-fn parse_calories<B: BufRead>(lines: &mut Lines<B>) -> u32 {
+fn parse_calories<B: BufRead>(lines: &mut Enumerate<Lines<B>>) -> Result<u32, Located<ParseError>> {
     let mut calories: u32 = 0;
 
-    for line in lines {
+    for (index, line) in lines {
+        // The lines come from a `&str`, they can't hold invalid UTF-8
         let line = line.unwrap();
 
         if line.is_empty() {
             break; // Empty line between two elves
         }
 
-        calories += line.parse::<u32>().unwrap();
+        calories += line
+            .parse::<u32>()
+            .map_err(|_| ParseError::InvalidCalories(line.clone()))
+            .at(index + 1, 1)?;
     }
 
-    calories
+    Ok(calories)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io;
+
+    #[test]
+    fn example() {
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(45000u32));
+    }
+
+    #[test]
+    fn invalid_calories_line() {
+        let error = PartTwo::solve("1000\n\n2000\nabc\n").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid input: line 4, column 1: expected a number of calories, found 'abc'"
+        );
+    }
 
     #[test]
     fn zero_calory() {
         let zero = String::from("0");
         let line = io::IoSlice::new(zero.as_bytes());
 
-        assert_eq!(parse_calories(&mut line.lines()), 0);
+        assert_eq!(parse_calories(&mut line.lines().enumerate()), Ok(0));
     }
 
     #[test]
@@ -67,7 +104,7 @@ mod tests {
         let calory = String::from("1000");
         let line = io::IoSlice::new(calory.as_bytes());
 
-        assert_eq!(parse_calories(&mut line.lines()), 1000);
+        assert_eq!(parse_calories(&mut line.lines().enumerate()), Ok(1000));
     }
 
     #[test]
@@ -75,7 +112,7 @@ mod tests {
         let calories = String::from("20\n50\n30");
         let line = io::IoSlice::new(calories.as_bytes());
 
-        assert_eq!(parse_calories(&mut line.lines()), 100);
+        assert_eq!(parse_calories(&mut line.lines().enumerate()), Ok(100));
     }
 
     #[test]
@@ -83,7 +120,10 @@ mod tests {
         let calories = String::from("20\n50\n30\n\n40\n60");
         let line = io::IoSlice::new(calories.as_bytes());
 
-        assert_eq!(parse_max_calories(&mut line.lines()), 100);
+        assert_eq!(
+            parse_max_calories(&mut line.lines().enumerate()).unwrap()[0],
+            100
+        );
     }
 
     #[test]
@@ -91,7 +131,10 @@ mod tests {
         let calories = String::from("20\n50\n30\n\n40\n60\n10");
         let line = io::IoSlice::new(calories.as_bytes());
 
-        assert_eq!(parse_max_calories(&mut line.lines()), 110);
+        assert_eq!(
+            parse_max_calories(&mut line.lines().enumerate()).unwrap()[0],
+            110
+        );
     }
 
     #[test]
@@ -99,6 +142,9 @@ mod tests {
         let calories = String::from("20\n50\n30\n\n40\n\n10");
         let line = io::IoSlice::new(calories.as_bytes());
 
-        assert_eq!(parse_max_calories(&mut line.lines()), 100);
+        assert_eq!(
+            parse_max_calories(&mut line.lines().enumerate()).unwrap()[0],
+            100
+        );
     }
 }
@@ -0,0 +1,427 @@
+use std::collections::VecDeque;
+use std::iter::Fuse;
+use std::ops::Index;
+
+use crate::program::{Instruction, Operand, Register};
+
+/// Values of the registers of the CPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Registers([i32; 4]);
+
+impl Registers {
+    pub fn x(&self) -> i32 {
+        self[Register::X]
+    }
+}
+
+impl Default for Registers {
+    /// X starts at 1, the other registers at 0
+    fn default() -> Self {
+        Registers([1, 0, 0, 0])
+    }
+}
+
+impl Index<Register> for Registers {
+    type Output = i32;
+
+    fn index(&self, register: Register) -> &Self::Output {
+        &self.0[register as usize]
+    }
+}
+
+/// What an observer sees of the CPU during or after a cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct State {
+    /// Number of the cycle, starting at 1
+    pub cycle: usize,
+    pub registers: Registers,
+    /// Index of the instruction being executed, or next to execute after a cycle
+    pub instr_index: usize,
+    /// The instruction at `instr_index`, `None` once the CPU is idle
+    pub instruction: Option<Instruction>,
+}
+
+impl State {
+    pub fn x(&self) -> i32 {
+        self.registers.x()
+    }
+}
+
+/// Watches a run of the CPU, cycle by cycle
+pub trait Observer {
+    /// Called during each cycle, before the instruction has any effect
+    fn during(&mut self, _state: &State) {}
+
+    /// Called at the end of each cycle, once the instruction completed on it took effect
+    fn after(&mut self, _state: &State) {}
+}
+
+impl Observer for () {}
+
+/// Both observers watch the same run, the first one being called first
+impl<A: Observer, B: Observer> Observer for (A, B) {
+    fn during(&mut self, state: &State) {
+        self.0.during(state);
+        self.1.during(state);
+    }
+
+    fn after(&mut self, state: &State) {
+        self.0.after(state);
+        self.1.after(state);
+    }
+}
+
+impl<O: Observer + ?Sized> Observer for &mut O {
+    fn during(&mut self, state: &State) {
+        (**self).during(state)
+    }
+
+    fn after(&mut self, state: &State) {
+        (**self).after(state)
+    }
+}
+
+/// Stops a run at the end of a cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// At the end of this cycle
+    Cycle(usize),
+    /// When a cycle sets the register to the value
+    Register(Register, i32),
+}
+
+/// Why a run stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    Breakpoint(Breakpoint),
+    /// The program executed `halt`
+    Halted,
+    /// The program ran past its last instruction
+    Finished,
+    /// The program jumped back to this instruction, which was no longer kept
+    Forgotten(usize),
+}
+
+/// Number of instructions kept by default for the jumps going back
+pub const WINDOW: usize = 1 << 16;
+
+/// Executes a stream of instructions, fetching them only when they are needed.
+///
+/// The last instructions fetched are kept so that jumps can go back to them, up to a window
+/// which bounds the memory used by an endless stream. Once the program is over, the CPU stays
+/// idle and the cycles go on with the registers unchanged.
+pub struct Cpu<I> {
+    registers: Registers,
+    source: Fuse<I>,
+    /// The last instructions fetched, the first one being at index `first` of the program
+    fetched: VecDeque<Instruction>,
+    first: usize,
+    /// Maximum number of instructions in `fetched`
+    window: usize,
+    instr_index: usize,
+    /// Cycles left before the current instruction is done, 0 if it hasn't started
+    cycle_left: u8,
+    /// Number of cycles done
+    cycle: usize,
+    halted: bool,
+    breakpoints: Vec<Breakpoint>,
+}
+
+impl<I: Iterator<Item = Instruction>> Cpu<I> {
+    pub fn new<P: IntoIterator<IntoIter = I>>(program: P) -> Self {
+        Self::with_window(program, WINDOW)
+    }
+
+    /// A CPU keeping only the last `window` instructions fetched, at least one. Jumping further
+    /// back stops it with [`Stop::Forgotten`].
+    pub fn with_window<P: IntoIterator<IntoIter = I>>(program: P, window: usize) -> Self {
+        let mut cpu = Cpu {
+            registers: Registers::default(),
+            source: program.into_iter().fuse(),
+            fetched: VecDeque::new(),
+            first: 0,
+            window: window.max(1),
+            instr_index: 0,
+            cycle_left: 0,
+            cycle: 0,
+            halted: false,
+            breakpoints: Vec::new(),
+        };
+
+        cpu.fetch();
+        cpu
+    }
+
+    pub fn registers(&self) -> Registers {
+        self.registers
+    }
+
+    pub fn x(&self) -> i32 {
+        self.registers.x()
+    }
+
+    /// Number of cycles done
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.push(breakpoint);
+    }
+
+    pub fn remove_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.retain(|&b| b != breakpoint);
+    }
+
+    /// Whether the program is over, by running out of instructions, with `halt` or by jumping
+    /// back to a forgotten instruction
+    pub fn is_idle(&self) -> bool {
+        self.current().is_none()
+    }
+
+    /// Do one cycle, returning the breakpoint it hit if any
+    pub fn step(&mut self, observer: &mut impl Observer) -> Option<Breakpoint> {
+        self.cycle += 1;
+
+        let instr = self.current();
+        if let Some(instr) = instr {
+            if self.cycle_left == 0 {
+                self.cycle_left = instr.cycles();
+            }
+        }
+        observer.during(&self.state(instr));
+
+        let before = self.registers;
+        if let Some(instr) = instr {
+            self.cycle_left -= 1;
+
+            if self.cycle_left == 0 {
+                self.execute(instr);
+                self.fetch();
+            }
+        }
+        let next = self.current();
+        observer.after(&self.state(next));
+
+        self.breakpoints
+            .iter()
+            .copied()
+            .find(|&breakpoint| match breakpoint {
+                Breakpoint::Cycle(cycle) => cycle == self.cycle,
+                Breakpoint::Register(register, value) => {
+                    before[register] != value && self.registers[register] == value
+                }
+            })
+    }
+
+    /// Run until a breakpoint or the end of the program
+    pub fn run(&mut self, observer: &mut impl Observer) -> Stop {
+        loop {
+            if self.halted {
+                return Stop::Halted;
+            }
+            if self.instr_index < self.first {
+                return Stop::Forgotten(self.instr_index);
+            }
+            if self.is_idle() {
+                return Stop::Finished;
+            }
+            if let Some(breakpoint) = self.step(observer) {
+                return Stop::Breakpoint(breakpoint);
+            }
+        }
+    }
+
+    /// Run until the end of `cycle`, idle if the program is over, unless a breakpoint stops it
+    pub fn run_until(&mut self, cycle: usize, observer: &mut impl Observer) -> Option<Breakpoint> {
+        while self.cycle < cycle {
+            if let Some(breakpoint) = self.step(observer) {
+                return Some(breakpoint);
+            }
+        }
+
+        None
+    }
+
+    /// The instruction to execute, `None` once the CPU is idle
+    fn current(&self) -> Option<Instruction> {
+        if self.halted {
+            return None;
+        }
+
+        let index = self.instr_index.checked_sub(self.first)?;
+        self.fetched.get(index).copied()
+    }
+
+    /// Fetch the instructions up to the one to execute, forgetting the oldest ones beyond the
+    /// window
+    fn fetch(&mut self) {
+        while !self.halted && self.first + self.fetched.len() <= self.instr_index {
+            let Some(instr) = self.source.next() else {
+                return;
+            };
+
+            self.fetched.push_back(instr);
+            if self.fetched.len() > self.window {
+                self.fetched.pop_front();
+                self.first += 1;
+            }
+        }
+    }
+
+    fn execute(&mut self, instr: Instruction) {
+        let mut next = self.instr_index + 1;
+
+        match instr {
+            Instruction::Noop => (),
+            Instruction::Addx(value) => {
+                self.set(Register::X, self.registers.x().wrapping_add(value))
+            }
+            Instruction::Add(register, operand) => {
+                let value = self.registers[register].wrapping_add(self.value(operand));
+                self.set(register, value)
+            }
+            Instruction::Mul(register, operand) => {
+                let value = self.registers[register].wrapping_mul(self.value(operand));
+                self.set(register, value)
+            }
+            Instruction::Mov(register, operand) => self.set(register, self.value(operand)),
+            Instruction::Jmp(target) => next = target,
+            Instruction::Jnz(register, target) => {
+                if self.registers[register] != 0 {
+                    next = target;
+                }
+            }
+            Instruction::Halt => self.halted = true,
+        }
+
+        self.instr_index = next;
+    }
+
+    fn value(&self, operand: Operand) -> i32 {
+        match operand {
+            Operand::Register(register) => self.registers[register],
+            Operand::Value(value) => value,
+        }
+    }
+
+    fn set(&mut self, register: Register, value: i32) {
+        self.registers.0[register as usize] = value;
+    }
+
+    fn state(&self, instruction: Option<Instruction>) -> State {
+        State {
+            cycle: self.cycle,
+            registers: self.registers,
+            instr_index: self.instr_index,
+            instruction,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::program::Program;
+
+    /// Values of X during and after each cycle
+    #[derive(Default)]
+    struct History {
+        during: Vec<i32>,
+        after: Vec<i32>,
+    }
+
+    impl Observer for History {
+        fn during(&mut self, state: &State) {
+            self.during.push(state.x());
+        }
+
+        fn after(&mut self, state: &State) {
+            self.after.push(state.x());
+        }
+    }
+
+    fn cpu(input: &str) -> Cpu<std::vec::IntoIter<Instruction>> {
+        Cpu::new(Program::parse(input).unwrap())
+    }
+
+    #[test]
+    fn simple() {
+        let mut cpu = cpu("noop\naddx 3\naddx -5");
+        let mut history = History::default();
+
+        assert_eq!(cpu.run(&mut history), Stop::Finished);
+        assert_eq!(cpu.cycle(), 5);
+        assert_eq!(history.during, [1, 1, 1, 4, 4]);
+        assert_eq!(history.after, [1, 1, 4, 4, -1]);
+    }
+
+    #[test]
+    fn idle_after_the_end() {
+        let mut cpu = cpu("addx 3");
+
+        assert_eq!(cpu.run_until(10, &mut ()), None);
+        assert_eq!(cpu.cycle(), 10);
+        assert_eq!(cpu.x(), 4);
+        assert!(cpu.is_idle());
+    }
+
+    #[test]
+    fn loops_and_halt() {
+        // X = 1 + 2 * 3, then a halt that stops before the last addx
+        let mut cpu = cpu("mov a 3\nloop: addx 2\nadd a, -1\njnz a loop\nhalt\naddx 100");
+
+        assert_eq!(cpu.run(&mut ()), Stop::Halted);
+        assert_eq!(cpu.x(), 7);
+        assert_eq!(cpu.cycle(), 1 + 3 * 6 + 1);
+        assert_eq!(cpu.run(&mut ()), Stop::Halted);
+    }
+
+    #[test]
+    fn breakpoints() {
+        let mut cpu = cpu("addx 3\naddx 3\nmov b, x\nmul b b");
+
+        cpu.add_breakpoint(Breakpoint::Cycle(3));
+        cpu.add_breakpoint(Breakpoint::Register(Register::B, 49));
+
+        assert_eq!(cpu.run(&mut ()), Stop::Breakpoint(Breakpoint::Cycle(3)));
+        assert_eq!(cpu.x(), 4);
+        assert_eq!(
+            cpu.run(&mut ()),
+            Stop::Breakpoint(Breakpoint::Register(Register::B, 49))
+        );
+        assert_eq!(cpu.cycle(), 7);
+        assert_eq!(cpu.run(&mut ()), Stop::Finished);
+    }
+
+    #[test]
+    fn stream() {
+        // An endless program, only read as far as it runs
+        let mut cpu = Cpu::new(std::iter::repeat(Instruction::Addx(1)));
+
+        assert_eq!(cpu.run_until(1000, &mut ()), None);
+        assert_eq!(cpu.x(), 501);
+        assert_eq!(cpu.fetched.len(), 501);
+
+        let mut cpu = Cpu::with_window(std::iter::repeat(Instruction::Addx(1)), 10);
+
+        assert_eq!(cpu.run_until(1000, &mut ()), None);
+        assert_eq!(cpu.x(), 501);
+        assert_eq!(cpu.fetched.len(), 10);
+    }
+
+    #[test]
+    fn window() {
+        let program = Program::parse("start: addx 1\naddx 1\nnoop\nnoop\njmp start").unwrap();
+
+        // The jump goes 4 instructions back, 5 being kept
+        let mut cpu = Cpu::with_window(program.clone(), 5);
+        assert_eq!(cpu.run_until(100, &mut ()), None);
+        assert!(!cpu.is_idle());
+
+        let mut cpu = Cpu::with_window(program, 4);
+        assert_eq!(cpu.run(&mut ()), Stop::Forgotten(0));
+        assert_eq!(cpu.x(), 3);
+        assert!(cpu.is_idle());
+    }
+}
@@ -0,0 +1,106 @@
+use crate::cpu::{Observer, State};
+
+pub const WIDTH: usize = 40;
+pub const HEIGHT: usize = 6;
+
+/// Pixels of the screen, lit or not, row by row
+pub type Frame = [[bool; WIDTH]; HEIGHT];
+
+/// The screen, drawing a pixel during each cycle where the sprite centered on X covers it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crt {
+    pub pixels: Frame,
+}
+
+impl Default for Crt {
+    fn default() -> Self {
+        Crt {
+            pixels: [[false; WIDTH]; HEIGHT],
+        }
+    }
+}
+
+impl Observer for Crt {
+    fn during(&mut self, state: &State) {
+        // 1. Init cycle
+        let Some(index) = state.cycle.checked_sub(1).filter(|&i| i < WIDTH * HEIGHT) else {
+            return;
+        };
+        let row = index / WIDTH;
+        let position = index % WIDTH;
+
+        // 2. During cycle, in 64 bits as X can be anywhere
+        let sprite_pos = i64::from(state.x());
+
+        self.pixels[row][position] = (position as i64 - sprite_pos).abs() <= 1;
+    }
+}
+
+impl std::fmt::Display for Crt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let screen: Vec<String> = self
+            .pixels
+            .iter()
+            .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).collect())
+            .collect();
+
+        write!(f, "{}", screen.join("\n"))
+    }
+}
+
+/// Keeps the screen as it is after every cycle, to replay how it was drawn
+#[derive(Debug, Clone, Default)]
+pub struct Recorder {
+    pub crt: Crt,
+    /// The screen after each cycle, the first one being after cycle 1
+    pub frames: Vec<Frame>,
+}
+
+impl Observer for Recorder {
+    fn during(&mut self, state: &State) {
+        self.crt.during(state);
+    }
+
+    fn after(&mut self, state: &State) {
+        self.crt.after(state);
+        self.frames.push(self.crt.pixels);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::Cpu;
+    use crate::program::Program;
+
+    #[test]
+    fn extreme_x() {
+        // X goes to i32::MAX, then wraps to i32::MIN
+        let program = Program::parse("addx 2147483646\nnoop\naddx 1\nnoop").unwrap();
+        let mut recorder = Recorder::default();
+
+        Cpu::new(program).run_until(8, &mut recorder);
+
+        assert_eq!(
+            recorder.frames[7][0][..8],
+            [true, true, false, false, false, false, false, false]
+        );
+    }
+
+    #[test]
+    fn every_cycle_is_recorded() {
+        let program = Program::parse(include_str!("../simple_input.txt")).unwrap();
+        let mut recorder = Recorder::default();
+
+        Cpu::new(program).run_until(WIDTH * HEIGHT, &mut recorder);
+
+        assert_eq!(recorder.frames.len(), WIDTH * HEIGHT);
+        assert_eq!(recorder.frames.last(), Some(&recorder.crt.pixels));
+
+        // Each cycle draws its own pixel and nothing after it
+        for (index, frame) in recorder.frames.iter().enumerate() {
+            let drawn = frame.iter().flatten().skip(index + 1);
+            assert!(drawn.clone().all(|&lit| !lit), "cycle {}", index + 1);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading the CPU program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A word that isn't one of the instructions of the CPU
+    UnknownInstruction(String),
+    /// An instruction without one of its operands
+    MissingOperand {
+        instruction: String,
+        operand: &'static str,
+    },
+    /// An operand that doesn't have the kind expected by the instruction
+    InvalidOperand {
+        instruction: String,
+        operand: &'static str,
+        found: String,
+    },
+    /// Something after the last operand of an instruction
+    UnexpectedOperand(String),
+    /// A label that isn't made of letters, digits and '_', or starts with a digit
+    InvalidLabel(String),
+    /// A label defined on two instructions
+    DuplicateLabel(String),
+    /// A jump to a label that is never defined
+    UnknownLabel(String),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnknownInstruction(s) => write!(
+                f,
+                "expected an instruction (noop, addx, add, mul, mov, jmp, jnz or halt), found '{}'",
+                s
+            ),
+            ParseError::MissingOperand {
+                instruction,
+                operand,
+            } => write!(
+                f,
+                "expected a {} for {}, found nothing",
+                operand, instruction
+            ),
+            ParseError::InvalidOperand {
+                instruction,
+                operand,
+                found,
+            } => write!(
+                f,
+                "expected a {} for {}, found '{}'",
+                operand, instruction, found
+            ),
+            ParseError::UnexpectedOperand(s) => {
+                write!(f, "expected the end of the instruction, found '{}'", s)
+            }
+            ParseError::InvalidLabel(s) => write!(
+                f,
+                "expected a label (letters, digits and '_', not starting with a digit), found '{}'",
+                s
+            ),
+            ParseError::DuplicateLabel(s) => write!(f, "label '{}' is defined twice", s),
+            ParseError::UnknownLabel(s) => write!(f, "no label named '{}'", s),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Errors found while reading letters off the CRT
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    /// A screen that isn't as high as the letters
+    Height(usize),
+    /// Letters matching no glyph of the font, at these positions (starting at 1) of the text
+    /// read, where they are replaced by '?'
+    UnknownGlyphs { text: String, positions: Vec<usize> },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::Height(height) => {
+                write!(f, "expected 6 rows of pixels, found {}", height)
+            }
+            OcrError::UnknownGlyphs { text, positions } => {
+                let mut list: Vec<String> = positions.iter().map(|p| p.to_string()).collect();
+                let last = list.pop().unwrap_or_default();
+                let list = if list.is_empty() {
+                    last
+                } else {
+                    format!("{} and {}", list.join(", "), last)
+                };
+
+                let letters = if positions.len() == 1 {
+                    "letter at position"
+                } else {
+                    "letters at positions"
+                };
+                write!(
+                    f,
+                    "couldn't recognise the {} {} of '{}'",
+                    letters, list, text
+                )
+            }
+        }
+    }
+}
+
+impl Error for OcrError {}
@@ -0,0 +1,592 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::iter;
+use std::path::Path;
+
+use crate::crt::{Frame, HEIGHT, WIDTH};
+
+/// Formats a single frame can be saved in, lit pixels being black on white
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Binary portable bitmap (P4)
+    Pbm,
+    /// Grayscale PNG, left uncompressed
+    Png,
+}
+
+impl Format {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Pbm => "pbm",
+            Format::Png => "png",
+        }
+    }
+}
+
+/// Write `frame` with every pixel drawn as a square of `scale` pixels
+pub fn write_frame<W: Write>(
+    frame: &Frame,
+    format: Format,
+    scale: usize,
+    out: W,
+) -> io::Result<()> {
+    match format {
+        Format::Pbm => write_pbm(frame, scale, out),
+        Format::Png => write_png(frame, scale, out),
+    }
+}
+
+/// Save every frame in `dir`, as "frame_001.png" and so on
+pub fn save_frames(frames: &[Frame], dir: &Path, format: Format, scale: usize) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    for (index, frame) in frames.iter().enumerate() {
+        let name = format!("frame_{:03}.{}", index + 1, format.extension());
+        let file = io::BufWriter::new(fs::File::create(dir.join(name))?);
+
+        write_frame(frame, format, scale, file)?;
+    }
+
+    Ok(())
+}
+
+pub fn write_pbm<W: Write>(frame: &Frame, scale: usize, mut out: W) -> io::Result<()> {
+    let rows = upscale(frame, scale)?;
+    write!(out, "P4\n{} {}\n", WIDTH * scale, HEIGHT * scale)?;
+
+    // 8 pixels a byte, the first one in the highest bit, each row starting a new byte
+    for row in rows {
+        let bytes: Vec<u8> = row
+            .chunks(8)
+            .map(|pixels| {
+                pixels
+                    .iter()
+                    .enumerate()
+                    .fold(0, |byte, (i, &lit)| byte | (u8::from(lit) << (7 - i)))
+            })
+            .collect();
+        out.write_all(&bytes)?;
+    }
+
+    out.flush()
+}
+
+pub fn write_png<W: Write>(frame: &Frame, scale: usize, mut out: W) -> io::Result<()> {
+    let rows = upscale(frame, scale)?;
+    let (width, height) = ((WIDTH * scale) as u32, (HEIGHT * scale) as u32);
+
+    // Each row of gray levels starts with its filter, none here
+    let mut raw = Vec::new();
+    for row in rows {
+        raw.push(0);
+        raw.extend(row.iter().map(|&lit| if lit { 0 } else { 255 }));
+    }
+
+    let mut header = Vec::new();
+    header.extend(width.to_be_bytes());
+    header.extend(height.to_be_bytes());
+    // 8 bits of gray, the standard compression and filters, no interlacing
+    header.extend([8, 0, 0, 0, 0]);
+
+    out.write_all(b"\x89PNG\r\n\x1a\n")?;
+    write_chunk(&mut out, b"IHDR", &header)?;
+    write_chunk(&mut out, b"IDAT", &zlib_stored(&raw))?;
+    write_chunk(&mut out, b"IEND", &[])?;
+    out.flush()
+}
+
+/// Write the frames as an animated GIF looping forever, showing each one for `delay`
+/// hundredths of a second
+pub fn write_gif<W: Write>(
+    frames: &[Frame],
+    scale: usize,
+    delay: u16,
+    mut out: W,
+) -> io::Result<()> {
+    let (width, height) = (WIDTH * scale, HEIGHT * scale);
+    let (width, height) = match (u16::try_from(width), u16::try_from(height)) {
+        (Ok(width), Ok(height)) => (width, height),
+        _ => {
+            return Err(invalid(format!(
+                "a scale of {} is too large for a GIF",
+                scale
+            )))
+        }
+    };
+
+    out.write_all(b"GIF89a")?;
+    out.write_all(&width.to_le_bytes())?;
+    out.write_all(&height.to_le_bytes())?;
+    // A global table of 2 colors, the background being the first one
+    out.write_all(&[0b1000_0000, 0, 0])?;
+    out.write_all(&[255, 255, 255, 0, 0, 0])?;
+    // Loop forever
+    out.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+    for frame in frames {
+        let pixels: Vec<u8> = upscale(frame, scale)?
+            .into_iter()
+            .flatten()
+            .map(u8::from)
+            .collect();
+
+        out.write_all(&[0x21, 0xf9, 4, 0])?;
+        out.write_all(&delay.to_le_bytes())?;
+        out.write_all(&[0, 0])?;
+
+        out.write_all(&[0x2c, 0, 0, 0, 0])?;
+        out.write_all(&width.to_le_bytes())?;
+        out.write_all(&height.to_le_bytes())?;
+        out.write_all(&[0])?;
+
+        out.write_all(&[GIF_MIN_CODE_SIZE])?;
+        for block in lzw(&pixels, GIF_MIN_CODE_SIZE).chunks(255) {
+            out.write_all(&[block.len() as u8])?;
+            out.write_all(block)?;
+        }
+        out.write_all(&[0])?;
+    }
+
+    out.write_all(&[0x3b])?;
+    out.flush()
+}
+
+/// Smallest code size GIF allows, enough for 2 colors
+const GIF_MIN_CODE_SIZE: u8 = 2;
+/// GIF codes have at most 12 bits
+const MAX_CODE: u16 = (1 << 12) - 1;
+
+/// Rows of the frame, every pixel repeated `scale` times in both directions
+fn upscale(frame: &Frame, scale: usize) -> io::Result<Vec<Vec<bool>>> {
+    if scale == 0 {
+        return Err(invalid(String::from("the scale must be at least 1")));
+    }
+
+    Ok(frame
+        .iter()
+        .flat_map(|row| {
+            let row: Vec<bool> = row
+                .iter()
+                .flat_map(|&lit| iter::repeat_n(lit, scale))
+                .collect();
+            iter::repeat_n(row, scale)
+        })
+        .collect())
+}
+
+fn write_chunk<W: Write>(out: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    let crc = !data
+        .iter()
+        .fold(kind.iter().fold(!0, |crc, &b| crc32(crc, b)), |crc, &b| {
+            crc32(crc, b)
+        });
+
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    out.write_all(&crc.to_be_bytes())
+}
+
+/// CRC-32 table of PNG chunks, for the reversed polynomial 0xedb88320
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut n = 0;
+
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 == 1 {
+                0xedb88320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+
+    table
+}
+
+/// Add `byte` to a running CRC-32, which starts and ends inverted
+fn crc32(crc: u32, byte: u8) -> u32 {
+    CRC_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8)
+}
+
+/// zlib stream of `data` in stored deflate blocks, at most 65535 bytes each
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    // Deflate with a 32K window, and the check bits making the header a multiple of 31
+    let mut stream = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = if data.is_empty() {
+        vec![&[]]
+    } else {
+        data.chunks(u16::MAX as usize).collect()
+    };
+
+    for (index, block) in blocks.iter().enumerate() {
+        let len = block.len() as u16;
+
+        stream.push(u8::from(index + 1 == blocks.len()));
+        stream.extend(len.to_le_bytes());
+        stream.extend((!len).to_le_bytes());
+        stream.extend(*block);
+    }
+
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    stream.extend(((b << 16) | a).to_be_bytes());
+
+    stream
+}
+
+/// Codes of the variable length LZW used by GIF, packed from the lowest bit
+fn lzw(pixels: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear: u16 = 1 << min_code_size;
+    let end = clear + 1;
+
+    let mut bits = BitWriter::default();
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut width = min_code_size + 1;
+    // Last code of the table, and the first one needing another bit
+    let mut last = end;
+    let mut overflow = clear << 1;
+
+    bits.write(clear, width);
+
+    let Some((&first, rest)) = pixels.split_first() else {
+        bits.write(end, width);
+        return bits.finish();
+    };
+    let mut code = first as u16;
+
+    for &pixel in rest {
+        let key = (code, pixel);
+        if let Some(&longer) = table.get(&key) {
+            code = longer;
+            continue;
+        }
+
+        bits.write(code, width);
+        code = pixel as u16;
+
+        last += 1;
+        if last == overflow {
+            width += 1;
+            overflow <<= 1;
+        }
+
+        // The table is full, start a new one
+        if last == MAX_CODE {
+            bits.write(clear, width);
+            table.clear();
+            width = min_code_size + 1;
+            last = end;
+            overflow = clear << 1;
+            continue;
+        }
+
+        table.insert(key, last);
+    }
+
+    // The decoder adds a last code to its table on reading this one
+    bits.write(code, width);
+    last += 1;
+    if last == overflow {
+        width += 1;
+    }
+    if last == MAX_CODE {
+        bits.write(clear, width);
+        width = min_code_size + 1;
+    }
+    bits.write(end, width);
+
+    bits.finish()
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    len: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u8) {
+        self.buffer |= (code as u32) << self.len;
+        self.len += width;
+
+        while self.len >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.len -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+
+        self.bytes
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::Cpu;
+    use crate::crt::Recorder;
+    use crate::program::Program;
+
+    fn example_frames() -> Vec<Frame> {
+        let program = Program::parse(include_str!("../simple_input.txt")).unwrap();
+        let mut recorder = Recorder::default();
+
+        Cpu::new(program).run_until(WIDTH * HEIGHT, &mut recorder);
+        recorder.frames
+    }
+
+    /// Deterministic pseudo-random frame (xorshift)
+    fn noise(seed: u64) -> Frame {
+        let mut x = seed;
+        let mut frame = [[false; WIDTH]; HEIGHT];
+
+        for pixel in frame.iter_mut().flatten() {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            *pixel = x & 1 == 1;
+        }
+
+        frame
+    }
+
+    /// Decoder of the GIF variant of LZW, to check the encoder
+    fn unlzw(data: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let literals = |table: &mut Vec<Vec<u8>>| {
+            *table = (0..clear).map(|c| vec![c as u8]).collect();
+            table.extend([Vec::new(), Vec::new()]);
+        };
+
+        let mut table = Vec::new();
+        literals(&mut table);
+        let mut width = min_code_size + 1;
+        let mut previous: Option<usize> = None;
+        let mut out = Vec::new();
+        let mut bit = 0;
+
+        loop {
+            let code = (0..width as usize).fold(0, |code, i| {
+                let (byte, shift) = ((bit + i) / 8, (bit + i) % 8);
+                code | (((data[byte] >> shift) & 1) as usize) << i
+            });
+            bit += width as usize;
+
+            if code == clear {
+                literals(&mut table);
+                width = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return out;
+            }
+
+            let entry = match table.get(code) {
+                Some(entry) => entry.clone(),
+                None => {
+                    let mut entry = table[previous.unwrap()].clone();
+                    entry.push(entry[0]);
+                    entry
+                }
+            };
+            if let Some(previous) = previous.filter(|_| table.len() < 1 << 12) {
+                let mut added = table[previous].clone();
+                added.push(entry[0]);
+                table.push(added);
+            }
+
+            out.extend(&entry);
+            previous = Some(code);
+            if table.len() == 1 << width && width < 12 {
+                width += 1;
+            }
+        }
+    }
+
+    /// Frames of a GIF written by `write_gif`, with their delays
+    fn read_gif(gif: &[u8]) -> (usize, usize, Vec<(u16, Vec<u8>)>) {
+        assert_eq!(&gif[..6], b"GIF89a");
+        let width = u16::from_le_bytes([gif[6], gif[7]]) as usize;
+        let height = u16::from_le_bytes([gif[8], gif[9]]) as usize;
+
+        let mut frames = Vec::new();
+        let mut at = 13 + 6 + 19;
+        while gif[at] == 0x21 {
+            let delay = u16::from_le_bytes([gif[at + 4], gif[at + 5]]);
+            at += 8;
+
+            assert_eq!(gif[at], 0x2c);
+            at += 10;
+            let min_code_size = gif[at];
+            at += 1;
+
+            let mut data = Vec::new();
+            while gif[at] != 0 {
+                let len = gif[at] as usize;
+                data.extend(&gif[at + 1..at + 1 + len]);
+                at += 1 + len;
+            }
+            at += 1;
+
+            frames.push((delay, unlzw(&data, min_code_size)));
+        }
+
+        assert_eq!(&gif[at..], [0x3b]);
+        (width, height, frames)
+    }
+
+    #[test]
+    fn pbm() {
+        let frames = example_frames();
+        let mut pbm = Vec::new();
+
+        write_pbm(frames.last().unwrap(), 1, &mut pbm).unwrap();
+
+        assert_eq!(&pbm[..8], b"P4\n40 6\n");
+        // "##..##..##..##..##..##..##..##..##..##.."
+        assert_eq!(&pbm[8..13], [0b1100_1100; 5]);
+        assert_eq!(pbm.len(), 8 + 6 * 5);
+
+        let mut scaled = Vec::new();
+        write_pbm(frames.last().unwrap(), 3, &mut scaled).unwrap();
+        assert_eq!(&scaled[..10], b"P4\n120 18\n");
+        assert_eq!(&scaled[10..13], [0b1111_1100, 0b0000_1111, 0b1100_0000]);
+        assert_eq!(scaled.len(), 10 + 18 * 15);
+    }
+
+    #[test]
+    fn png() {
+        let frame = example_frames()[WIDTH * HEIGHT - 1];
+        let mut png = Vec::new();
+
+        write_png(&frame, 2, &mut png).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+        // Chunks with their checksums
+        let mut chunks = Vec::new();
+        let mut at = 8;
+        while at < png.len() {
+            let len = u32::from_be_bytes(png[at..at + 4].try_into().unwrap()) as usize;
+            let kind = &png[at + 4..at + 8];
+            let data = &png[at + 8..at + 8 + len];
+            let crc = u32::from_be_bytes(png[at + 8 + len..at + 12 + len].try_into().unwrap());
+
+            let expected = !kind.iter().chain(data).fold(!0, |crc, &b| crc32(crc, b));
+            assert_eq!(crc, expected);
+            chunks.push((kind.to_vec(), data.to_vec()));
+            at += 12 + len;
+        }
+
+        let kinds: Vec<&[u8]> = chunks.iter().map(|(kind, _)| kind.as_slice()).collect();
+        assert_eq!(kinds, [b"IHDR", b"IDAT", b"IEND"]);
+        assert_eq!(chunks[0].1, [0, 0, 0, 80, 0, 0, 0, 12, 8, 0, 0, 0, 0]);
+
+        // A single stored block, with its length, the gray levels and the Adler-32
+        let idat = &chunks[1].1;
+        let raw = &idat[7..idat.len() - 4];
+        assert_eq!(&idat[..3], [0x78, 0x01, 1]);
+        assert_eq!(raw.len(), 12 * (1 + 80));
+        assert_eq!(&raw[..6], [0, 0, 0, 0, 0, 255]);
+        assert_eq!(raw[81], 0);
+    }
+
+    #[test]
+    fn crc_and_adler() {
+        let crc = !b"123456789".iter().fold(!0, |crc, &b| crc32(crc, b));
+        assert_eq!(crc, 0xcbf43926);
+
+        let stream = zlib_stored(b"Wikipedia");
+        assert_eq!(&stream[stream.len() - 4..], 0x11e60398u32.to_be_bytes());
+    }
+
+    #[test]
+    fn gif() {
+        let frames = example_frames();
+        let mut gif = Vec::new();
+
+        write_gif(&frames, 2, 5, &mut gif).unwrap();
+        let (width, height, decoded) = read_gif(&gif);
+
+        assert_eq!((width, height), (80, 12));
+        assert_eq!(decoded.len(), frames.len());
+
+        for (frame, (delay, pixels)) in frames.iter().zip(&decoded) {
+            let expected: Vec<u8> = upscale(frame, 2)
+                .unwrap()
+                .into_iter()
+                .flatten()
+                .map(u8::from)
+                .collect();
+            assert_eq!(*delay, 5);
+            assert_eq!(pixels, &expected);
+        }
+    }
+
+    #[test]
+    fn lzw_table_resets() {
+        // Noise fills the table of codes several times over
+        for (seed, scale) in [(1, 1), (7, 3), (2022, 20)] {
+            let pixels: Vec<u8> = upscale(&noise(seed), scale)
+                .unwrap()
+                .into_iter()
+                .flatten()
+                .map(u8::from)
+                .collect();
+
+            assert_eq!(unlzw(&lzw(&pixels, 2), 2), pixels);
+        }
+
+        for len in 0..50 {
+            let pixels: Vec<u8> = (0..len).map(|i| (i * i % 7 % 2) as u8).collect();
+            assert_eq!(unlzw(&lzw(&pixels, 2), 2), pixels);
+        }
+    }
+
+    #[test]
+    fn save_every_frame() {
+        let dir = std::env::temp_dir().join(format!("day_10-frames-{}", std::process::id()));
+        let frames = &example_frames()[..3];
+
+        save_frames(frames, &dir, Format::Pbm, 1).unwrap();
+
+        let mut names: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["frame_001.pbm", "frame_002.pbm", "frame_003.pbm"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_scale() {
+        let frame = [[false; WIDTH]; HEIGHT];
+
+        assert!(write_png(&frame, 0, io::sink()).is_err());
+        assert!(write_gif(&[frame], 2000, 1, io::sink()).is_err());
+    }
+}
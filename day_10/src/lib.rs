@@ -0,0 +1,9 @@
+pub mod cpu;
+pub mod crt;
+pub mod error;
+pub mod image;
+pub mod ocr;
+pub mod part_one;
+pub mod part_two;
+pub mod program;
+pub mod trace;
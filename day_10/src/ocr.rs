@@ -0,0 +1,145 @@
+use crate::error::OcrError;
+
+/// Height of the letters, and of the screen
+pub const GLYPH_HEIGHT: usize = 6;
+/// Width of the letters, each one followed by an empty column
+pub const GLYPH_WIDTH: usize = 4;
+
+/// The capital letters the puzzles draw, 4 pixels wide and 6 high
+const FONT: [(char, [&str; GLYPH_HEIGHT]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Read the letters drawn on a screen, one every 5 columns.
+///
+/// The error lists the letters that match no glyph of the font, or whose empty column isn't.
+pub fn read<R: AsRef<[bool]>>(rows: &[R]) -> Result<String, OcrError> {
+    if rows.len() != GLYPH_HEIGHT {
+        return Err(OcrError::Height(rows.len()));
+    }
+
+    let width = rows.iter().map(|row| row.as_ref().len()).min().unwrap_or(0);
+    let letters = width.div_ceil(GLYPH_WIDTH + 1);
+
+    let mut text = String::new();
+    let mut positions = Vec::new();
+
+    for letter in 0..letters {
+        let left = letter * (GLYPH_WIDTH + 1);
+        let pixel = |row: usize, x: usize| rows[row].as_ref().get(left + x).copied();
+
+        let blank_gap = (0..GLYPH_HEIGHT).all(|row| pixel(row, GLYPH_WIDTH) != Some(true));
+        let glyph = FONT.iter().find(|(_, glyph)| {
+            glyph.iter().enumerate().all(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .all(|(x, c)| pixel(row, x) == Some(c == '#'))
+            })
+        });
+
+        match glyph {
+            Some(&(c, _)) if blank_gap => text.push(c),
+            _ => {
+                text.push('?');
+                positions.push(letter + 1);
+            }
+        }
+    }
+
+    if positions.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError::UnknownGlyphs { text, positions })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Screen showing `text` with the font, `?` leaving a letter empty
+    fn draw(text: &str) -> Vec<Vec<bool>> {
+        let mut rows = vec![Vec::new(); GLYPH_HEIGHT];
+
+        for c in text.chars() {
+            let glyph = FONT.iter().find(|&&(letter, _)| letter == c);
+
+            for (row, pixels) in rows.iter_mut().enumerate() {
+                match glyph {
+                    Some((_, glyph)) => pixels.extend(glyph[row].chars().map(|c| c == '#')),
+                    None => pixels.extend([false; GLYPH_WIDTH]),
+                }
+                pixels.push(false);
+            }
+        }
+
+        rows
+    }
+
+    #[test]
+    fn whole_font() {
+        let letters: String = FONT.iter().map(|&(c, _)| c).collect();
+
+        assert_eq!(read(&draw(&letters)), Ok(letters));
+    }
+
+    #[test]
+    fn screen_of_the_puzzle() {
+        let screen = [
+            "###..####.###...##....##.####.#....#..#.",
+            "#..#....#.#..#.#..#....#.#....#....#.#..",
+            "###....#..#..#.#..#....#.###..#....##...",
+            "#..#..#...###..####....#.#....#....#.#..",
+            "#..#.#....#....#..#.#..#.#....#....#.#..",
+            "###..####.#....#..#..##..####.####.#..#.",
+        ];
+        let rows: Vec<Vec<bool>> = screen
+            .iter()
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .collect();
+
+        assert_eq!(read(&rows), Ok(String::from("BZPAJELK")));
+    }
+
+    #[test]
+    fn unknown_glyphs() {
+        let mut rows = draw("HELLO??");
+        // A stray pixel in the gap after the E
+        rows[2][9] = true;
+
+        let error = read(&rows).unwrap_err();
+
+        assert_eq!(
+            error,
+            OcrError::UnknownGlyphs {
+                text: String::from("H?LLO??"),
+                positions: vec![2, 6, 7]
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "couldn't recognise the letters at positions 2, 6 and 7 of 'H?LLO??'"
+        );
+    }
+
+    #[test]
+    fn wrong_height() {
+        assert_eq!(read(&draw("AB")[1..]), Err(OcrError::Height(5)));
+    }
+}
@@ -1,110 +1,37 @@
-use std::error::Error;
-use std::io;
+use aoc_core::{Answer, Solution, SolveError};
 
-pub fn main_p1() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
+use crate::cpu::{Cpu, Observer, State};
+use crate::program::Program;
 
-    let mut instructions = Vec::<Instruction>::new();
+pub struct PartOne;
 
-    // 1. Construct an array with all the instructions
-    for line in lines {
-        let line = line.unwrap();
-        let instr = Instruction::from(&line);
+/// Last cycle whose signal strength counts
+const LAST_CYCLE: usize = 220;
 
-        instructions.push(instr);
-    }
-
-    let mut cpu = CPU::new(instructions);
-    let mut signal_strength: i32 = 0;
-
-    for cycle in 1..221 {
-        if (cycle + 20) % 40 == 0 {
-            println!("Signal at cycle {} : {}", cycle, cpu.x());
-            signal_strength += cycle * cpu.x();
-        }
-
-        cpu.cycle();
-    }
-
-    println!("Total signal strength: {}", signal_strength);
-
-    Ok(())
-}
-
-struct CPU {
-    reg: i32,
-    cycles: u32,
-    cycle_left: u8,
-    instructions: Vec<Instruction>,
-    instr_index: usize,
-}
-
-impl CPU {
-    fn new(instructions: Vec<Instruction>) -> Self {
-        CPU {
-            reg: 1,
-            cycles: 0,
-            cycle_left: 0,
-            instructions,
-            instr_index: 0,
-        }
-    }
-
-    fn cycle(&mut self) {
-        self.cycles += 1;
-
-        // 1. Load instruction
-        if self.cycle_left == 0 {
-            self.cycle_left = self.instructions[self.instr_index].cycles();
-        }
-
-        // 3. Do one cycle
-        self.cycle_left -= 1;
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let program = Program::parse(input)?;
+        let mut cpu = Cpu::new(program);
+        let mut signal = SignalStrength::default();
 
-        // 4. Execute the instruction if it's cycle are done
-        if self.cycle_left == 0 {
-            match self.instructions[self.instr_index] {
-                Instruction::Addx(value) => self.reg += value,
-                Instruction::Noop => (),
-            };
+        cpu.run_until(LAST_CYCLE, &mut signal);
 
-            // 4.1 Increment instruction pointer
-            self.instr_index += 1;
-        }
-    }
-
-    fn cycles(&self) -> u32 {
-        self.cycles
-    }
-
-    fn x(&self) -> i32 {
-        self.reg
+        Ok(signal.total.into())
     }
 }
 
-#[derive(Clone, Copy)]
-enum Instruction {
-    Addx(i32),
-    Noop,
-}
-
-impl Instruction {
-    fn cycles(&self) -> u8 {
-        match self {
-            Instruction::Noop => 1,
-            Instruction::Addx(_) => 2,
-        }
-    }
+/// Sums the signal strength during the 20th cycle and every 40 cycles after, up to the 220th.
+///
+/// The sum is done in 64 bits, where any value of X times the cycle fits.
+#[derive(Debug, Default)]
+pub struct SignalStrength {
+    pub total: i64,
 }
 
-impl From<&String> for Instruction {
-    fn from(instr: &String) -> Self {
-        let data: Vec<&str> = instr.split(' ').collect();
-
-        match data[0] {
-            "noop" => Instruction::Noop,
-            "addx" => Instruction::Addx(data[1].parse().unwrap()),
-            _ => panic!("Not an instruction"),
+impl Observer for SignalStrength {
+    fn during(&mut self, state: &State) {
+        if (state.cycle + 20).is_multiple_of(40) && state.cycle <= LAST_CYCLE {
+            self.total += state.cycle as i64 * i64::from(state.x());
         }
     }
 }
@@ -114,29 +41,41 @@ mod tests {
     use super::*;
 
     #[test]
-    fn simple() {
-        let mut instructions = Vec::<Instruction>::new();
+    fn example() {
+        let input = include_str!("../simple_input.txt");
 
-        instructions.push(Instruction::Noop);
-        instructions.push(Instruction::Addx(3));
-        instructions.push(Instruction::Addx(-5));
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(13140i32));
+    }
 
-        let mut cpu = CPU::new(instructions);
+    #[test]
+    fn unknown_instruction() {
+        assert_eq!(
+            PartOne::solve("noop\nmulx 3\n").unwrap_err().to_string(),
+            "Invalid input: line 2, column 1: expected an instruction (noop, addx, add, mul, mov, jmp, jnz or halt), found 'mulx'"
+        );
+    }
 
-        // Noop
-        cpu.cycle();
-        assert_eq!(cpu.x(), 1);
+    #[test]
+    fn invalid_value() {
+        assert_eq!(
+            PartOne::solve("addx 3\naddx three\n")
+                .unwrap_err()
+                .to_string(),
+            "Invalid input: line 2, column 6: expected a value for addx, found 'three'"
+        );
+    }
 
-        // Addx 3
-        cpu.cycle();
-        assert_eq!(cpu.x(), 1);
-        cpu.cycle();
-        assert_eq!(cpu.x(), 4);
+    #[test]
+    fn extreme_x() {
+        // X is i32::MAX from the third cycle
+        assert_eq!(
+            PartOne::solve("addx 2147483646\n").unwrap(),
+            Answer::from(720 * i32::MAX as i64)
+        );
+    }
 
-        // Addx -5
-        cpu.cycle();
-        assert_eq!(cpu.x(), 4);
-        cpu.cycle();
-        assert_eq!(cpu.x(), -1);
+    #[test]
+    fn short_program() {
+        assert_eq!(PartOne::solve("addx 3\n").unwrap(), Answer::from(2880i32));
     }
 }
@@ -1,159 +1,111 @@
-use std::error::Error;
-use std::io;
+use aoc_core::{Answer, Solution, SolveError};
 
-pub fn main_p2() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
+use crate::cpu::Cpu;
+use crate::crt::{Crt, HEIGHT, WIDTH};
+use crate::ocr;
+use crate::program::Program;
 
-    let mut instructions = Vec::<Instruction>::new();
-    let mut crt = [[char::default(); 40]; 6];
+pub struct PartTwo;
 
-    // 1. Construct an array with all the instructions
-    for line in lines {
-        let line = line.unwrap();
-        let instr = Instruction::from(&line);
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let program = Program::parse(input)?;
+        let mut cpu = Cpu::new(program);
+        let mut crt = Crt::default();
 
-        instructions.push(instr);
-    }
-
-    let mut cpu = CPU::new(instructions);
-    let mut row: usize = 0;
-
-    for cycle in 1..240 {
-        // 1. Init cycle
-        let position = (cycle - 1) % 40 as usize;
-        if cycle % 40 == 0 {
-            row += 1;
-        }
-
-        // 2. During cycle
-        let sprite_pos = cpu.x();
-
-        println!("Sprite position: {}", sprite_pos);
+        cpu.run_until(WIDTH * HEIGHT, &mut crt);
 
-        if position as i32 >= sprite_pos - 1 && position as i32 <= sprite_pos + 1 {
-            crt[row][position] = '#'
-        } else {
-            crt[row][position] = '.'
-        }
+        // The screen is shown along the error, so that a human can still read it
+        let letters = ocr::read(&crt.pixels)
+            .map_err(|e| SolveError::NoSolution(format!("{}\n{}", e, crt)))?;
 
-        // 3. Execute cycle
-        cpu.cycle();
+        Ok(letters.into())
     }
-
-    for row in crt {
-        row.into_iter().for_each(|c| print!("{}", c));
-        println!("");
-    }
-
-    Ok(())
 }
 
-struct CPU {
-    reg: i32,
-    cycles: u32,
-    cycle_left: u8,
-    instructions: Vec<Instruction>,
-    instr_index: usize,
-}
-
-impl CPU {
-    fn new(instructions: Vec<Instruction>) -> Self {
-        CPU {
-            reg: 1,
-            cycles: 0,
-            cycle_left: 0,
-            instructions,
-            instr_index: 0,
-        }
-    }
-
-    fn cycle(&mut self) {
-        self.cycles += 1;
-
-        // 1. Load instruction
-        if self.cycle_left == 0 {
-            self.cycle_left = self.instructions[self.instr_index].cycles();
-        }
-
-        // 3. Do one cycle
-        self.cycle_left -= 1;
-
-        // 4. Execute the instruction if it's cycle are done
-        if self.cycle_left == 0 {
-            match self.instructions[self.instr_index] {
-                Instruction::Addx(value) => self.reg += value,
-                Instruction::Noop => (),
-            };
-
-            // 4.1 Increment instruction pointer
-            self.instr_index += 1;
-        }
-    }
-
-    fn cycles(&self) -> u32 {
-        self.cycles
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::part_one::SignalStrength;
+
+    /// Program moving the sprite so that the CRT draws `screen`, whose first pixel must be lit
+    fn drawing(screen: &[&str]) -> String {
+        let pixels = screen.iter().flat_map(|row| row.chars().enumerate());
+
+        pixels
+            .skip(1)
+            .map(|(position, c)| {
+                let x = if c == '#' { position } else { position + 5 };
+                format!("mov x, {}\n", x)
+            })
+            .collect()
     }
 
-    fn x(&self) -> i32 {
-        self.reg
+    #[test]
+    fn letters() {
+        let screen = [
+            "###..####.###...##....##.####.#....#..#.",
+            "#..#....#.#..#.#..#....#.#....#....#.#..",
+            "###....#..#..#.#..#....#.###..#....##...",
+            "#..#..#...###..####....#.#....#....#.#..",
+            "#..#.#....#....#..#.#..#.#....#....#.#..",
+            "###..####.#....#..#..##..####.####.#..#.",
+        ];
+
+        assert_eq!(
+            PartTwo::solve(&drawing(&screen)).unwrap(),
+            Answer::from("BZPAJELK")
+        );
     }
-}
-
-#[derive(Clone, Copy)]
-enum Instruction {
-    Addx(i32),
-    Noop,
-}
 
-impl Instruction {
-    fn cycles(&self) -> u8 {
-        match self {
-            Instruction::Noop => 1,
-            Instruction::Addx(_) => 2,
-        }
+    #[test]
+    fn example() {
+        let program = Program::parse(include_str!("../simple_input.txt")).unwrap();
+        let mut crt = Crt::default();
+        let screen = concat!(
+            "##..##..##..##..##..##..##..##..##..##..\n",
+            "###...###...###...###...###...###...###.\n",
+            "####....####....####....####....####....\n",
+            "#####.....#####.....#####.....#####.....\n",
+            "######......######......######......####\n",
+            "#######.......#######.......#######....."
+        );
+
+        Cpu::new(program).run_until(WIDTH * HEIGHT, &mut crt);
+        assert_eq!(crt.to_string(), screen);
+
+        // The example draws no letters
+        assert_eq!(
+            PartTwo::solve(include_str!("../simple_input.txt"))
+                .unwrap_err()
+                .to_string(),
+            format!(
+                "No solution: couldn't recognise the letters at positions 1, 2, 3, 4, 5, 6, 7 and 8 of '????????'\n{}",
+                screen
+            )
+        );
     }
-}
-
-impl From<&String> for Instruction {
-    fn from(instr: &String) -> Self {
-        let data: Vec<&str> = instr.split(' ').collect();
 
-        match data[0] {
-            "noop" => Instruction::Noop,
-            "addx" => Instruction::Addx(data[1].parse().unwrap()),
-            _ => panic!("Not an instruction"),
-        }
+    #[test]
+    fn missing_value() {
+        assert_eq!(
+            PartTwo::solve("noop\naddx\n").unwrap_err().to_string(),
+            "Invalid input: line 2, column 5: expected a value for addx, found nothing"
+        );
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
     #[test]
-    fn simple() {
-        let mut instructions = Vec::<Instruction>::new();
-
-        instructions.push(Instruction::Noop);
-        instructions.push(Instruction::Addx(3));
-        instructions.push(Instruction::Addx(-5));
-
-        let mut cpu = CPU::new(instructions);
-
-        // Noop
-        cpu.cycle();
-        assert_eq!(cpu.x(), 1);
-
-        // Addx 3
-        cpu.cycle();
-        assert_eq!(cpu.x(), 1);
-        cpu.cycle();
-        assert_eq!(cpu.x(), 4);
-
-        // Addx -5
-        cpu.cycle();
-        assert_eq!(cpu.x(), 4);
-        cpu.cycle();
-        assert_eq!(cpu.x(), -1);
+    fn both_parts_on_one_run() {
+        let program = Program::parse(include_str!("../simple_input.txt")).unwrap();
+        let mut cpu = Cpu::new(program);
+        let mut observers = (SignalStrength::default(), Crt::default());
+
+        cpu.run_until(WIDTH * HEIGHT, &mut observers);
+
+        assert_eq!(observers.0.total, 13140);
+        assert!(observers
+            .1
+            .to_string()
+            .starts_with("##..##..##..##..##..##..##..##..##..##..\n"));
     }
 }
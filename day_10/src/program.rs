@@ -0,0 +1,435 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use aoc_core::{column, Located};
+
+use crate::error::ParseError;
+
+/// A register of the CPU, X being the one the CRT follows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Register {
+    X,
+    A,
+    B,
+    C,
+}
+
+impl FromStr for Register {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "x" => Ok(Register::X),
+            "a" => Ok(Register::A),
+            "b" => Ok(Register::B),
+            "c" => Ok(Register::C),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Register::X => "x",
+            Register::A => "a",
+            Register::B => "b",
+            Register::C => "c",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// Source of a value: a register or an integer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Register(Register),
+    Value(i32),
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operand::Register(register) => write!(f, "{}", register),
+            Operand::Value(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// An instruction of the CPU, jumps going to the index of an instruction in the program
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Noop,
+    /// Add a value to X
+    Addx(i32),
+    Add(Register, Operand),
+    Mul(Register, Operand),
+    Mov(Register, Operand),
+    Jmp(usize),
+    /// Jump if the register isn't zero
+    Jnz(Register, usize),
+    /// Stop the program, the CPU staying idle afterwards
+    Halt,
+}
+
+impl Instruction {
+    /// Number of cycles the instruction takes, its effect only being visible after the last one
+    pub fn cycles(&self) -> u8 {
+        match self {
+            Instruction::Noop | Instruction::Mov(..) | Instruction::Jmp(_) | Instruction::Halt => 1,
+            Instruction::Addx(_)
+            | Instruction::Add(..)
+            | Instruction::Mul(..)
+            | Instruction::Jnz(..) => 2,
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Noop => write!(f, "noop"),
+            Instruction::Addx(value) => write!(f, "addx {}", value),
+            Instruction::Add(register, operand) => write!(f, "add {}, {}", register, operand),
+            Instruction::Mul(register, operand) => write!(f, "mul {}, {}", register, operand),
+            Instruction::Mov(register, operand) => write!(f, "mov {}, {}", register, operand),
+            Instruction::Jmp(target) => write!(f, "jmp {}", target),
+            Instruction::Jnz(register, target) => write!(f, "jnz {}, {}", register, target),
+            Instruction::Halt => write!(f, "halt"),
+        }
+    }
+}
+
+/// An assembled program, with the labels of its instructions and where they come from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Program {
+    instructions: Vec<Instruction>,
+    /// Line of the source of each instruction
+    lines: Vec<usize>,
+    /// Index of the instruction following each label
+    labels: HashMap<String, usize>,
+}
+
+/// A jump waiting for its label to be defined
+struct Pending<'a> {
+    instruction: usize,
+    label: &'a str,
+    line: usize,
+    column: usize,
+}
+
+impl Program {
+    /// Assemble a program, one instruction per line.
+    ///
+    /// A line may start with labels ("loop:") naming its instruction, or the next one if it has
+    /// none, and anything after ';' is a comment. Jumps go to a label or an instruction index.
+    pub fn parse(input: &str) -> Result<Self, Located<ParseError>> {
+        let mut program = Program::default();
+        let mut pending = Vec::new();
+
+        for (index, line) in input.lines().enumerate() {
+            program.parse_line(line, index + 1, &mut pending)?;
+        }
+
+        for jump in pending {
+            let target = *program.labels.get(jump.label).ok_or_else(|| {
+                let error = ParseError::UnknownLabel(jump.label.to_string());
+                Located::new(jump.line, jump.column, error)
+            })?;
+
+            match &mut program.instructions[jump.instruction] {
+                Instruction::Jmp(to) | Instruction::Jnz(_, to) => *to = target,
+                _ => unreachable!("only jumps have a label"),
+            }
+        }
+
+        Ok(program)
+    }
+
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// Line of the source the instruction at `index` comes from
+    pub fn line(&self, index: usize) -> Option<usize> {
+        self.lines.get(index).copied()
+    }
+
+    /// Index of the instruction named by `label`
+    pub fn label(&self, label: &str) -> Option<usize> {
+        self.labels.get(label).copied()
+    }
+
+    /// Labels naming the instruction at `index`, sorted
+    pub fn labels_at(&self, index: usize) -> Vec<&str> {
+        let mut labels: Vec<&str> = self
+            .labels
+            .iter()
+            .filter(|&(_, &at)| at == index)
+            .map(|(label, _)| label.as_str())
+            .collect();
+
+        labels.sort_unstable();
+        labels
+    }
+
+    fn parse_line<'a>(
+        &mut self,
+        line: &'a str,
+        line_number: usize,
+        pending: &mut Vec<Pending<'a>>,
+    ) -> Result<(), Located<ParseError>> {
+        let mut code = line.split(';').next().unwrap_or_default();
+
+        while let Some((label, rest)) = code.split_once(':') {
+            let label = label.trim();
+            let at = |error| Located::new(line_number, column(line, label), error);
+
+            let valid = label.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                && label.starts_with(|c: char| !c.is_ascii_digit());
+            if !valid {
+                return Err(at(ParseError::InvalidLabel(label.to_string())));
+            }
+            if self
+                .labels
+                .insert(label.to_string(), self.instructions.len())
+                .is_some()
+            {
+                return Err(at(ParseError::DuplicateLabel(label.to_string())));
+            }
+
+            code = rest;
+        }
+
+        let end_of_code = column(line, code) + code.trim_end().chars().count();
+        let mut tokens = code
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty());
+
+        let Some(name) = tokens.next() else {
+            return Ok(());
+        };
+
+        let mut operands = Operands {
+            line,
+            line_number,
+            end_of_code,
+            instruction: name,
+            tokens,
+        };
+
+        let instruction = match name {
+            "noop" => Instruction::Noop,
+            "addx" => Instruction::Addx(operands.value()?),
+            "add" => Instruction::Add(operands.register()?, operands.operand()?),
+            "mul" => Instruction::Mul(operands.register()?, operands.operand()?),
+            "mov" => Instruction::Mov(operands.register()?, operands.operand()?),
+            "jmp" => Instruction::Jmp(operands.target(self.instructions.len(), pending)?),
+            "jnz" => {
+                let register = operands.register()?;
+                Instruction::Jnz(register, operands.target(self.instructions.len(), pending)?)
+            }
+            "halt" => Instruction::Halt,
+            _ => {
+                let error = ParseError::UnknownInstruction(name.to_string());
+                return Err(Located::new(line_number, column(line, name), error));
+            }
+        };
+
+        if let Some(extra) = operands.tokens.next() {
+            let error = ParseError::UnexpectedOperand(extra.to_string());
+            return Err(Located::new(line_number, column(line, extra), error));
+        }
+
+        self.instructions.push(instruction);
+        self.lines.push(line_number);
+        Ok(())
+    }
+}
+
+impl IntoIterator for Program {
+    type Item = Instruction;
+    type IntoIter = std::vec::IntoIter<Instruction>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.instructions.into_iter()
+    }
+}
+
+/// Operands of the instruction being read, located in their line for errors
+struct Operands<'a, I> {
+    line: &'a str,
+    line_number: usize,
+    /// Column right after the last character of the instruction
+    end_of_code: usize,
+    instruction: &'a str,
+    tokens: I,
+}
+
+impl<'a, I: Iterator<Item = &'a str>> Operands<'a, I> {
+    fn next(&mut self, operand: &'static str) -> Result<&'a str, Located<ParseError>> {
+        self.tokens.next().ok_or_else(|| {
+            let error = ParseError::MissingOperand {
+                instruction: self.instruction.to_string(),
+                operand,
+            };
+            Located::new(self.line_number, self.end_of_code, error)
+        })
+    }
+
+    fn invalid(&self, operand: &'static str, token: &str) -> Located<ParseError> {
+        let error = ParseError::InvalidOperand {
+            instruction: self.instruction.to_string(),
+            operand,
+            found: token.to_string(),
+        };
+        Located::new(self.line_number, column(self.line, token), error)
+    }
+
+    fn value(&mut self) -> Result<i32, Located<ParseError>> {
+        let token = self.next("value")?;
+
+        token.parse().map_err(|_| self.invalid("value", token))
+    }
+
+    fn register(&mut self) -> Result<Register, Located<ParseError>> {
+        let token = self.next("register")?;
+
+        token.parse().map_err(|_| self.invalid("register", token))
+    }
+
+    fn operand(&mut self) -> Result<Operand, Located<ParseError>> {
+        let token = self.next("value")?;
+
+        if let Ok(register) = token.parse() {
+            return Ok(Operand::Register(register));
+        }
+        token
+            .parse()
+            .map(Operand::Value)
+            .map_err(|_| self.invalid("value", token))
+    }
+
+    /// Index of the instruction to jump to, left to resolve later for a label
+    fn target(
+        &mut self,
+        instruction: usize,
+        pending: &mut Vec<Pending<'a>>,
+    ) -> Result<usize, Located<ParseError>> {
+        let token = self.next("label")?;
+
+        if token.starts_with(|c: char| c.is_ascii_digit()) {
+            return token.parse().map_err(|_| self.invalid("label", token));
+        }
+
+        pending.push(Pending {
+            instruction,
+            label: token,
+            line: self.line_number,
+            column: column(self.line, token),
+        });
+        Ok(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(input: &str) -> String {
+        Program::parse(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn labels_and_comments() {
+        let program = Program::parse(concat!(
+            "; count down from 3\n",
+            "        mov a, 3\n",
+            "loop:   addx 2   ; two more\n",
+            "        add a -1\n",
+            "        jnz a, loop\n",
+            "end:\n",
+            "done:   halt\n"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            program.instructions(),
+            [
+                Instruction::Mov(Register::A, Operand::Value(3)),
+                Instruction::Addx(2),
+                Instruction::Add(Register::A, Operand::Value(-1)),
+                Instruction::Jnz(Register::A, 1),
+                Instruction::Halt,
+            ]
+        );
+        assert_eq!(program.label("loop"), Some(1));
+        assert_eq!(program.labels_at(4), ["done", "end"]);
+        assert_eq!(program.line(4), Some(7));
+    }
+
+    #[test]
+    fn forward_jumps_and_indexes() {
+        let program = Program::parse("jmp skip\nnoop\nskip: jnz x 0\n").unwrap();
+
+        assert_eq!(
+            program.instructions(),
+            [
+                Instruction::Jmp(2),
+                Instruction::Noop,
+                Instruction::Jnz(Register::X, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let input = "noop\naddx -4\nadd b, x\nmul c, 7\nmov x, a\njmp 0\njnz c, 3\nhalt";
+        let program = Program::parse(input).unwrap();
+        let written: Vec<String> = program
+            .instructions()
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+
+        assert_eq!(written.join("\n"), input);
+    }
+
+    #[test]
+    fn operand_errors() {
+        assert_eq!(
+            error("noop\nadd q, 3"),
+            "line 2, column 5: expected a register for add, found 'q'"
+        );
+        assert_eq!(
+            error("mov a, three ; comment"),
+            "line 1, column 8: expected a value for mov, found 'three'"
+        );
+        assert_eq!(
+            error("jnz a   ; where?"),
+            "line 1, column 6: expected a label for jnz, found nothing"
+        );
+        assert_eq!(
+            error("noop 3"),
+            "line 1, column 6: expected the end of the instruction, found '3'"
+        );
+    }
+
+    #[test]
+    fn label_errors() {
+        assert_eq!(
+            error("a: noop\n  a: noop"),
+            "line 2, column 3: label 'a' is defined twice"
+        );
+        assert_eq!(
+            error("1st: noop"),
+            "line 1, column 1: expected a label (letters, digits and '_', not starting with a digit), found '1st'"
+        );
+        assert_eq!(
+            error("noop\njmp nowhere\n"),
+            "line 2, column 5: no label named 'nowhere'"
+        );
+    }
+}
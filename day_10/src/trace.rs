@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use crate::cpu::{Observer, State};
+use crate::program::{Instruction, Program};
+
+/// What happened to X during a cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub cycle: usize,
+    /// Index of the instruction executing, past the end of the program once the CPU is idle
+    pub instr_index: usize,
+    /// `None` while the CPU is idle
+    pub instruction: Option<Instruction>,
+    /// X during the cycle
+    pub x_before: i32,
+    /// X at the end of the cycle
+    pub x_after: i32,
+}
+
+/// Records every cycle of a run
+#[derive(Debug, Clone, Default)]
+pub struct Trace {
+    pub steps: Vec<Step>,
+    /// The state seen during the cycle going on
+    during: Option<State>,
+}
+
+impl Observer for Trace {
+    fn during(&mut self, state: &State) {
+        self.during = Some(*state);
+    }
+
+    fn after(&mut self, state: &State) {
+        let Some(during) = self.during.take() else {
+            return;
+        };
+
+        self.steps.push(Step {
+            cycle: during.cycle,
+            instr_index: during.instr_index,
+            instruction: during.instruction,
+            x_before: during.x(),
+            x_after: state.x(),
+        });
+    }
+}
+
+impl Trace {
+    /// Write the steps as CSV, with a header and an empty instruction while the CPU is idle
+    pub fn write_csv<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "cycle,instr_index,instruction,x_before,x_after")?;
+
+        for step in &self.steps {
+            // Instructions with two operands have a comma
+            let instruction = step
+                .instruction
+                .map(|i| i.to_string())
+                .map(|i| {
+                    if i.contains(',') {
+                        format!("\"{}\"", i)
+                    } else {
+                        i
+                    }
+                })
+                .unwrap_or_default();
+
+            writeln!(
+                out,
+                "{},{},{},{},{}",
+                step.cycle, step.instr_index, instruction, step.x_before, step.x_after
+            )?;
+        }
+
+        out.flush()
+    }
+
+    /// Write the steps as JSON lines, one object per cycle, the instruction being null while
+    /// the CPU is idle
+    pub fn write_jsonl<W: Write>(&self, mut out: W) -> io::Result<()> {
+        for step in &self.steps {
+            let instruction = step
+                .instruction
+                .map_or(String::from("null"), |i| format!("\"{}\"", i));
+
+            writeln!(
+                out,
+                "{{\"cycle\":{},\"instr_index\":{},\"instruction\":{},\"x_before\":{},\"x_after\":{}}}",
+                step.cycle, step.instr_index, instruction, step.x_before, step.x_after
+            )?;
+        }
+
+        out.flush()
+    }
+}
+
+/// Lists a program with the cycles each instruction takes when the program runs straight
+/// through, jumps not being followed.
+///
+/// Jumps show the label of their target when there is one, and marked cycles are pointed out
+/// on the instruction executing during them.
+#[derive(Debug, Clone)]
+pub struct Disassembler<'a> {
+    instructions: &'a [Instruction],
+    /// Labels naming each instruction
+    labels: HashMap<usize, Vec<&'a str>>,
+    marks: Vec<usize>,
+}
+
+impl<'a> Disassembler<'a> {
+    pub fn new(instructions: &'a [Instruction]) -> Self {
+        Disassembler {
+            instructions,
+            labels: HashMap::new(),
+            marks: Vec::new(),
+        }
+    }
+
+    /// Disassemble a program with its labels
+    pub fn from_program(program: &'a Program) -> Self {
+        let instructions = program.instructions();
+        let labels = (0..=instructions.len())
+            .map(|index| (index, program.labels_at(index)))
+            .filter(|(_, labels)| !labels.is_empty())
+            .collect();
+
+        Disassembler {
+            instructions,
+            labels,
+            marks: Vec::new(),
+        }
+    }
+
+    /// Point out these cycles, like the ones whose signal strength is sampled
+    pub fn mark(mut self, cycles: impl IntoIterator<Item = usize>) -> Self {
+        self.marks.extend(cycles);
+        self.marks.sort_unstable();
+        self
+    }
+
+    fn target(&self, index: usize) -> String {
+        match self.labels.get(&index) {
+            Some(labels) => labels[0].to_string(),
+            None => index.to_string(),
+        }
+    }
+
+    fn write_labels(&self, f: &mut std::fmt::Formatter<'_>, index: usize) -> std::fmt::Result {
+        for label in self.labels.get(&index).into_iter().flatten() {
+            writeln!(f, "{}:", label)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Disassembler<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut start = 1;
+
+        for (index, instr) in self.instructions.iter().enumerate() {
+            self.write_labels(f, index)?;
+
+            let end = start + instr.cycles() as usize - 1;
+            let cycles = if start == end {
+                start.to_string()
+            } else {
+                format!("{}-{}", start, end)
+            };
+            let text = match *instr {
+                Instruction::Jmp(target) => format!("jmp {}", self.target(target)),
+                Instruction::Jnz(register, target) => {
+                    format!("jnz {}, {}", register, self.target(target))
+                }
+                instr => instr.to_string(),
+            };
+
+            let marks: Vec<String> = self
+                .marks
+                .iter()
+                .filter(|&&mark| (start..=end).contains(&mark))
+                .map(|mark| mark.to_string())
+                .collect();
+
+            if marks.is_empty() {
+                writeln!(f, "{:>4}  {:>9}  {}", index, cycles, text)?;
+            } else {
+                let s = if marks.len() == 1 { "" } else { "s" };
+                let text = format!("{:<16}", text);
+                let marks = marks.join(", ");
+                writeln!(
+                    f,
+                    "{:>4}  {:>9}  {}; cycle{} {}",
+                    index, cycles, text, s, marks
+                )?;
+            }
+
+            start = end + 1;
+        }
+
+        // Labels after the last instruction
+        self.write_labels(f, self.instructions.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::Cpu;
+    use crate::part_one::SignalStrength;
+
+    /// Cycles whose signal strength part one sums
+    const SAMPLED: [usize; 6] = [20, 60, 100, 140, 180, 220];
+
+    fn trace(input: &str, cycles: usize) -> Trace {
+        let mut trace = Trace::default();
+
+        Cpu::new(Program::parse(input).unwrap()).run_until(cycles, &mut trace);
+        trace
+    }
+
+    #[test]
+    fn csv() {
+        let mut csv = Vec::new();
+
+        trace("noop\naddx 3\nadd x, -5", 6)
+            .write_csv(&mut csv)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            concat!(
+                "cycle,instr_index,instruction,x_before,x_after\n",
+                "1,0,noop,1,1\n",
+                "2,1,addx 3,1,1\n",
+                "3,1,addx 3,1,4\n",
+                "4,2,\"add x, -5\",4,4\n",
+                "5,2,\"add x, -5\",4,-1\n",
+                "6,3,,-1,-1\n"
+            )
+        );
+    }
+
+    #[test]
+    fn jsonl() {
+        let mut jsonl = Vec::new();
+
+        trace("addx 2", 3).write_jsonl(&mut jsonl).unwrap();
+
+        assert_eq!(
+            String::from_utf8(jsonl).unwrap(),
+            concat!(
+                "{\"cycle\":1,\"instr_index\":0,\"instruction\":\"addx 2\",\"x_before\":1,\"x_after\":1}\n",
+                "{\"cycle\":2,\"instr_index\":0,\"instruction\":\"addx 2\",\"x_before\":1,\"x_after\":3}\n",
+                "{\"cycle\":3,\"instr_index\":1,\"instruction\":null,\"x_before\":3,\"x_after\":3}\n"
+            )
+        );
+    }
+
+    #[test]
+    fn disassemble() {
+        let program = Program::parse("mov a 2\nloop: addx 5\nadd a -1\njnz a loop\nend:").unwrap();
+
+        assert_eq!(
+            Disassembler::from_program(&program)
+                .mark([3, 7])
+                .to_string(),
+            concat!(
+                "   0          1  mov a, 2\n",
+                "loop:\n",
+                "   1        2-3  addx 5          ; cycle 3\n",
+                "   2        4-5  add a, -1\n",
+                "   3        6-7  jnz a, loop     ; cycle 7\n",
+                "end:\n"
+            )
+        );
+        assert_eq!(
+            Disassembler::new(program.instructions())
+                .to_string()
+                .lines()
+                .nth(3),
+            Some("   3        6-7  jnz a, 1")
+        );
+    }
+
+    #[test]
+    fn sampled_cycles_match_the_trace() {
+        let input = include_str!("../simple_input.txt");
+        let program = Program::parse(input).unwrap();
+        let trace = trace(input, 220);
+        let mut signal = SignalStrength::default();
+
+        Cpu::new(program.clone()).run_until(220, &mut signal);
+
+        let sampled: i64 = SAMPLED
+            .iter()
+            .map(|&cycle| {
+                let step = trace.steps[cycle - 1];
+                assert_eq!(step.cycle, cycle);
+                cycle as i64 * i64::from(step.x_before)
+            })
+            .sum();
+        assert_eq!(sampled, signal.total);
+
+        // The example has no jumps, so the cycles listed are the ones of the trace
+        let listing = Disassembler::new(program.instructions())
+            .mark(SAMPLED)
+            .to_string();
+        for cycle in SAMPLED {
+            let line = listing
+                .lines()
+                .find(|line| line.ends_with(&format!("; cycle {}", cycle)))
+                .unwrap();
+            let index: usize = line.split_whitespace().next().unwrap().parse().unwrap();
+
+            assert_eq!(trace.steps[cycle - 1].instr_index, index);
+        }
+    }
+}
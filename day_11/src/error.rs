@@ -0,0 +1,121 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading the notes about the monkeys
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A line that doesn't start with the expected field
+    UnexpectedLine {
+        expected: &'static str,
+        found: String,
+    },
+    /// The notes end in the middle of a monkey
+    MissingLine(&'static str),
+    /// A worry level that isn't a positive integer
+    InvalidItem(String),
+    /// A monkey whose number isn't its place in the notes
+    UnexpectedMonkey { expected: usize, found: String },
+    /// An operator other than + or *
+    InvalidOperator(String),
+    /// An operand other than old, a positive integer or an expression in parentheses
+    InvalidOperand(String),
+    /// An operation ending where an operand is expected
+    MissingOperand,
+    /// An operation ending inside parentheses
+    UnclosedParenthesis,
+    /// An operation with more levels of operations or parentheses than the limit
+    TooDeep(usize),
+    /// A divisor or a monkey number that isn't a positive integer
+    InvalidNumber(String),
+    /// A test dividing by zero
+    ZeroDivisor,
+    /// A monkey throwing to a monkey that isn't in the notes
+    UnknownMonkey(usize),
+    /// A monkey throwing to itself, which would never end its turn
+    ThrowToItself(usize),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnexpectedLine { expected, found } => {
+                write!(f, "expected '{}', found '{}'", expected, found)
+            }
+            ParseError::MissingLine(expected) => {
+                write!(f, "expected '{}', found the end of the notes", expected)
+            }
+            ParseError::InvalidItem(s) => write!(f, "expected a worry level, found '{}'", s),
+            ParseError::UnexpectedMonkey { expected, found } => {
+                write!(f, "expected 'Monkey {}:', found '{}'", expected, found)
+            }
+            ParseError::InvalidOperator(s) => write!(f, "expected '+' or '*', found '{}'", s),
+            ParseError::InvalidOperand(s) => {
+                write!(f, "expected 'old' or a number, found '{}'", s)
+            }
+            ParseError::MissingOperand => {
+                write!(
+                    f,
+                    "expected 'old' or a number, found the end of the operation"
+                )
+            }
+            ParseError::UnclosedParenthesis => {
+                write!(f, "expected ')', found the end of the operation")
+            }
+            ParseError::TooDeep(depth) => {
+                write!(f, "operation nested more than {} levels deep", depth)
+            }
+            ParseError::InvalidNumber(s) => write!(f, "expected a number, found '{}'", s),
+            ParseError::ZeroDivisor => write!(f, "expected a divisor, found 0"),
+            ParseError::UnknownMonkey(id) => write!(f, "monkey {} doesn't exist", id),
+            ParseError::ThrowToItself(id) => write!(f, "monkey {} throws to itself", id),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Errors stopping a simulation of the monkeys
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimulationError {
+    /// A worry level too large for the integers of the simulation
+    Overflow {
+        round: usize,
+        monkey: usize,
+        /// Worry level of the item before the inspection
+        item: String,
+    },
+    /// A stop condition, which can't be checked while following the items one by one
+    StopCondition,
+    /// A relief dividing the worry levels by zero
+    ZeroRelief,
+    /// Divisors whose least common multiple doesn't fit in 64 bits, so that the worry levels
+    /// can't be reduced modulo it
+    ModulusOverflow,
+}
+
+impl Display for SimulationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SimulationError::Overflow {
+                round,
+                monkey,
+                item,
+            } => write!(
+                f,
+                "the worry level of item {} overflows when monkey {} inspects it in round {}",
+                item, monkey, round
+            ),
+            SimulationError::StopCondition => write!(
+                f,
+                "the stop condition needs the monkeys to take turns round after round"
+            ),
+            SimulationError::ZeroRelief => write!(f, "the worry levels can't be divided by 0"),
+            SimulationError::ModulusOverflow => write!(
+                f,
+                "the least common multiple of the divisors doesn't fit in 64 bits"
+            ),
+        }
+    }
+}
+
+impl Error for SimulationError {}
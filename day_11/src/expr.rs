@@ -0,0 +1,334 @@
+use std::fmt::Display;
+use std::iter::Peekable;
+use std::str::FromStr;
+use std::vec::IntoIter;
+
+use aoc_core::{column, Located};
+
+use crate::error::ParseError;
+use crate::worry::Worry;
+
+/// An operator of the worry expressions.
+///
+/// Subtraction is left out: remainders would follow it, but a worry level reduced modulo the
+/// divisors can be smaller than what is subtracted while the real one isn't, and the result
+/// couldn't be told apart from a real underflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Mul,
+}
+
+impl Operator {
+    /// The result of the operation, `None` if it overflows
+    pub fn apply<W: Worry>(&self, left: &W, right: &W) -> Option<W> {
+        match self {
+            Operator::Add => left.checked_add(right),
+            Operator::Mul => left.checked_mul(right),
+        }
+    }
+
+    /// Operators of higher precedence are applied first
+    fn precedence(&self) -> u8 {
+        match self {
+            Operator::Add => 1,
+            Operator::Mul => 2,
+        }
+    }
+}
+
+impl FromStr for Operator {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "+" => Ok(Operator::Add),
+            "*" => Ok(Operator::Mul),
+            _ => Err(ParseError::InvalidOperator(s.to_string())),
+        }
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operator::Add => write!(f, "+"),
+            Operator::Mul => write!(f, "*"),
+        }
+    }
+}
+
+/// Deepest nesting of operations and parentheses in an expression, which keeps the recursions
+/// over it short
+pub const MAX_DEPTH: usize = 64;
+
+/// How a monkey computes the new worry level of an item from the `old` one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Old,
+    Number(u64),
+    Binary(Box<Expr>, Operator, Box<Expr>),
+}
+
+impl Expr {
+    /// Parse an expression such as `old * (old + 3)`, `*` being applied before `+`.
+    ///
+    /// Errors are on line 1, their column being the one of the token in `text`.
+    pub fn parse(text: &str) -> Result<Expr, Located<ParseError>> {
+        let mut parser = Parser {
+            text,
+            tokens: tokens(text).into_iter().peekable(),
+            parentheses: 0,
+        };
+
+        let expr = parser.expr(0)?;
+        match parser.tokens.next() {
+            None => Ok(expr),
+            // Only a closing parenthesis can stop an expression early
+            Some(token) => Err(parser.error(token, ParseError::InvalidOperator(token.to_string()))),
+        }
+    }
+
+    /// The new worry level of an item, `None` if it overflows
+    pub fn eval<W: Worry>(&self, old: &W) -> Option<W> {
+        match self {
+            Expr::Old => Some(old.clone()),
+            Expr::Number(n) => Some(W::from_u64(*n)),
+            Expr::Binary(left, op, right) => op.apply(&left.eval(old)?, &right.eval(old)?),
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Binary(_, op, _) => op.precedence(),
+            _ => u8::MAX,
+        }
+    }
+
+    /// Number of levels of the expression, 1 for a single operand
+    fn depth(&self) -> usize {
+        match self {
+            Expr::Binary(left, _, right) => 1 + left.depth().max(right.depth()),
+            _ => 1,
+        }
+    }
+}
+
+impl Display for Expr {
+    /// Write the expression back, with only the parentheses it needs
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expr::Old => write!(f, "old"),
+            Expr::Number(n) => write!(f, "{}", n),
+            Expr::Binary(left, op, right) => {
+                if left.precedence() < op.precedence() {
+                    write!(f, "({})", left)?;
+                } else {
+                    write!(f, "{}", left)?;
+                }
+                write!(f, " {} ", op)?;
+                // Operators are left associative, so a right operand of the same precedence
+                // needs its parentheses
+                if right.precedence() <= op.precedence() {
+                    write!(f, "({})", right)
+                } else {
+                    write!(f, "{}", right)
+                }
+            }
+        }
+    }
+}
+
+/// Split an expression in words, numbers and single symbols, whitespace being ignored
+fn tokens(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+
+        let mut end = start + c.len_utf8();
+        if c.is_alphanumeric() {
+            while let Some(&(index, c)) = chars.peek() {
+                if !c.is_alphanumeric() {
+                    break;
+                }
+                end = index + c.len_utf8();
+                chars.next();
+            }
+        }
+
+        tokens.push(&text[start..end]);
+    }
+
+    tokens
+}
+
+/// Precedence climbing parser over the tokens of an expression
+struct Parser<'a> {
+    text: &'a str,
+    tokens: Peekable<IntoIter<&'a str>>,
+    /// Number of parentheses open
+    parentheses: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// Parse operations whose operators have at least `precedence`
+    fn expr(&mut self, precedence: u8) -> Result<Expr, Located<ParseError>> {
+        let mut left = self.operand()?;
+
+        while let Some(&token) = self.tokens.peek() {
+            if token == ")" {
+                break;
+            }
+
+            let op = Operator::from_str(token).map_err(|e| self.error(token, e))?;
+            if op.precedence() < precedence {
+                break;
+            }
+            self.tokens.next();
+
+            let right = self.expr(op.precedence() + 1)?;
+            left = Expr::Binary(Box::new(left), op, Box::new(right));
+            if left.depth() > MAX_DEPTH {
+                return Err(self.error(token, ParseError::TooDeep(MAX_DEPTH)));
+            }
+        }
+
+        Ok(left)
+    }
+
+    fn operand(&mut self) -> Result<Expr, Located<ParseError>> {
+        let token = match self.tokens.next() {
+            Some(token) => token,
+            None => return Err(self.error(self.end(), ParseError::MissingOperand)),
+        };
+
+        match token {
+            "old" => Ok(Expr::Old),
+            "(" => {
+                self.parentheses += 1;
+                if self.parentheses > MAX_DEPTH {
+                    return Err(self.error(token, ParseError::TooDeep(MAX_DEPTH)));
+                }
+
+                let expr = self.expr(0)?;
+                match self.tokens.next() {
+                    Some(")") => {
+                        self.parentheses -= 1;
+                        Ok(expr)
+                    }
+                    // The expression only stops early on a parenthesis
+                    _ => Err(self.error(self.end(), ParseError::UnclosedParenthesis)),
+                }
+            }
+            _ => token
+                .parse()
+                .map(Expr::Number)
+                .map_err(|_| self.error(token, ParseError::InvalidOperand(token.to_string()))),
+        }
+    }
+
+    /// Empty token at the end of the expression
+    fn end(&self) -> &'a str {
+        &self.text[self.text.len()..]
+    }
+
+    fn error(&self, token: &str, error: ParseError) -> Located<ParseError> {
+        Located::new(1, column(self.text, token), error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::worry::BigUint;
+
+    #[test]
+    fn precedence() {
+        let expr = Expr::parse("old * old + 3").unwrap();
+
+        assert_eq!(expr.eval(&4u64), Some(19));
+        assert_eq!(Expr::parse("3 + old * old").unwrap().eval(&4u64), Some(19));
+        assert_eq!(
+            Expr::parse("old * (old + 3)").unwrap().eval(&4u64),
+            Some(28)
+        );
+        assert_eq!(
+            Expr::parse("2*old*old+old+1").unwrap().eval(&3u64),
+            Some(22)
+        );
+        assert_eq!(expr.eval(&(u64::MAX / 2)), None);
+        assert_eq!(
+            expr.eval(&BigUint::from_u64(u64::MAX / 2))
+                .unwrap()
+                .to_string(),
+            ((u64::MAX / 2) as u128 * (u64::MAX / 2) as u128 + 3).to_string()
+        );
+    }
+
+    #[test]
+    fn display() {
+        for text in [
+            "old * old + 3",
+            "old * (old + 3)",
+            "(old + 1) * 2 * (3 + old)",
+            "1 + (2 + old)",
+        ] {
+            assert_eq!(Expr::parse(text).unwrap().to_string(), text);
+        }
+        assert_eq!(Expr::parse("((old))*19").unwrap().to_string(), "old * 19");
+    }
+
+    #[test]
+    fn errors() {
+        let error = |text: &str| Expr::parse(text).unwrap_err();
+
+        assert_eq!(
+            error("old / 19"),
+            Located::new(1, 5, ParseError::InvalidOperator(String::from("/")))
+        );
+        assert_eq!(
+            error("old * x"),
+            Located::new(1, 7, ParseError::InvalidOperand(String::from("x")))
+        );
+        assert_eq!(
+            error("old *"),
+            Located::new(1, 6, ParseError::MissingOperand)
+        );
+        assert_eq!(error(""), Located::new(1, 1, ParseError::MissingOperand));
+        assert_eq!(
+            error("(old + 1"),
+            Located::new(1, 9, ParseError::UnclosedParenthesis)
+        );
+        assert_eq!(
+            error("old + 1)"),
+            Located::new(1, 8, ParseError::InvalidOperator(String::from(")")))
+        );
+    }
+
+    #[test]
+    fn too_deep() {
+        let chain = |operations: usize| format!("old{}", " + 1".repeat(operations));
+        let nested = |parentheses: usize| {
+            format!("{}old{}", "(".repeat(parentheses), ")".repeat(parentheses))
+        };
+
+        assert_eq!(
+            Expr::parse(&chain(MAX_DEPTH - 1)).unwrap().eval(&0u64),
+            Some(63)
+        );
+        assert_eq!(
+            Expr::parse(&chain(MAX_DEPTH)).unwrap_err(),
+            Located::new(1, 257, ParseError::TooDeep(MAX_DEPTH))
+        );
+        assert_eq!(Expr::parse(&nested(MAX_DEPTH)), Ok(Expr::Old));
+        assert_eq!(
+            Expr::parse(&nested(100_000)).unwrap_err(),
+            Located::new(1, 65, ParseError::TooDeep(MAX_DEPTH))
+        );
+    }
+}
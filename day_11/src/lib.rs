@@ -0,0 +1,8 @@
+pub mod error;
+pub mod expr;
+pub mod monkey;
+pub mod part_one;
+pub mod part_two;
+pub mod simulation;
+pub mod trace;
+pub mod worry;
@@ -0,0 +1,292 @@
+use std::collections::VecDeque;
+use std::fmt::Display;
+
+use aoc_core::{column, Locate, Located};
+
+use crate::error::ParseError;
+use crate::expr::Expr;
+use crate::worry::Worry;
+
+/// A monkey of the notes, with the items it holds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monkey<W = u64> {
+    pub id: usize,
+    /// Worry levels of the items, the first one being inspected next
+    pub items: VecDeque<W>,
+    pub operation: Expr,
+    /// The test checks whether the worry level is a multiple of it
+    pub divisor: u64,
+    /// Monkey to throw to when the test is true
+    pub if_true: usize,
+    /// Monkey to throw to when the test is false
+    pub if_false: usize,
+    /// Number of items inspected so far
+    pub inspected: u64,
+}
+
+impl Monkey {
+    /// The same monkey with other integers for the worry levels
+    pub fn with_worry<W: Worry>(self) -> Monkey<W> {
+        Monkey {
+            id: self.id,
+            items: self.items.into_iter().map(W::from_u64).collect(),
+            operation: self.operation,
+            divisor: self.divisor,
+            if_true: self.if_true,
+            if_false: self.if_false,
+            inspected: self.inspected,
+        }
+    }
+}
+
+impl<W: Worry> Monkey<W> {
+    pub fn has_item(&self) -> bool {
+        !self.items.is_empty()
+    }
+
+    /// Inspect an item taken from the monkey, counting it, and work out its new worry level,
+    /// `None` if it overflows
+    pub fn inspect(&mut self, item: &W) -> Option<W> {
+        self.inspected += 1;
+
+        self.operation.eval(item)
+    }
+
+    pub fn is_divisible(&self, worry: &W) -> bool {
+        worry.is_multiple_of(self.divisor)
+    }
+
+    /// The monkey an item with this worry level is thrown to
+    pub fn target(&self, worry: &W) -> usize {
+        if self.is_divisible(worry) {
+            self.if_true
+        } else {
+            self.if_false
+        }
+    }
+}
+
+impl<W: Worry> Display for Monkey<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Monkey {}: {:?}", self.id, self.items)
+    }
+}
+
+/// Read the notes about the monkeys.
+///
+/// Blank lines are ignored wherever they are, as is the spacing inside the lines. Each monkey
+/// must be numbered after its place in the notes and throw to monkeys of the notes. The
+/// operation is any expression of `old` and numbers with `+`, `*` and parentheses.
+pub fn parse(notes: &str) -> Result<Vec<Monkey>, Located<ParseError>> {
+    let mut lines = notes
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let mut monkeys = Vec::<Monkey>::new();
+    // Monkeys to throw to, checked once every monkey is known
+    let mut targets = Vec::<(usize, usize, usize)>::new();
+
+    while let Some((index, line)) = lines.next() {
+        let id = monkeys.len();
+
+        // 1. The monkey and its number
+        let header = line.trim();
+        let number = match strip_words(header, "Monkey") {
+            Some(number) => number.strip_suffix(':').unwrap_or(number).trim(),
+            None => {
+                let error = ParseError::UnexpectedLine {
+                    expected: "Monkey",
+                    found: header.to_string(),
+                };
+                return Err(Located::new(index + 1, column(line, header), error));
+            }
+        };
+        if number.parse() != Ok(id) {
+            let error = ParseError::UnexpectedMonkey {
+                expected: id,
+                found: header.to_string(),
+            };
+            return Err(Located::new(index + 1, column(line, header), error));
+        }
+
+        // 2. Its starting items, which can be none
+        let (index, line, starting_items) = field(&mut lines, index, "Starting items:")?;
+        let mut items = VecDeque::new();
+        if !starting_items.is_empty() {
+            for item in starting_items.split(',').map(str::trim) {
+                let worry = item
+                    .parse()
+                    .map_err(|_| ParseError::InvalidItem(item.to_string()))
+                    .at(index + 1, column(line, item))?;
+                items.push_back(worry);
+            }
+        }
+
+        // 3. The operation
+        let (index, line, operation) = field(&mut lines, index, "Operation: new =")?;
+        // The columns of the expression errors are within the operation
+        let operation = Expr::parse(operation).map_err(|e| {
+            let col = column(line, operation) + e.column() - 1;
+            Located::new(index + 1, col, e.error().clone())
+        })?;
+
+        // 4. The test
+        let (index, line, test) = field(&mut lines, index, "Test: divisible by")?;
+        let divisor = match test.parse() {
+            Ok(0) => Err(ParseError::ZeroDivisor),
+            Ok(divisor) => Ok(divisor),
+            Err(_) => Err(ParseError::InvalidNumber(test.to_string())),
+        }
+        .at(index + 1, column(line, test))?;
+
+        // 5. The monkeys to throw to
+        let (index, line, throw) = field(&mut lines, index, "If true: throw to monkey")?;
+        let if_true = monkey_number(id, throw).at(index + 1, column(line, throw))?;
+        targets.push((index + 1, column(line, throw), if_true));
+
+        let (index, line, throw) = field(&mut lines, index, "If false: throw to monkey")?;
+        let if_false = monkey_number(id, throw).at(index + 1, column(line, throw))?;
+        targets.push((index + 1, column(line, throw), if_false));
+
+        monkeys.push(Monkey {
+            id,
+            items,
+            operation,
+            divisor,
+            if_true,
+            if_false,
+            inspected: 0,
+        });
+    }
+
+    for (line, col, target) in targets {
+        if target >= monkeys.len() {
+            return Err(Located::new(line, col, ParseError::UnknownMonkey(target)));
+        }
+    }
+
+    Ok(monkeys)
+}
+
+/// The monkey monkey `id` throws to
+fn monkey_number(id: usize, throw: &str) -> Result<usize, ParseError> {
+    match throw.parse() {
+        Ok(target) if target == id => Err(ParseError::ThrowToItself(id)),
+        Ok(target) => Ok(target),
+        Err(_) => Err(ParseError::InvalidNumber(throw.to_string())),
+    }
+}
+
+/// What follows the words of `name` at the start of `text`, however they are spaced
+fn strip_words<'a>(text: &'a str, name: &str) -> Option<&'a str> {
+    name.split_whitespace()
+        .try_fold(text, |rest, word| rest.trim_start().strip_prefix(word))
+        .map(str::trim)
+}
+
+/// Read the next line of a monkey's notes, returning its index, the line
+/// and what follows the `name` of its field
+fn field<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    previous: usize,
+    name: &'static str,
+) -> Result<(usize, &'a str, &'a str), Located<ParseError>> {
+    let (index, line) = lines
+        .next()
+        .ok_or_else(|| Located::new(previous + 2, 1, ParseError::MissingLine(name)))?;
+
+    match strip_words(line, name) {
+        Some(value) => Ok((index, line, value)),
+        None => {
+            let error = ParseError::UnexpectedLine {
+                expected: name,
+                found: line.trim().to_string(),
+            };
+            Err(Located::new(
+                index + 1,
+                column(line, line.trim_start()),
+                error,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let monkeys = parse(include_str!("../simple_input.txt")).unwrap();
+
+        assert_eq!(monkeys.len(), 4);
+        assert_eq!(monkeys[1].items, [54, 65, 75, 74]);
+        assert_eq!(monkeys[2].operation, Expr::parse("old * old").unwrap());
+        assert_eq!(monkeys[3].divisor, 17);
+        assert_eq!((monkeys[0].if_true, monkeys[0].if_false), (2, 3));
+    }
+
+    #[test]
+    fn tolerant() {
+        let notes = concat!(
+            "\n\n  Monkey 0\r\n",
+            "Starting items:\n",
+            "Operation:new=old*old +3\n",
+            "\n",
+            "   Test:   divisible by 5  \n",
+            "If true: throw to monkey 1\n",
+            "If false: throw to monkey 1\n",
+            "Monkey 1:\n",
+            "  Starting items: 7,8\n",
+            "  Operation: new = (old + 1) * 2\n",
+            "  Test: divisible by 2\n",
+            "  If true: throw to monkey 0\n",
+            "  If false: throw to monkey 0\n\n\n"
+        );
+
+        let mut monkeys = parse(notes).unwrap();
+
+        assert_eq!(monkeys.len(), 2);
+        assert!(!monkeys[0].has_item());
+        assert_eq!(monkeys[0].operation.to_string(), "old * old + 3");
+        assert_eq!(monkeys[0].divisor, 5);
+        let item = monkeys[1].items.pop_front().unwrap();
+        assert_eq!(monkeys[1].inspect(&item), Some(16));
+        assert_eq!(monkeys[1].inspected, 1);
+        assert_eq!(monkeys[1].target(&16), 0);
+    }
+
+    #[test]
+    fn expression_error_column() {
+        let notes = include_str!("../simple_input.txt").replace("old + 6", "old + (6 * old");
+
+        assert_eq!(
+            parse(&notes).unwrap_err().to_string(),
+            "line 10, column 34: expected ')', found the end of the operation"
+        );
+    }
+
+    #[test]
+    fn throw_to_itself() {
+        let notes = include_str!("../simple_input.txt").replace(
+            "false: throw to monkey 3\n\nMonkey 1",
+            "false: throw to monkey 0\n\nMonkey 1",
+        );
+
+        assert_eq!(
+            parse(&notes).unwrap_err().to_string(),
+            "line 6, column 31: monkey 0 throws to itself"
+        );
+    }
+
+    #[test]
+    fn unexpected_monkey() {
+        let notes = include_str!("../simple_input.txt").replace("Monkey 2:", "Monkey 5:");
+
+        assert_eq!(
+            parse(&notes).unwrap_err().to_string(),
+            "line 15, column 1: expected 'Monkey 2:', found 'Monkey 5:'"
+        );
+    }
+}
@@ -0,0 +1,666 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::error::SimulationError;
+use crate::monkey::Monkey;
+use crate::worry::Worry;
+
+/// What happens to the worry level of an item once a monkey is done inspecting it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relief {
+    /// The worry level is divided by this number, the simulation failing if it is 0
+    Divide(u64),
+    /// The worry level is reduced modulo the least common multiple of the divisors, which
+    /// keeps the results of the tests without the worry levels growing. The simulation fails if
+    /// that multiple doesn't fit in 64 bits.
+    Modulo,
+    /// The worry level is kept as it is, the simulation failing once it overflows, which
+    /// `BigUint` worry levels never do
+    None,
+}
+
+/// How the rounds are simulated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// The monkeys take turns throwing their items, round after round
+    Rounds,
+    /// Each item is followed on its own, its path only depending on its worry level, until it
+    /// is back to a monkey and a worry level it had at the start of a round. The inspections of
+    /// the rounds left are then worked out from that cycle.
+    ///
+    /// The worry levels only repeat once they are kept small, by `Relief::Modulo`. An item
+    /// without a cycle in its first `HISTORY` rounds is followed without looking for one
+    /// anymore. The items end up held by the same monkeys as with `Rounds`, but not in the
+    /// same order.
+    Items,
+}
+
+/// Rounds an item is followed while looking for a cycle, which bounds the memory used
+pub const HISTORY: usize = 1 << 16;
+
+/// Watches a simulation done round by round, item by item
+pub trait Observer<W> {
+    /// A monkey inspected an item, its worry level going from `old` to `new`
+    fn inspect(&mut self, _round: usize, _monkey: usize, _old: &W, _new: &W) {}
+
+    /// The worry level went down from `before` to `after` once the monkey was done
+    fn relief(&mut self, _round: usize, _monkey: usize, _before: &W, _after: &W) {}
+
+    /// The monkey tested whether the worry level is a multiple of its divisor
+    fn test(&mut self, _round: usize, _monkey: usize, _worry: &W, _divisible: bool) {}
+
+    /// An item was thrown from a monkey to another
+    fn throw(&mut self, _round: usize, _from: usize, _to: usize, _worry: &W) {}
+}
+
+impl<W> Observer<W> for () {}
+
+/// A pair of observers, so that a single run feeds them both, in the order of the pair
+impl<W, A: Observer<W>, B: Observer<W>> Observer<W> for (A, B) {
+    fn inspect(&mut self, round: usize, monkey: usize, old: &W, new: &W) {
+        self.0.inspect(round, monkey, old, new);
+        self.1.inspect(round, monkey, old, new);
+    }
+
+    fn relief(&mut self, round: usize, monkey: usize, before: &W, after: &W) {
+        self.0.relief(round, monkey, before, after);
+        self.1.relief(round, monkey, before, after);
+    }
+
+    fn test(&mut self, round: usize, monkey: usize, worry: &W, divisible: bool) {
+        self.0.test(round, monkey, worry, divisible);
+        self.1.test(round, monkey, worry, divisible);
+    }
+
+    fn throw(&mut self, round: usize, from: usize, to: usize, worry: &W) {
+        self.0.throw(round, from, to, worry);
+        self.1.throw(round, from, to, worry);
+    }
+}
+
+/// Lets a pair borrow its observers, which are still there after the run
+impl<W, O: Observer<W> + ?Sized> Observer<W> for &mut O {
+    fn inspect(&mut self, round: usize, monkey: usize, old: &W, new: &W) {
+        (**self).inspect(round, monkey, old, new)
+    }
+
+    fn relief(&mut self, round: usize, monkey: usize, before: &W, after: &W) {
+        (**self).relief(round, monkey, before, after)
+    }
+
+    fn test(&mut self, round: usize, monkey: usize, worry: &W, divisible: bool) {
+        (**self).test(round, monkey, worry, divisible)
+    }
+
+    fn throw(&mut self, round: usize, from: usize, to: usize, worry: &W) {
+        (**self).throw(round, from, to, worry)
+    }
+}
+
+/// Decides after each round whether the simulation stops, from the number of rounds done and
+/// the monkeys
+pub type StopCondition<W = u64> = Box<dyn FnMut(usize, &[Monkey<W>]) -> bool>;
+
+/// The monkeys throwing their items round after round
+pub struct Simulation<W = u64> {
+    monkeys: Vec<Monkey<W>>,
+    relief: Relief,
+    mode: Mode,
+    stop: Option<StopCondition<W>>,
+    /// Number of rounds done
+    round: usize,
+}
+
+impl<W: Worry> Simulation<W> {
+    /// Simulate the monkeys, their worry levels being divided by 3 after each inspection
+    pub fn new(monkeys: Vec<Monkey<W>>) -> Self {
+        Simulation {
+            monkeys,
+            relief: Relief::Divide(3),
+            mode: Mode::Rounds,
+            stop: None,
+            round: 0,
+        }
+    }
+
+    pub fn relief(mut self, relief: Relief) -> Self {
+        self.relief = relief;
+        self
+    }
+
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Stop the runs early, once `stop` is true at the end of a round
+    pub fn stop_when(mut self, stop: impl FnMut(usize, &[Monkey<W>]) -> bool + 'static) -> Self {
+        self.stop = Some(Box::new(stop));
+        self
+    }
+
+    pub fn monkeys(&self) -> &[Monkey<W>] {
+        &self.monkeys
+    }
+
+    /// Number of rounds done
+    pub fn round(&self) -> usize {
+        self.round
+    }
+
+    /// Do `rounds` more rounds, unless the stop condition ends the run sooner
+    pub fn run(&mut self, rounds: usize) -> Result<Report, SimulationError> {
+        match self.mode {
+            Mode::Rounds => self.run_rounds(rounds, &mut ())?,
+            Mode::Items if self.stop.is_some() => return Err(SimulationError::StopCondition),
+            Mode::Items => self.run_items(rounds)?,
+        }
+
+        Ok(self.report())
+    }
+
+    /// Do `rounds` more rounds like `run`, the observer seeing everything the monkeys do.
+    ///
+    /// The monkeys always take turns round after round, whatever the mode.
+    pub fn run_with(
+        &mut self,
+        rounds: usize,
+        observer: &mut impl Observer<W>,
+    ) -> Result<Report, SimulationError> {
+        self.run_rounds(rounds, observer)?;
+
+        Ok(self.report())
+    }
+
+    fn run_rounds(
+        &mut self,
+        rounds: usize,
+        observer: &mut impl Observer<W>,
+    ) -> Result<(), SimulationError> {
+        let modulus = self.modulus()?;
+
+        for _ in 0..rounds {
+            self.round += 1;
+
+            for index in 0..self.monkeys.len() {
+                while let Some(item) = self.monkeys[index].items.front() {
+                    // Worked out before the item is taken and counted, so that an overflow
+                    // leaves it with the monkey
+                    let worry = self.monkeys[index].operation.eval(item).ok_or_else(|| {
+                        SimulationError::Overflow {
+                            round: self.round,
+                            monkey: index,
+                            item: item.to_string(),
+                        }
+                    })?;
+                    let monkey = &mut self.monkeys[index];
+                    let item = monkey.items.pop_front().expect("the item was just seen");
+                    monkey.inspected += 1;
+                    observer.inspect(self.round, index, &item, &worry);
+
+                    let relieved = self.relieve(&worry, modulus);
+                    observer.relief(self.round, index, &worry, &relieved);
+
+                    let monkey = &self.monkeys[index];
+                    let divisible = monkey.is_divisible(&relieved);
+                    observer.test(self.round, index, &relieved, divisible);
+
+                    let target = if divisible {
+                        monkey.if_true
+                    } else {
+                        monkey.if_false
+                    };
+                    observer.throw(self.round, index, target, &relieved);
+                    self.monkeys[target].items.push_back(relieved);
+                }
+            }
+
+            if let Some(stop) = self.stop.as_mut() {
+                if stop(self.round, &self.monkeys) {
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Follow every item, the monkeys being left as they were if one of them fails
+    fn run_items(&mut self, rounds: usize) -> Result<(), SimulationError> {
+        let modulus = self.modulus()?;
+        // Items held and inspections done by each monkey at the end of the run
+        let mut held = vec![VecDeque::new(); self.monkeys.len()];
+        let mut inspected = vec![0; self.monkeys.len()];
+
+        for (index, monkey) in self.monkeys.iter().enumerate() {
+            for item in &monkey.items {
+                let (holder, worry, inspections) =
+                    self.follow(index, item.clone(), rounds, modulus)?;
+
+                for (total, count) in inspected.iter_mut().zip(inspections) {
+                    *total += count;
+                }
+                held[holder].push_back(worry);
+            }
+        }
+
+        for ((monkey, items), count) in self.monkeys.iter_mut().zip(held).zip(inspected) {
+            monkey.items = items;
+            monkey.inspected += count;
+        }
+        self.round += rounds;
+
+        Ok(())
+    }
+
+    /// Follow an item held by `monkey` for `rounds` rounds, returning the monkey holding it at
+    /// the end, its worry level and the number of times each monkey inspected it
+    fn follow(
+        &self,
+        monkey: usize,
+        item: W,
+        rounds: usize,
+        modulus: Option<u64>,
+    ) -> Result<(usize, W, Vec<u64>), SimulationError> {
+        // Round at the start of which the item was at a monkey with a worry level
+        let mut seen = HashMap::<(usize, W), usize>::new();
+        // Monkey and worry level of the item at the start of each round
+        let mut states = vec![(monkey, item)];
+        // Inspections of each monkey before each round
+        let mut inspections = vec![vec![0; self.monkeys.len()]];
+
+        loop {
+            let round = states.len() - 1;
+            if round == rounds {
+                let (holder, worry) = states.swap_remove(round);
+                return Ok((holder, worry, inspections.swap_remove(round)));
+            }
+
+            // The item goes through the same rounds again, so the rounds left are whole cycles
+            // and the start of a cycle
+            if let Some(&start) = seen.get(&states[round]) {
+                let length = round - start;
+                let cycles = ((rounds - round) / length) as u64;
+                let rest = (rounds - round) % length;
+
+                let total = (0..self.monkeys.len())
+                    .map(|m| {
+                        let cycle = inspections[round][m] - inspections[start][m];
+                        let partial = inspections[start + rest][m] - inspections[start][m];
+                        inspections[round][m] + cycles * cycle + partial
+                    })
+                    .collect();
+                let (holder, worry) = states.swap_remove(start + rest);
+
+                return Ok((holder, worry, total));
+            }
+
+            // Probably no cycle at all, as without `Relief::Modulo`: the rounds left are
+            // followed one by one, forgetting the ones done
+            if round == HISTORY {
+                let (mut holder, mut worry) = states.swap_remove(round);
+                let mut counts = inspections.swap_remove(round);
+                drop((seen, states, inspections));
+
+                for round in HISTORY..rounds {
+                    (holder, worry) =
+                        self.follow_round(holder, worry, &mut counts, round, modulus)?;
+                }
+                return Ok((holder, worry, counts));
+            }
+
+            seen.insert(states[round].clone(), round);
+            let (holder, worry) = states[round].clone();
+            let mut counts = inspections[round].clone();
+
+            let (holder, worry) = self.follow_round(holder, worry, &mut counts, round, modulus)?;
+            states.push((holder, worry));
+            inspections.push(counts);
+        }
+    }
+
+    /// Follow an item held by `holder` during `round` of the run (from 0), counting the
+    /// inspections, and return the monkey and worry level it has at the start of the next one
+    fn follow_round(
+        &self,
+        mut holder: usize,
+        mut worry: W,
+        counts: &mut [u64],
+        round: usize,
+        modulus: Option<u64>,
+    ) -> Result<(usize, W), SimulationError> {
+        // The item goes on being thrown during the round while it goes to monkeys that haven't
+        // had their turn yet
+        loop {
+            let monkey = &self.monkeys[holder];
+            counts[holder] += 1;
+
+            let new = monkey
+                .operation
+                .eval(&worry)
+                .ok_or_else(|| SimulationError::Overflow {
+                    round: self.round + round + 1,
+                    monkey: holder,
+                    item: worry.to_string(),
+                })?;
+            worry = self.relieve(&new, modulus);
+
+            let target = monkey.target(&worry);
+            let next_round = target < holder;
+            holder = target;
+            if next_round {
+                return Ok((holder, worry));
+            }
+        }
+    }
+
+    fn relieve(&self, worry: &W, modulus: Option<u64>) -> W {
+        match (self.relief, modulus) {
+            (Relief::Divide(k), _) => worry.divided_by(k),
+            (_, Some(modulus)) => W::from_u64(worry.remainder(modulus)),
+            _ => worry.clone(),
+        }
+    }
+
+    /// The inspections done so far
+    pub fn report(&self) -> Report {
+        Report {
+            rounds: self.round,
+            inspections: self.monkeys.iter().map(|m| m.inspected).collect(),
+        }
+    }
+
+    /// Least common multiple of the divisors of the tests for `Relief::Modulo`, `None` for the
+    /// other reliefs, which are checked
+    fn modulus(&self) -> Result<Option<u64>, SimulationError> {
+        match self.relief {
+            Relief::Divide(0) => Err(SimulationError::ZeroRelief),
+            Relief::Divide(_) | Relief::None => Ok(None),
+            Relief::Modulo => self
+                .monkeys
+                .iter()
+                .map(|m| m.divisor)
+                .try_fold(1u64, |lcm, divisor| {
+                    (lcm / gcd(lcm, divisor)).checked_mul(divisor)
+                })
+                .map(Some)
+                .ok_or(SimulationError::ModulusOverflow),
+        }
+    }
+}
+
+/// Number of items each monkey inspected after some rounds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub rounds: usize,
+    /// Inspections of each monkey, in the order of the notes
+    pub inspections: Vec<u64>,
+}
+
+impl Report {
+    /// Product of the inspections of the `n` most active monkeys, `None` if there are fewer
+    /// monkeys than that
+    pub fn monkey_business(&self, n: usize) -> Option<u128> {
+        if self.inspections.len() < n {
+            return None;
+        }
+
+        let mut inspections = self.inspections.clone();
+        inspections.sort_unstable_by(|a, b| b.cmp(a));
+
+        Some(inspections[..n].iter().map(|&i| i as u128).product())
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monkey;
+    use crate::trace::{Event, Trace};
+    use crate::worry::BigUint;
+
+    fn simulation() -> Simulation {
+        Simulation::new(monkey::parse(include_str!("../simple_input.txt")).unwrap())
+    }
+
+    #[test]
+    fn inspections() {
+        let mut simulation = simulation();
+
+        let report = simulation.run(20).unwrap();
+
+        assert_eq!(report.inspections, [101, 95, 7, 105]);
+        assert_eq!(report.monkey_business(2), Some(10605));
+        assert_eq!(report.monkey_business(5), None);
+        assert_eq!(simulation.monkeys()[0].items, [10, 12, 14, 26, 34]);
+    }
+
+    #[test]
+    fn runs_go_on() {
+        let mut simulation = simulation().relief(Relief::Modulo);
+
+        simulation.run(1).unwrap();
+        simulation.run(19).unwrap();
+
+        assert_eq!(simulation.round(), 20);
+        assert_eq!(simulation.report().inspections, [99, 97, 8, 103]);
+    }
+
+    #[test]
+    fn stop_condition() {
+        let mut simulation = simulation()
+            .relief(Relief::Modulo)
+            .stop_when(|_, monkeys| monkeys.iter().any(|m| m.inspected >= 1000));
+
+        let report = simulation.run(10000).unwrap();
+
+        assert!(report.rounds < 10000);
+        assert!(report.inspections.iter().any(|&i| i >= 1000));
+        assert_eq!(simulation.run(1).unwrap().rounds, report.rounds + 1);
+    }
+
+    #[test]
+    fn overflow() {
+        let mut simulation = simulation().relief(Relief::None);
+        let mut trace = Trace::default();
+
+        // Monkey 2 squares the worry levels, which soon don't fit
+        let error = simulation.run_with(20, &mut trace).unwrap_err();
+
+        assert_eq!(
+            error,
+            SimulationError::Overflow {
+                round: 13,
+                monkey: 0,
+                item: String::from("13988703546165100909")
+            }
+        );
+        assert_eq!(simulation.round(), 13);
+
+        // The item is still there and only the inspections done are counted
+        let items: Vec<&u64> = simulation.monkeys().iter().flat_map(|m| &m.items).collect();
+        assert_eq!(items.len(), 10);
+        assert_eq!(
+            simulation.monkeys()[0].items.front(),
+            Some(&13988703546165100909)
+        );
+        let inspections = trace
+            .events
+            .iter()
+            .filter(|e| matches!(e, Event::Inspect { .. }))
+            .count() as u64;
+        assert_eq!(
+            simulation.report().inspections.iter().sum::<u64>(),
+            inspections
+        );
+    }
+
+    /// Items held by each monkey, whatever their order
+    fn held(simulation: &Simulation) -> Vec<Vec<u64>> {
+        simulation
+            .monkeys()
+            .iter()
+            .map(|m| {
+                let mut items = Vec::from(m.items.clone());
+                items.sort_unstable();
+                items
+            })
+            .collect()
+    }
+
+    #[test]
+    fn items_mode_matches_rounds() {
+        for (relief, rounds) in [(Relief::Divide(3), 20), (Relief::Modulo, 10000)] {
+            let mut by_rounds = simulation().relief(relief);
+            let mut by_items = simulation().relief(relief).mode(Mode::Items);
+
+            assert_eq!(by_items.run(rounds), by_rounds.run(rounds));
+            assert_eq!(held(&by_items), held(&by_rounds));
+
+            // Following runs start from where the items are
+            assert_eq!(by_items.run(7), by_rounds.run(7));
+            assert_eq!(held(&by_items), held(&by_rounds));
+        }
+    }
+
+    #[test]
+    fn items_without_cycle() {
+        let notes = concat!(
+            "Monkey 0:\n",
+            "  Starting items: 1, 2\n",
+            "  Operation: new = old + 1\n",
+            "  Test: divisible by 2\n",
+            "    If true: throw to monkey 1\n",
+            "    If false: throw to monkey 1\n",
+            "\n",
+            "Monkey 1:\n",
+            "  Starting items:\n",
+            "  Operation: new = old + 1\n",
+            "  Test: divisible by 3\n",
+            "    If true: throw to monkey 0\n",
+            "    If false: throw to monkey 0\n",
+        );
+        let monkeys = monkey::parse(notes).unwrap();
+        let rounds = HISTORY + 1000;
+
+        // The worry levels only grow, so the items are followed past the history
+        let mut by_items = Simulation::new(monkeys.clone())
+            .relief(Relief::None)
+            .mode(Mode::Items);
+        let mut by_rounds = Simulation::new(monkeys).relief(Relief::None);
+
+        assert_eq!(by_items.run(rounds), by_rounds.run(rounds));
+        assert_eq!(held(&by_items), held(&by_rounds));
+        assert_eq!(
+            held(&by_items)[0],
+            [2 * rounds as u64 + 1, 2 * rounds as u64 + 2]
+        );
+    }
+
+    #[test]
+    fn billion_rounds() {
+        let mut simulation = simulation().relief(Relief::Modulo).mode(Mode::Items);
+
+        let report = simulation.run(1_000_000_000).unwrap();
+
+        assert_eq!(report.rounds, 1_000_000_000);
+        assert_eq!(
+            report.inspections,
+            [5217653496, 4782346500, 193256586, 5202028499]
+        );
+        assert_eq!(simulation.run(0).unwrap().inspections, report.inspections);
+    }
+
+    #[test]
+    fn items_mode_overflow() {
+        let mut simulation = simulation().relief(Relief::None).mode(Mode::Items);
+        let monkeys = simulation.monkeys().to_vec();
+
+        assert!(matches!(
+            simulation.run(20),
+            Err(SimulationError::Overflow { .. })
+        ));
+        // No item is lost and no inspection counted
+        assert_eq!(simulation.monkeys(), monkeys);
+        assert_eq!(simulation.round(), 0);
+    }
+
+    #[test]
+    fn items_mode_without_stop_condition() {
+        let mut simulation = simulation()
+            .mode(Mode::Items)
+            .stop_when(|round, _| round == 5);
+
+        assert_eq!(simulation.run(10), Err(SimulationError::StopCondition));
+    }
+
+    #[test]
+    fn modulo_matches_exact_worry_levels() {
+        let monkeys = monkey::parse(include_str!("../simple_input.txt")).unwrap();
+        let mut exact: Simulation<BigUint> =
+            Simulation::new(monkeys.into_iter().map(Monkey::with_worry).collect())
+                .relief(Relief::None);
+
+        let report = exact.run(100).unwrap();
+
+        assert_eq!(
+            report,
+            simulation().relief(Relief::Modulo).run(100).unwrap()
+        );
+        // The worry levels went way past 64 bits
+        let bits = exact
+            .monkeys()
+            .iter()
+            .flat_map(|m| &m.items)
+            .map(BigUint::bits);
+        assert_eq!(bits.max(), Some(1156));
+    }
+
+    #[test]
+    fn least_common_multiple() {
+        let mut monkeys = monkey::parse(include_str!("../simple_input.txt")).unwrap();
+        for (monkey, divisor) in monkeys.iter_mut().zip([4, 6, 10, 15]) {
+            monkey.divisor = divisor;
+        }
+
+        let simulation = Simulation::new(monkeys).relief(Relief::Modulo);
+        assert_eq!(simulation.modulus(), Ok(Some(60)));
+        assert_eq!(simulation.relief(Relief::Divide(3)).modulus(), Ok(None));
+    }
+
+    #[test]
+    fn large_divisors() {
+        let mut monkeys = monkey::parse(include_str!("../simple_input.txt")).unwrap();
+        for (monkey, divisor) in monkeys.iter_mut().zip([1000003, 1000033, 1000037, 1000039]) {
+            monkey.divisor = divisor;
+        }
+
+        // Only the modulo relief needs their least common multiple
+        let mut simulation = Simulation::new(monkeys.clone());
+        assert_eq!(simulation.run(20).unwrap().rounds, 20);
+
+        for mode in [Mode::Rounds, Mode::Items] {
+            let mut simulation = Simulation::new(monkeys.clone())
+                .relief(Relief::Modulo)
+                .mode(mode);
+
+            assert_eq!(simulation.run(20), Err(SimulationError::ModulusOverflow));
+            assert_eq!(simulation.report().inspections, [0; 4]);
+        }
+    }
+
+    #[test]
+    fn zero_relief() {
+        let mut simulation = simulation().relief(Relief::Divide(0));
+
+        assert_eq!(simulation.run(1), Err(SimulationError::ZeroRelief));
+        assert_eq!(simulation.round(), 0);
+    }
+}
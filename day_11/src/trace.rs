@@ -0,0 +1,332 @@
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use crate::monkey::Monkey;
+use crate::simulation::Observer;
+
+/// Something a monkey did to an item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<W> {
+    Inspect {
+        round: usize,
+        monkey: usize,
+        old: W,
+        new: W,
+    },
+    Relief {
+        round: usize,
+        monkey: usize,
+        before: W,
+        after: W,
+    },
+    Test {
+        round: usize,
+        monkey: usize,
+        worry: W,
+        divisible: bool,
+    },
+    Throw {
+        round: usize,
+        from: usize,
+        to: usize,
+        worry: W,
+    },
+}
+
+impl<W: Display> Display for Event<W> {
+    /// Write the event as a JSON object
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Event::Inspect {
+                round,
+                monkey,
+                old,
+                new,
+            } => write!(
+                f,
+                "{{\"round\":{},\"event\":\"inspect\",\"monkey\":{},\"old\":{},\"new\":{}}}",
+                round, monkey, old, new
+            ),
+            Event::Relief {
+                round,
+                monkey,
+                before,
+                after,
+            } => write!(
+                f,
+                "{{\"round\":{},\"event\":\"relief\",\"monkey\":{},\"before\":{},\"after\":{}}}",
+                round, monkey, before, after
+            ),
+            Event::Test {
+                round,
+                monkey,
+                worry,
+                divisible,
+            } => write!(
+                f,
+                "{{\"round\":{},\"event\":\"test\",\"monkey\":{},\"worry\":{},\"divisible\":{}}}",
+                round, monkey, worry, divisible
+            ),
+            Event::Throw {
+                round,
+                from,
+                to,
+                worry,
+            } => write!(
+                f,
+                "{{\"round\":{},\"event\":\"throw\",\"from\":{},\"to\":{},\"worry\":{}}}",
+                round, from, to, worry
+            ),
+        }
+    }
+}
+
+/// Records everything the monkeys do during a simulation
+#[derive(Debug, Clone)]
+pub struct Trace<W = u64> {
+    pub events: Vec<Event<W>>,
+}
+
+impl<W> Default for Trace<W> {
+    fn default() -> Self {
+        Trace { events: Vec::new() }
+    }
+}
+
+impl<W: Clone> Observer<W> for Trace<W> {
+    fn inspect(&mut self, round: usize, monkey: usize, old: &W, new: &W) {
+        self.events.push(Event::Inspect {
+            round,
+            monkey,
+            old: old.clone(),
+            new: new.clone(),
+        });
+    }
+
+    fn relief(&mut self, round: usize, monkey: usize, before: &W, after: &W) {
+        self.events.push(Event::Relief {
+            round,
+            monkey,
+            before: before.clone(),
+            after: after.clone(),
+        });
+    }
+
+    fn test(&mut self, round: usize, monkey: usize, worry: &W, divisible: bool) {
+        self.events.push(Event::Test {
+            round,
+            monkey,
+            worry: worry.clone(),
+            divisible,
+        });
+    }
+
+    fn throw(&mut self, round: usize, from: usize, to: usize, worry: &W) {
+        self.events.push(Event::Throw {
+            round,
+            from,
+            to,
+            worry: worry.clone(),
+        });
+    }
+}
+
+impl<W: Display> Trace<W> {
+    /// Write the events as JSON lines, one object per event
+    pub fn write_jsonl<O: Write>(&self, mut out: O) -> io::Result<()> {
+        for event in &self.events {
+            writeln!(out, "{}", event)?;
+        }
+
+        out.flush()
+    }
+}
+
+/// Counts the items each monkey threw to each other monkey
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThrowGraph {
+    /// Throws from a monkey, to each monkey
+    pub throws: Vec<Vec<u64>>,
+}
+
+impl<W> Observer<W> for ThrowGraph {
+    /// Count the throw, the graph growing if it comes from or goes to monkeys it didn't have
+    fn throw(&mut self, _round: usize, from: usize, to: usize, _worry: &W) {
+        let monkeys = from.max(to) + 1;
+        if monkeys > self.throws.len() {
+            self.throws.resize_with(monkeys, Vec::new);
+            for row in &mut self.throws {
+                row.resize(monkeys, 0);
+            }
+        }
+
+        self.throws[from][to] += 1;
+    }
+}
+
+impl ThrowGraph {
+    /// No throws yet between the monkeys of a simulation, given by `Simulation::monkeys`, so
+    /// that the ones that never throw nor catch are in the graph as well
+    pub fn new<W>(monkeys: &[Monkey<W>]) -> Self {
+        ThrowGraph {
+            throws: vec![vec![0; monkeys.len()]; monkeys.len()],
+        }
+    }
+
+    /// Write the graph in the DOT language of Graphviz, the weight of each edge being the
+    /// number of throws, and the edges with the most throws being the thickest
+    pub fn write_dot<O: Write>(&self, mut out: O) -> io::Result<()> {
+        let most = self.throws.iter().flatten().copied().max().unwrap_or(0);
+
+        writeln!(out, "digraph monkeys {{")?;
+        for (monkey, throws) in self.throws.iter().enumerate() {
+            let total: u64 = throws.iter().sum();
+            writeln!(
+                out,
+                "    {} [label=\"Monkey {}\\n{} throws\"];",
+                monkey, monkey, total
+            )?;
+        }
+        for (from, throws) in self.throws.iter().enumerate() {
+            for (to, &count) in throws.iter().enumerate().filter(|(_, &count)| count > 0) {
+                let width = 1.0 + 4.0 * count as f64 / most as f64;
+                writeln!(
+                    out,
+                    "    {} -> {} [label=\"{}\", weight={}, penwidth={:.2}];",
+                    from, to, count, count, width
+                )?;
+            }
+        }
+        writeln!(out, "}}")?;
+
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monkey;
+    use crate::simulation::Simulation;
+
+    fn simulation() -> Simulation {
+        Simulation::new(monkey::parse(include_str!("../simple_input.txt")).unwrap())
+    }
+
+    #[test]
+    fn first_item() {
+        let mut trace = Trace::default();
+
+        simulation().run_with(1, &mut trace).unwrap();
+
+        // Monkey 0 inspects 79, as told in the puzzle
+        assert_eq!(
+            trace.events[..4],
+            [
+                Event::Inspect {
+                    round: 1,
+                    monkey: 0,
+                    old: 79,
+                    new: 1501
+                },
+                Event::Relief {
+                    round: 1,
+                    monkey: 0,
+                    before: 1501,
+                    after: 500
+                },
+                Event::Test {
+                    round: 1,
+                    monkey: 0,
+                    worry: 500,
+                    divisible: false
+                },
+                Event::Throw {
+                    round: 1,
+                    from: 0,
+                    to: 3,
+                    worry: 500
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn jsonl() {
+        let mut trace = Trace::default();
+        let mut jsonl = Vec::new();
+
+        simulation().run_with(20, &mut trace).unwrap();
+        trace.write_jsonl(&mut jsonl).unwrap();
+
+        let jsonl = String::from_utf8(jsonl).unwrap();
+        assert_eq!(
+            jsonl.lines().take(4).collect::<Vec<_>>(),
+            [
+                "{\"round\":1,\"event\":\"inspect\",\"monkey\":0,\"old\":79,\"new\":1501}",
+                "{\"round\":1,\"event\":\"relief\",\"monkey\":0,\"before\":1501,\"after\":500}",
+                "{\"round\":1,\"event\":\"test\",\"monkey\":0,\"worry\":500,\"divisible\":false}",
+                "{\"round\":1,\"event\":\"throw\",\"from\":0,\"to\":3,\"worry\":500}",
+            ]
+        );
+        // 4 events for each inspection
+        assert_eq!(jsonl.lines().count(), 4 * (101 + 95 + 7 + 105));
+        assert!(jsonl.lines().last().unwrap().starts_with("{\"round\":20,"));
+    }
+
+    #[test]
+    fn throw_graph() {
+        let mut simulation = simulation();
+        let mut graph = ThrowGraph::new(simulation.monkeys());
+        let mut dot = Vec::new();
+
+        let report = simulation.run_with(20, &mut graph).unwrap();
+        graph.write_dot(&mut dot).unwrap();
+
+        // Every inspection ends with a throw
+        let throws: Vec<u64> = graph.throws.iter().map(|to| to.iter().sum()).collect();
+        assert_eq!(throws, report.inspections);
+
+        let dot = String::from_utf8(dot).unwrap();
+        assert!(dot.starts_with("digraph monkeys {\n    0 [label=\"Monkey 0\\n101 throws\"];\n"));
+        assert!(dot.ends_with("}\n"));
+        for (from, throws) in graph.throws.iter().enumerate() {
+            for (to, &count) in throws.iter().enumerate().filter(|(_, &count)| count > 0) {
+                let edge = format!(
+                    "    {} -> {} [label=\"{}\", weight={},",
+                    from, to, count, count
+                );
+                assert!(dot.contains(&edge));
+            }
+        }
+    }
+
+    #[test]
+    fn both_observers() {
+        let mut simulation = simulation();
+        let mut trace = Trace::default();
+        let mut graph = ThrowGraph::new(simulation.monkeys());
+
+        simulation
+            .run_with(3, &mut (&mut trace, &mut graph))
+            .unwrap();
+
+        let throws = trace
+            .events
+            .iter()
+            .filter(|e| matches!(e, Event::Throw { .. }))
+            .count() as u64;
+        assert_eq!(throws, graph.throws.iter().flatten().sum::<u64>());
+    }
+
+    #[test]
+    fn throw_graph_grows() {
+        let mut graph = ThrowGraph::new::<u64>(&[]);
+
+        simulation().run_with(1, &mut graph).unwrap();
+
+        assert_eq!(graph.throws.len(), 4);
+        assert!(graph.throws.iter().all(|to| to.len() == 4));
+        assert_eq!(graph.throws[0][3], 2);
+    }
+}
@@ -0,0 +1,219 @@
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+
+/// A worry level, which the operations of the monkeys can make overflow
+pub trait Worry: Clone + Eq + Hash + Debug + Display {
+    fn from_u64(n: u64) -> Self;
+
+    /// `self + other`, `None` if it overflows
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+
+    /// `self * other`, `None` if it overflows
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+
+    /// `self / divisor`, rounded down
+    fn divided_by(&self, divisor: u64) -> Self;
+
+    /// `self % divisor`
+    fn remainder(&self, divisor: u64) -> u64;
+
+    fn is_multiple_of(&self, divisor: u64) -> bool {
+        self.remainder(divisor) == 0
+    }
+}
+
+impl Worry for u64 {
+    fn from_u64(n: u64) -> Self {
+        n
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        u64::checked_add(*self, *other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        u64::checked_mul(*self, *other)
+    }
+
+    fn divided_by(&self, divisor: u64) -> Self {
+        self / divisor
+    }
+
+    fn remainder(&self, divisor: u64) -> u64 {
+        self % divisor
+    }
+}
+
+/// An unsigned integer as large as needed, to follow the worry levels exactly
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    /// Digits in base 2^32, the least significant first, without zeros at the end
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    /// Number of bits needed to write the integer, 0 for 0
+    pub fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            Some(last) => self.limbs.len() as u64 * 32 - last.leading_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    /// Quotient and remainder of the division by `divisor`, which must not be 0
+    fn div_rem(&self, divisor: u64) -> (BigUint, u64) {
+        let divisor = divisor as u128;
+        let mut limbs = vec![0; self.limbs.len()];
+        let mut remainder = 0u128;
+
+        for (index, &limb) in self.limbs.iter().enumerate().rev() {
+            let current = (remainder << 32) | limb as u128;
+            limbs[index] = (current / divisor) as u32;
+            remainder = current % divisor;
+        }
+
+        (BigUint { limbs }.normalized(), remainder as u64)
+    }
+
+    fn normalized(mut self) -> Self {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+}
+
+impl Worry for BigUint {
+    fn from_u64(n: u64) -> Self {
+        BigUint {
+            limbs: vec![n as u32, (n >> 32) as u32],
+        }
+        .normalized()
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        let mut limbs = Vec::with_capacity(self.limbs.len().max(other.limbs.len()) + 1);
+        let mut carry = 0u64;
+
+        for index in 0..self.limbs.len().max(other.limbs.len()) {
+            let a = self.limbs.get(index).copied().unwrap_or(0) as u64;
+            let b = other.limbs.get(index).copied().unwrap_or(0) as u64;
+            let sum = a + b + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+        limbs.push(carry as u32);
+
+        Some(BigUint { limbs }.normalized())
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                // Can't overflow: (2^32 - 1)^2 + 2 * (2^32 - 1) < 2^64
+                let product = a as u64 * b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = product as u32;
+                carry = product >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+
+        Some(BigUint { limbs }.normalized())
+    }
+
+    fn divided_by(&self, divisor: u64) -> Self {
+        self.div_rem(divisor).0
+    }
+
+    fn remainder(&self, divisor: u64) -> u64 {
+        self.div_rem(divisor).1
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const CHUNK: u64 = 1_000_000_000;
+
+        // Groups of 9 decimal digits, the least significant first
+        let mut chunks = Vec::new();
+        let mut n = self.clone();
+        while !n.limbs.is_empty() {
+            let (quotient, chunk) = n.div_rem(CHUNK);
+            chunks.push(chunk);
+            n = quotient;
+        }
+
+        match chunks.split_last() {
+            Some((first, rest)) => {
+                write!(f, "{}", first)?;
+                for chunk in rest.iter().rev() {
+                    write!(f, "{:09}", chunk)?;
+                }
+                Ok(())
+            }
+            None => write!(f, "0"),
+        }
+    }
+}
+
+impl Debug for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(n: u64) -> BigUint {
+        BigUint::from_u64(n)
+    }
+
+    #[test]
+    fn small_numbers() {
+        assert_eq!(big(0).to_string(), "0");
+        assert_eq!(big(0).bits(), 0);
+        assert_eq!(big(u64::MAX).to_string(), u64::MAX.to_string());
+        assert_eq!(big(u64::MAX).bits(), 64);
+        assert_eq!(big(79).checked_mul(&big(19)), Some(big(1501)));
+        assert_eq!(big(1501).divided_by(3), big(500));
+        assert!(big(1501).is_multiple_of(19));
+    }
+
+    #[test]
+    fn large_numbers() {
+        let max = big(u64::MAX);
+        let square = max.checked_mul(&max).unwrap();
+
+        // (2^64 - 1)^2 = 2^128 - 2^65 + 1
+        assert_eq!(square.to_string(), (u128::MAX - (1 << 65) + 2).to_string());
+        assert_eq!(square.bits(), 128);
+        assert_eq!(square.divided_by(u64::MAX), max);
+        assert_eq!(square.checked_add(&max).unwrap().remainder(u64::MAX), 0);
+        assert_eq!(
+            square
+                .checked_add(&big(1))
+                .unwrap()
+                .remainder(1_000_000_007),
+            ((u128::MAX - (1 << 65) + 3) % 1_000_000_007) as u64
+        );
+
+        // 10^40, with zeros inside the chunks of digits
+        let ten_20 = big(10_000_000_000)
+            .checked_mul(&big(10_000_000_000))
+            .unwrap();
+        let ten_40 = ten_20.checked_mul(&ten_20).unwrap();
+        assert_eq!(ten_40.to_string(), format!("1{}", "0".repeat(40)));
+    }
+
+    #[test]
+    fn u64_overflows() {
+        assert_eq!(Worry::checked_mul(&u64::MAX, &2), None);
+        assert_eq!(Worry::checked_add(&u64::MAX, &0), Some(u64::MAX));
+        assert_eq!(Worry::remainder(&1501u64, 19), 0);
+    }
+}
@@ -0,0 +1,38 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading the heightmap
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A square that isn't a height (a-z), the start (S) or the best signal (E)
+    InvalidHeight(char),
+    /// A row that isn't as wide as the first one
+    InvalidWidth { expected: usize, found: usize },
+    /// The start (S) or the best signal (E) found a second time
+    DuplicateMarker(char),
+    /// The start (S) or the best signal (E) isn't on the map
+    MissingMarker(char),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidHeight(c) => {
+                write!(f, "expected a height (a-z), 'S' or 'E', found '{}'", c)
+            }
+            ParseError::InvalidWidth { expected, found } => {
+                write!(
+                    f,
+                    "expected {} squares in the row, found {}",
+                    expected, found
+                )
+            }
+            ParseError::DuplicateMarker(c) => write!(f, "expected a single '{}', found another", c),
+            ParseError::MissingMarker(c) => {
+                write!(f, "expected '{}' somewhere on the map, found none", c)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
@@ -0,0 +1,3 @@
+pub mod error;
+pub mod part_one;
+pub mod part_two;
@@ -1,46 +1,89 @@
 use std::collections::VecDeque;
-use std::error::Error;
-use std::io;
 
-pub fn main_p2() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
+use aoc_core::{Answer, Located, Solution, SolveError};
+use aoc_grid::{Grid, Pos};
 
-    let mut starts: Vec<(usize, usize)> = Vec::new();
-    let mut mat = Vec::<Vec<HeightNode>>::new();
+use crate::error::ParseError;
 
-    let mut row_idx = 0;
-    for line in lines {
-        let mut row = Vec::new();
-        let mut col_idx = 0;
+pub struct PartTwo;
 
-        let line = line.unwrap();
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let lines = input.lines();
 
-        for c in line.chars() {
-            if c == 'S' || c == 'a' {
-                starts.push((col_idx, row_idx));
+        let mut start: Option<(usize, usize)> = None;
+        let mut end = false;
+        let mut starts: Vec<(usize, usize)> = Vec::new();
+        let mut mat = Vec::<Vec<HeightNode>>::new();
+
+        for (row_idx, line) in lines.enumerate() {
+            let mut row = Vec::new();
+
+            for (col_idx, c) in line.chars().enumerate() {
+                let error = match c {
+                    'a'..='z' => None,
+                    'S' if start.is_some() => Some(ParseError::DuplicateMarker(c)),
+                    'S' => {
+                        start = Some((col_idx, row_idx));
+                        None
+                    }
+                    'E' if end => Some(ParseError::DuplicateMarker(c)),
+                    'E' => {
+                        end = true;
+                        None
+                    }
+                    _ => Some(ParseError::InvalidHeight(c)),
+                };
+                if let Some(error) = error {
+                    return Err(Located::new(row_idx + 1, col_idx + 1, error).into());
+                }
+
+                if c == 'S' || c == 'a' {
+                    starts.push((col_idx, row_idx));
+                }
+                row.push(HeightNode::new(col_idx, row_idx, c));
             }
-            row.push(HeightNode::new(col_idx, row_idx, c));
-            col_idx += 1;
+
+            if let Some(first) = mat.first() {
+                if first.len() != row.len() {
+                    let error = ParseError::InvalidWidth {
+                        expected: first.len(),
+                        found: row.len(),
+                    };
+                    return Err(Located::new(row_idx + 1, 1, error).into());
+                }
+            }
+
+            mat.push(row);
         }
 
-        mat.push(row);
-        row_idx += 1;
-    }
+        let last = input.lines().count().max(1);
+        if start.is_none() {
+            return Err(Located::new(last, 1, ParseError::MissingMarker('S')).into());
+        };
+        if !end {
+            return Err(Located::new(last, 1, ParseError::MissingMarker('E')).into());
+        }
 
-    let map = HeightMap::new(mat);
+        let map = HeightMap::new(mat);
 
-    let mut min_path: u32 = u32::MAX;
+        let mut min_path: u32 = u32::MAX;
 
-    for start in starts {
-        let mut path = FindShortestPath::new(&map);
-        match path.visit(start) {
-            Ok(value) => min_path = min_path.min(value),
-            Err(_) => (),
+        for start in starts {
+            let mut path = FindShortestPath::new(&map);
+            if let Ok(value) = path.visit(start) {
+                min_path = min_path.min(value)
+            }
+        }
+
+        if min_path == u32::MAX {
+            return Err(SolveError::NoSolution(String::from(
+                "no path to the best signal",
+            )));
         }
-    }
-    println!("{}", min_path);
 
-    Ok(())
+        Ok(min_path.into())
+    }
 }
 
 #[derive(Debug)]
@@ -70,34 +113,24 @@ impl HeightNode {
 
 #[derive(Debug)]
 struct HeightMap {
-    map: Vec<Vec<HeightNode>>,
-    height: usize,
-    width: usize,
+    map: Grid<HeightNode>,
 }
 
 impl HeightMap {
     fn new(map: Vec<Vec<HeightNode>>) -> Self {
-        let height = map.len();
-        assert!(height > 0);
-        let width = map[0].len();
-
-        HeightMap { map, height, width }
-    }
+        let map = Grid::from_rows(map).expect("every row of the heightmap has the same width");
+        assert!(!map.is_empty());
 
-    fn height(&self) -> usize {
-        self.height
+        HeightMap { map }
     }
 
-    fn width(&self) -> usize {
-        self.width
+    fn get(&self, x: usize, y: usize) -> Option<&HeightNode> {
+        self.map.get(Pos::new(x as isize, y as isize))
     }
 
-    fn get(&self, x: usize, y: usize) -> Option<&HeightNode> {
-        if x >= self.width || y >= self.height {
-            None
-        } else {
-            Some(&self.map[y][x])
-        }
+    fn neighbours(&self, node: &HeightNode) -> impl Iterator<Item = (Pos, &HeightNode)> {
+        self.map
+            .neighbours4(Pos::new(node.x() as isize, node.y() as isize))
     }
 }
 
@@ -108,47 +141,30 @@ trait Visitor {
 #[derive(Debug)]
 struct FindShortestPath<'a> {
     map: &'a HeightMap,
-    visited: Vec<Vec<bool>>,
-    predecessor: Vec<Vec<&'a HeightNode>>,
+    visited: Grid<bool>,
+    predecessor: Grid<Option<&'a HeightNode>>,
 }
 
 impl<'a> FindShortestPath<'a> {
     fn new(map: &'a HeightMap) -> Self {
-        let mut visited = Vec::new();
-
-        for row in 0..map.height() {
-            visited.push(Vec::new());
-            for _ in 0..map.width() {
-                visited[row].push(false);
-            }
-        }
-
-        let mut predecessor = Vec::new();
-
-        for row in 0..map.height() {
-            predecessor.push(Vec::new());
-            for _ in 0..map.width() {
-                predecessor[row].push(map.get(0, 0).unwrap());
-            }
-        }
-
         FindShortestPath {
             map,
-            visited,
-            predecessor,
+            visited: map.map.map(|_| false),
+            predecessor: map.map.map(|_| None),
         }
     }
 }
 
 impl<'a> Visitor for FindShortestPath<'a> {
     fn visit(&mut self, start: (usize, usize)) -> Result<u32, ()> {
+        let map = self.map;
         let mut q = VecDeque::<&HeightNode>::new();
-        let node = self.map.get(start.0, start.1).unwrap();
+        let node = map.get(start.0, start.1).unwrap();
         let mut depth = 0;
         let mut node_left = 1;
         let mut found = false;
 
-        self.visited[node.y()][node.x()] = true;
+        self.visited[Pos::new(node.x() as isize, node.y() as isize)] = true;
         q.push_back(node);
 
         while !q.is_empty() {
@@ -165,50 +181,13 @@ impl<'a> Visitor for FindShortestPath<'a> {
                 node.value()
             };
 
-            // Check Up node
-            if node.y() != 0 {
-                let up = (node.x(), node.y() - 1);
-                if let Some(n) = self.map.get(up.0, up.1) {
-                    let n_value = if n.value() == 'E' { 'z' } else { n.value() };
-
-                    if !self.visited[up.1][up.0] && ((value as u32) + 1 >= (n_value as u32)) {
-                        self.visited[up.1][up.0] = true;
-                        self.predecessor[up.1][up.0] = node;
-                        q.push_back(&n);
-                    }
-                }
-            }
-            // Check Right node
-            let right = (node.x() + 1, node.y());
-            if let Some(n) = self.map.get(right.0, right.1) {
+            for (pos, n) in map.neighbours(node) {
                 let n_value = if n.value() == 'E' { 'z' } else { n.value() };
-                if !self.visited[right.1][right.0] && ((value as u32) + 1 >= (n_value as u32)) {
-                    self.visited[right.1][right.0] = true;
-                    self.predecessor[right.1][right.0] = node;
-                    q.push_back(&n);
-                }
-            }
 
-            // Check Down node
-            let down = (node.x(), node.y() + 1);
-            if let Some(n) = self.map.get(down.0, down.1) {
-                let n_value = if n.value() == 'E' { 'z' } else { n.value() };
-                if !self.visited[down.1][down.0] && ((value as u32) + 1 >= (n_value as u32)) {
-                    self.visited[down.1][down.0] = true;
-                    self.predecessor[down.1][down.0] = node;
-                    q.push_back(&n);
-                }
-            }
-            // Check Left node
-            if node.x() != 0 {
-                let left = (node.x() - 1, node.y());
-                if let Some(n) = self.map.get(left.0, left.1) {
-                    let n_value = if n.value() == 'E' { 'z' } else { n.value() };
-                    if !self.visited[left.1][left.0] && ((value as u32) + 1 >= (n_value as u32)) {
-                        self.visited[left.1][left.0] = true;
-                        self.predecessor[left.1][left.0] = node;
-                        q.push_back(&n);
-                    }
+                if !self.visited[pos] && ((value as u32) + 1 >= (n_value as u32)) {
+                    self.visited[pos] = true;
+                    self.predecessor[pos] = Some(node);
+                    q.push_back(n);
                 }
             }
 
@@ -220,9 +199,9 @@ impl<'a> Visitor for FindShortestPath<'a> {
         }
 
         if found {
-            return Ok(depth);
+            Ok(depth)
         } else {
-            return Err(());
+            Err(())
         }
     }
 }
@@ -232,10 +211,31 @@ mod tests {
     use super::*;
 
     #[test]
-    fn line() {
-        let mut mat = Vec::<Vec<HeightNode>>::new();
+    fn example() {
+        let input = "Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi\n";
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(29u32));
+    }
+
+    #[test]
+    fn duplicate_start() {
+        assert_eq!(
+            PartTwo::solve("Sab\ncSE\n").unwrap_err().to_string(),
+            "Invalid input: line 2, column 2: expected a single 'S', found another"
+        );
+    }
 
-        mat.push(Vec::new());
+    #[test]
+    fn invalid_width() {
+        assert_eq!(
+            PartTwo::solve("Sab\ncE\n").unwrap_err().to_string(),
+            "Invalid input: line 2, column 1: expected 3 squares in the row, found 2"
+        );
+    }
+
+    #[test]
+    fn line() {
+        let mut mat = vec![Vec::<HeightNode>::new()];
 
         mat[0].push(HeightNode::new(0, 0, 'S'));
         for c in 1..26 {
@@ -259,13 +259,7 @@ mod tests {
 
     #[test]
     fn simple() {
-        let mut mat = Vec::<Vec<HeightNode>>::new();
-
-        mat.push(Vec::new());
-        mat.push(Vec::new());
-        mat.push(Vec::new());
-        mat.push(Vec::new());
-        mat.push(Vec::new());
+        let mut mat: Vec<Vec<HeightNode>> = (0..5).map(|_| Vec::new()).collect();
 
         mat[0].push(HeightNode::new(0, 0, 'S'));
         mat[0].push(HeightNode::new(1, 0, 'a'));
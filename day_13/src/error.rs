@@ -0,0 +1,39 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading the packets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A character that can't appear at this place of a packet
+    Unexpected { expected: &'static str, found: char },
+    /// A packet that ends before being complete
+    UnexpectedEnd(&'static str),
+    /// A number too large for a packet value
+    InvalidNumber(String),
+    /// A pair with a single packet
+    MissingPacket,
+    /// Something else than an empty line between two pairs
+    MissingSeparator(String),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Unexpected { expected, found } => {
+                write!(f, "expected {}, found '{}'", expected, found)
+            }
+            ParseError::UnexpectedEnd(expected) => {
+                write!(f, "expected {}, found the end of the packet", expected)
+            }
+            ParseError::InvalidNumber(s) => write!(f, "expected a packet value, found '{}'", s),
+            ParseError::MissingPacket => {
+                write!(f, "expected the second packet of the pair, found nothing")
+            }
+            ParseError::MissingSeparator(s) => {
+                write!(f, "expected an empty line between pairs, found '{}'", s)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
@@ -1,46 +1,49 @@
-use std::error::Error;
 use std::fmt::{Debug, Display};
-use std::io;
+use std::iter::{Enumerate, Peekable};
 use std::str::Chars;
 
-pub fn main_p1() -> Result<(), Box<dyn Error>> {
-    let mut lines = io::stdin().lines();
+use aoc_core::{Answer, Locate, Located, Solution, SolveError};
 
-    let mut packets = Vec::<(Packet, Packet)>::new();
+use crate::error::ParseError;
 
-    loop {
-        let mut line = lines.next();
-        if line.is_none() {
-            break; // EOF
-        }
+pub struct PartOne;
 
-        let packet_one = line.unwrap().unwrap();
-        line = lines.next();
-        let packet_two = line.unwrap().unwrap();
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut lines = input.lines().enumerate();
 
-        packets.push((Packet::parse(packet_one), Packet::parse(packet_two)));
+        let mut packets = Vec::<(Packet, Packet)>::new();
 
-        lines.next(); // Empty line between 2 packets
-    }
+        while let Some((index, line)) = lines.next() {
+            let packet_one = Packet::parse(line, index + 1)?;
 
-    let mut results = Vec::<usize>::new();
-    let mut index = 1;
+            let (index, line) =
+                lines
+                    .next()
+                    .ok_or(Located::new(index + 2, 1, ParseError::MissingPacket))?;
+            let packet_two = Packet::parse(line, index + 1)?;
 
-    for pair in packets {
-        println!("{}", pair.0);
-        println!("{}", pair.1);
-        let result = pair.0.compare(&pair.1);
-        println!("{}", result);
-        if result == -1 {
-            results.push(index);
-        }
+            packets.push((packet_one, packet_two));
 
-        index += 1;
-    }
+            // Empty line between 2 packets
+            if let Some((index, line)) = lines.next() {
+                if !line.is_empty() {
+                    let error = ParseError::MissingSeparator(line.to_string());
+                    return Err(Located::new(index + 1, 1, error).into());
+                }
+            }
+        }
 
-    println!("Sum: {}", results.iter().sum::<usize>());
+        let mut results = Vec::<usize>::new();
+        for (index, pair) in packets.iter().enumerate() {
+            let result = pair.0.compare(&pair.1);
+            if result == -1 {
+                results.push(index + 1);
+            }
+        }
 
-    Ok(())
+        Ok(results.iter().sum::<usize>().into())
+    }
 }
 
 #[derive(Debug)]
@@ -50,36 +53,70 @@ enum Node {
 }
 
 impl Node {
-    fn parse(input: &mut Chars) -> Self {
-        let mut childs = Vec::new();
-
-        loop {
-            let c = input.next();
-            if c.is_none() {
-                break; // End of Input
+    /// Parse the node starting at the next character of a packet that is `len` characters long
+    fn parse(
+        input: &mut Peekable<Enumerate<Chars>>,
+        line: usize,
+        len: usize,
+    ) -> Result<Self, Located<ParseError>> {
+        let (col, c) = match input.next() {
+            Some((col, c)) => (col + 1, c),
+            None => {
+                let error = ParseError::UnexpectedEnd("'[' or a number");
+                return Err(Located::new(line, len + 1, error));
             }
+        };
 
-            let mut c = c.unwrap();
+        match c {
+            '[' => {
+                let mut childs = Vec::new();
 
-            if c == '[' {
-                let child = Node::parse(input);
-                childs.push(child);
-            } else if c == ',' {
-                continue;
-            } else if c == ']' {
-                break;
-            } else {
-                //TODO: While iterator not empty, ',', ']', on graille le chiffre
-                let mut number = String::new();
-                while c.is_digit(10) {
+                if input.next_if(|&(_, c)| c == ']').is_some() {
+                    return Ok(Node::Array(childs));
+                }
+
+                loop {
+                    childs.push(Node::parse(input, line, len)?);
+
+                    match input.next() {
+                        Some((_, ',')) => continue,
+                        Some((_, ']')) => break,
+                        Some((col, found)) => {
+                            let error = ParseError::Unexpected {
+                                expected: "',' or ']'",
+                                found,
+                            };
+                            return Err(Located::new(line, col + 1, error));
+                        }
+                        None => {
+                            let error = ParseError::UnexpectedEnd("',' or ']'");
+                            return Err(Located::new(line, len + 1, error));
+                        }
+                    }
+                }
+
+                Ok(Node::Array(childs))
+            }
+            '0'..='9' => {
+                let mut number = String::from(c);
+                while let Some((_, c)) = input.next_if(|(_, c)| c.is_ascii_digit()) {
                     number.push(c);
-                    c = input.next().unwrap();
                 }
-                childs.push(Node::Number(number.parse().unwrap()));
+
+                number
+                    .parse()
+                    .map(Node::Number)
+                    .map_err(|_| ParseError::InvalidNumber(number))
+                    .at(line, col)
+            }
+            found => {
+                let error = ParseError::Unexpected {
+                    expected: "'[' or a number",
+                    found,
+                };
+                Err(Located::new(line, col, error))
             }
         }
-
-        Node::Array(childs)
     }
 }
 
@@ -128,8 +165,8 @@ impl Display for Node {
                 if !arr.is_empty() {
                     write!(f, "{}", arr[0])?;
 
-                    for i in 1..arr.len() {
-                        write!(f, ",{}", arr[i])?;
+                    for node in &arr[1..] {
+                        write!(f, ",{}", node)?;
                     }
                 }
 
@@ -145,10 +182,19 @@ struct Packet {
 }
 
 impl Packet {
-    fn parse(input: String) -> Self {
-        let mut it = input[1..input.len()].chars();
-        let root = Node::parse(&mut it);
-        Packet { root }
+    fn parse(input: &str, line: usize) -> Result<Self, Located<ParseError>> {
+        let mut it = input.chars().enumerate().peekable();
+        let root = Node::parse(&mut it, line, input.chars().count())?;
+
+        if let Some((col, found)) = it.next() {
+            let error = ParseError::Unexpected {
+                expected: "the end of the packet",
+                found,
+            };
+            return Err(Located::new(line, col + 1, error));
+        }
+
+        Ok(Packet { root })
     }
 
     fn compare(&self, other: &Self) -> i32 {
@@ -166,13 +212,51 @@ impl Display for Packet {
 mod tests {
     use super::*;
 
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(13usize));
+    }
+
+    #[test]
+    fn nested_lists() {
+        let packet = Packet::parse("[[1],[2,3,4]]", 1).unwrap();
+
+        assert_eq!(packet.to_string(), "[[1],[2,3,4]]");
+    }
+
+    #[test]
+    fn unclosed_list() {
+        assert_eq!(
+            PartOne::solve("[1,[2]\n[1]\n").unwrap_err().to_string(),
+            "Invalid input: line 1, column 7: expected ',' or ']', found the end of the packet"
+        );
+    }
+
+    #[test]
+    fn unexpected_character() {
+        assert_eq!(
+            PartOne::solve("[1]\n[1;2]\n").unwrap_err().to_string(),
+            "Invalid input: line 2, column 3: expected ',' or ']', found ';'"
+        );
+    }
+
+    #[test]
+    fn missing_packet() {
+        assert_eq!(
+            PartOne::solve("[1]\n[2]\n\n[3]\n").unwrap_err().to_string(),
+            "Invalid input: line 5, column 1: expected the second packet of the pair, found nothing"
+        );
+    }
+
     #[test]
     fn packets_empty() {
         let packet_one = String::from("[]");
         let packet_two = String::from("[]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), 0);
     }
@@ -182,8 +266,8 @@ mod tests {
         let packet_one = String::from("[4]");
         let packet_two = String::from("[12]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), -1);
     }
@@ -193,8 +277,8 @@ mod tests {
         let packet_one = String::from("[42]");
         let packet_two = String::from("[12]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), 1);
     }
@@ -204,8 +288,8 @@ mod tests {
         let packet_one = String::from("[1,1,3,1,1]");
         let packet_two = String::from("[1,1,5,1,1]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), -1);
     }
@@ -215,8 +299,8 @@ mod tests {
         let packet_one = String::from("[[1],[2,3,4]]");
         let packet_two = String::from("[[1],4]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), -1);
     }
@@ -226,8 +310,8 @@ mod tests {
         let packet_one = String::from("[9]");
         let packet_two = String::from("[[8,7,6]]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), 1);
     }
@@ -237,8 +321,8 @@ mod tests {
         let packet_one = String::from("[[4,4],4,4]");
         let packet_two = String::from("[[4,4],4,4,4]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), -1);
     }
@@ -248,8 +332,8 @@ mod tests {
         let packet_one = String::from("[7,7,7,7]");
         let packet_two = String::from("[7,7,7]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), 1);
     }
@@ -259,8 +343,8 @@ mod tests {
         let packet_one = String::from("[]");
         let packet_two = String::from("[3]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), -1);
     }
@@ -270,8 +354,8 @@ mod tests {
         let packet_one = String::from("[[[]]]");
         let packet_two = String::from("[[]]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), 1);
     }
@@ -281,8 +365,8 @@ mod tests {
         let packet_one = String::from("[1,[2,[3,[4,[5,6,7]]]],8,9]");
         let packet_two = String::from("[1,[2,[3,[4,[5,6,0]]]],8,9]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), 1);
     }
@@ -292,8 +376,8 @@ mod tests {
         let packet_one = String::from("[[],[[[5,5,6,0,4],[6,0,8,2]],4],[[10,3,2,3]],[[[6]]],[[]]]");
         let packet_two = String::from("[[],[[7,1,[],[2,1],7],[[9],0,5],[10,[7,6,3,7],[9,3],9]],[],[[[9,4,9,4],[5,1,1,5]]],[10,5,7,0,[[3,8],[],2]]]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), -1);
     }
@@ -1,55 +1,59 @@
-use std::error::Error;
 use std::fmt::{Debug, Display};
-use std::io;
+use std::iter::{Enumerate, Peekable};
 use std::str::Chars;
 
-pub fn main_p2() -> Result<(), Box<dyn Error>> {
-    let mut lines = io::stdin().lines();
+use aoc_core::{Answer, Locate, Located, Solution, SolveError};
 
-    let mut packets = Vec::<Packet>::new();
+use crate::error::ParseError;
 
-    loop {
-        let mut line = lines.next();
-        if line.is_none() {
-            break; // EOF
-        }
-
-        let packet = line.unwrap().unwrap();
-        packets.push((Packet::parse(packet)));
+pub struct PartTwo;
 
-        line = lines.next();
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut lines = input.lines().enumerate();
 
-        let packet = line.unwrap().unwrap();
-        packets.push((Packet::parse(packet)));
+        let mut packets = Vec::<Packet>::new();
 
-        lines.next(); // Empty line between 2 packets
-    }
+        while let Some((index, line)) = lines.next() {
+            packets.push(Packet::parse(line, index + 1)?);
 
-    packets.push(Packet::parse(String::from("[[2]]")));
-    packets.push(Packet::parse(String::from("[[6]]")));
+            let (index, line) =
+                lines
+                    .next()
+                    .ok_or(Located::new(index + 2, 1, ParseError::MissingPacket))?;
+            packets.push(Packet::parse(line, index + 1)?);
 
-    packets.sort_by(|a, b| match a.compare(b) {
-        -1 => std::cmp::Ordering::Less,
-        0 => std::cmp::Ordering::Equal,
-        1 => std::cmp::Ordering::Greater,
-        _ => panic!("Error"),
-    });
+            // Empty line between 2 packets
+            if let Some((index, line)) = lines.next() {
+                if !line.is_empty() {
+                    let error = ParseError::MissingSeparator(line.to_string());
+                    return Err(Located::new(index + 1, 1, error).into());
+                }
+            }
+        }
 
-    packets.iter().for_each(|p| println!("{}", p));
+        packets.push(Packet::divider(2));
+        packets.push(Packet::divider(6));
 
-    let index_first = packets
-        .iter()
-        .position(|p| p.compare(&Packet::parse(String::from("[[2]]"))) == 0)
-        .unwrap();
+        packets.sort_by(|a, b| match a.compare(b) {
+            -1 => std::cmp::Ordering::Less,
+            0 => std::cmp::Ordering::Equal,
+            1 => std::cmp::Ordering::Greater,
+            _ => panic!("Error"),
+        });
 
-    let index_second = packets
-        .iter()
-        .position(|p| p.compare(&Packet::parse(String::from("[[6]]"))) == 0)
-        .unwrap();
+        let index_first = packets
+            .iter()
+            .position(|p| p.compare(&Packet::divider(2)) == 0)
+            .unwrap();
 
-    println!("Decoder key: {}", (index_first + 1) * (index_second + 1));
+        let index_second = packets
+            .iter()
+            .position(|p| p.compare(&Packet::divider(6)) == 0)
+            .unwrap();
 
-    Ok(())
+        Ok(((index_first + 1) * (index_second + 1)).into())
+    }
 }
 
 #[derive(Debug)]
@@ -59,36 +63,70 @@ enum Node {
 }
 
 impl Node {
-    fn parse(input: &mut Chars) -> Self {
-        let mut childs = Vec::new();
-
-        loop {
-            let c = input.next();
-            if c.is_none() {
-                break; // End of Input
+    /// Parse the node starting at the next character of a packet that is `len` characters long
+    fn parse(
+        input: &mut Peekable<Enumerate<Chars>>,
+        line: usize,
+        len: usize,
+    ) -> Result<Self, Located<ParseError>> {
+        let (col, c) = match input.next() {
+            Some((col, c)) => (col + 1, c),
+            None => {
+                let error = ParseError::UnexpectedEnd("'[' or a number");
+                return Err(Located::new(line, len + 1, error));
             }
+        };
 
-            let mut c = c.unwrap();
+        match c {
+            '[' => {
+                let mut childs = Vec::new();
 
-            if c == '[' {
-                let child = Node::parse(input);
-                childs.push(child);
-            } else if c == ',' {
-                continue;
-            } else if c == ']' {
-                break;
-            } else {
-                //TODO: While iterator not empty, ',', ']', on graille le chiffre
-                let mut number = String::new();
-                while c.is_digit(10) {
+                if input.next_if(|&(_, c)| c == ']').is_some() {
+                    return Ok(Node::Array(childs));
+                }
+
+                loop {
+                    childs.push(Node::parse(input, line, len)?);
+
+                    match input.next() {
+                        Some((_, ',')) => continue,
+                        Some((_, ']')) => break,
+                        Some((col, found)) => {
+                            let error = ParseError::Unexpected {
+                                expected: "',' or ']'",
+                                found,
+                            };
+                            return Err(Located::new(line, col + 1, error));
+                        }
+                        None => {
+                            let error = ParseError::UnexpectedEnd("',' or ']'");
+                            return Err(Located::new(line, len + 1, error));
+                        }
+                    }
+                }
+
+                Ok(Node::Array(childs))
+            }
+            '0'..='9' => {
+                let mut number = String::from(c);
+                while let Some((_, c)) = input.next_if(|(_, c)| c.is_ascii_digit()) {
                     number.push(c);
-                    c = input.next().unwrap();
                 }
-                childs.push(Node::Number(number.parse().unwrap()));
+
+                number
+                    .parse()
+                    .map(Node::Number)
+                    .map_err(|_| ParseError::InvalidNumber(number))
+                    .at(line, col)
+            }
+            found => {
+                let error = ParseError::Unexpected {
+                    expected: "'[' or a number",
+                    found,
+                };
+                Err(Located::new(line, col, error))
             }
         }
-
-        Node::Array(childs)
     }
 }
 
@@ -137,8 +175,8 @@ impl Display for Node {
                 if !arr.is_empty() {
                     write!(f, "{}", arr[0])?;
 
-                    for i in 1..arr.len() {
-                        write!(f, ",{}", arr[i])?;
+                    for node in &arr[1..] {
+                        write!(f, ",{}", node)?;
                     }
                 }
 
@@ -154,9 +192,24 @@ struct Packet {
 }
 
 impl Packet {
-    fn parse(input: String) -> Self {
-        let mut it = input[1..input.len()].chars();
-        let root = Node::parse(&mut it);
+    fn parse(input: &str, line: usize) -> Result<Self, Located<ParseError>> {
+        let mut it = input.chars().enumerate().peekable();
+        let root = Node::parse(&mut it, line, input.chars().count())?;
+
+        if let Some((col, found)) = it.next() {
+            let error = ParseError::Unexpected {
+                expected: "the end of the packet",
+                found,
+            };
+            return Err(Located::new(line, col + 1, error));
+        }
+
+        Ok(Packet { root })
+    }
+
+    /// Divider packet `[[value]]`
+    fn divider(value: u32) -> Self {
+        let root = Node::Array(vec![Node::Array(vec![Node::Number(value)])]);
         Packet { root }
     }
 
@@ -175,13 +228,38 @@ impl Display for Packet {
 mod tests {
     use super::*;
 
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(140usize));
+    }
+
+    #[test]
+    fn trailing_characters() {
+        assert_eq!(
+            PartTwo::solve("[1]]\n[2]\n").unwrap_err().to_string(),
+            "Invalid input: line 1, column 4: expected the end of the packet, found ']'"
+        );
+    }
+
+    #[test]
+    fn missing_separator() {
+        assert_eq!(
+            PartTwo::solve("[1]\n[2]\n[3]\n[4]\n")
+                .unwrap_err()
+                .to_string(),
+            "Invalid input: line 3, column 1: expected an empty line between pairs, found '[3]'"
+        );
+    }
+
     #[test]
     fn packets_empty() {
         let packet_one = String::from("[]");
         let packet_two = String::from("[]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), 0);
     }
@@ -191,8 +269,8 @@ mod tests {
         let packet_one = String::from("[4]");
         let packet_two = String::from("[12]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), -1);
     }
@@ -202,8 +280,8 @@ mod tests {
         let packet_one = String::from("[42]");
         let packet_two = String::from("[12]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), 1);
     }
@@ -213,8 +291,8 @@ mod tests {
         let packet_one = String::from("[1,1,3,1,1]");
         let packet_two = String::from("[1,1,5,1,1]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), -1);
     }
@@ -224,8 +302,8 @@ mod tests {
         let packet_one = String::from("[[1],[2,3,4]]");
         let packet_two = String::from("[[1],4]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), -1);
     }
@@ -235,8 +313,8 @@ mod tests {
         let packet_one = String::from("[9]");
         let packet_two = String::from("[[8,7,6]]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), 1);
     }
@@ -246,8 +324,8 @@ mod tests {
         let packet_one = String::from("[[4,4],4,4]");
         let packet_two = String::from("[[4,4],4,4,4]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), -1);
     }
@@ -257,8 +335,8 @@ mod tests {
         let packet_one = String::from("[7,7,7,7]");
         let packet_two = String::from("[7,7,7]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), 1);
     }
@@ -268,8 +346,8 @@ mod tests {
         let packet_one = String::from("[]");
         let packet_two = String::from("[3]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), -1);
     }
@@ -279,8 +357,8 @@ mod tests {
         let packet_one = String::from("[[[]]]");
         let packet_two = String::from("[[]]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), 1);
     }
@@ -290,8 +368,8 @@ mod tests {
         let packet_one = String::from("[1,[2,[3,[4,[5,6,7]]]],8,9]");
         let packet_two = String::from("[1,[2,[3,[4,[5,6,0]]]],8,9]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), 1);
     }
@@ -301,8 +379,8 @@ mod tests {
         let packet_one = String::from("[[],[[[5,5,6,0,4],[6,0,8,2]],4],[[10,3,2,3]],[[[6]]],[[]]]");
         let packet_two = String::from("[[],[[7,1,[],[2,1],7],[[9],0,5],[10,[7,6,3,7],[9,3],9]],[],[[[9,4,9,4],[5,1,1,5]]],[10,5,7,0,[[3,8],[],2]]]");
 
-        let packet_one = Packet::parse(packet_one);
-        let packet_two = Packet::parse(packet_two);
+        let packet_one = Packet::parse(&packet_one, 1).unwrap();
+        let packet_two = Packet::parse(&packet_two, 1).unwrap();
 
         assert_eq!(packet_one.compare(&packet_two), -1);
     }
@@ -0,0 +1,30 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading the rock paths
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A point without the comma between its coordinates
+    MissingComma(String),
+    /// A coordinate that isn't a positive integer
+    InvalidCoordinate(String),
+    /// A path made of a single point
+    SinglePoint,
+    /// Two points of a path that aren't on the same row or column
+    NotAligned(String, String),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::MissingComma(s) => write!(f, "expected a point 'x,y', found '{}'", s),
+            ParseError::InvalidCoordinate(s) => write!(f, "expected a coordinate, found '{}'", s),
+            ParseError::SinglePoint => write!(f, "expected a path of at least two points"),
+            ParseError::NotAligned(a, b) => {
+                write!(f, "expected a straight line, found '{} -> {}'", a, b)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
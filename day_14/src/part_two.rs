@@ -1,76 +1,64 @@
-use std::collections::HashMap;
-use std::error::Error;
-use std::io;
-use std::num::ParseIntError;
+use aoc_core::{column, Answer, Locate, Located, Solution, SolveError};
+use aoc_grid::{Grid, Pos};
+use aoc_point::error::ParsePointError;
+use aoc_point::Point2;
 
-pub fn main_p2() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
+use crate::error::ParseError;
 
-    let mut paths = Vec::<Path>::new();
+pub struct PartTwo;
 
-    for line in lines {
-        let line = line.unwrap();
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut paths = Vec::<Path>::new();
 
-        let path = Path::parse(&line)?;
+        for (index, line) in input.lines().enumerate() {
+            let path = Path::parse(line, index + 1)?;
 
-        paths.push(path);
-    }
+            // Rocks are only drawn along horizontal and vertical lines
+            if let Some((a, b)) = path.iter().find(|(a, b)| a.x != b.x && a.y != b.y) {
+                let error = ParseError::NotAligned(a.to_string(), b.to_string());
+                return Err(Located::new(index + 1, 1, error).into());
+            }
 
-    let mut map = Map::new();
+            paths.push(path);
+        }
 
-    paths.iter().for_each(|p| map.apply_rock_path(p));
+        let mut map = Map::new();
 
-    map.generate_floor();
+        paths.iter().for_each(|p| map.apply_rock_path(p));
 
-    let mut iterations = 0;
+        map.generate_floor();
 
-    while map.fill() != Point::new(500, 0) {
-        iterations += 1;
-    }
-    iterations += 1;
+        let mut iterations = 0;
 
-    println!("It took {} iterations to fill the cave.", iterations);
+        while map.fill() != Point::new(500, 0) {
+            iterations += 1;
+        }
+        iterations += 1;
 
-    Ok(())
+        Ok(iterations.into())
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Element {
     Rock,
-    Air,
     Sand,
-    Void,
 }
 
-#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
-struct Point {
-    x: usize,
-    y: usize,
-}
-
-impl Point {
-    fn new(x: usize, y: usize) -> Self {
-        Point { x, y }
-    }
-
-    fn parse(input: &str) -> Result<Self, ParseIntError> {
-        let values: Vec<&str> = input.split(',').collect();
+type Point = Point2<usize>;
 
-        assert_eq!(values.len(), 2);
-
-        let x = values[0].parse::<usize>()?;
-        let y = values[1].parse::<usize>()?;
-
-        Ok(Point { x, y })
-    }
-
-    fn x(&self) -> usize {
-        self.x
-    }
+/// Read a point written as "x,y"
+fn parse_point(input: &str) -> Result<Point, ParseError> {
+    input.parse().map_err(|e| match e {
+        ParsePointError::InvalidCount { .. } => ParseError::MissingComma(input.to_string()),
+        ParsePointError::InvalidCoordinate { found, .. } => ParseError::InvalidCoordinate(found),
+    })
+}
 
-    fn y(&self) -> usize {
-        self.y
-    }
+/// Position of a point in the grid of the cave
+fn pos(point: Point) -> Pos {
+    Pos::new(point.x as isize, point.y as isize)
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -83,13 +71,17 @@ impl Path {
         Path { points: Vec::new() }
     }
 
-    fn parse(input: &str) -> Result<Self, ParseIntError> {
+    fn parse(input: &str, line: usize) -> Result<Self, Located<ParseError>> {
         let mut path = Path::new();
 
         let points: Vec<&str> = input.split(" -> ").collect();
 
         for point in points {
-            path.add_point(Point::parse(point)?);
+            path.add_point(parse_point(point).at(line, column(input, point))?);
+        }
+
+        if path.len() < 2 {
+            return Err(Located::new(line, 1, ParseError::SinglePoint));
         }
 
         Ok(path)
@@ -103,7 +95,7 @@ impl Path {
         self.points.len()
     }
 
-    fn iter(&self) -> PathIter {
+    fn iter(&self) -> PathIter<'_> {
         assert!(self.points.len() > 1);
 
         PathIter {
@@ -148,59 +140,57 @@ impl<'a> Iterator for PathIter<'a> {
 
 #[derive(Debug)]
 struct Map {
-    map: HashMap<Point, Element>,
+    map: Grid<Element>,
     lowest_point: usize,
 }
 
 impl Map {
     fn new() -> Self {
         Map {
-            map: HashMap::new(),
+            map: Grid::sparse(),
             lowest_point: 0,
         }
     }
 
     fn apply_rock_path(&mut self, rocks: &Path) {
         for (start, end) in rocks {
-            if self.lowest_point < start.y() {
-                self.lowest_point = start.y();
+            if self.lowest_point < start.y {
+                self.lowest_point = start.y;
             }
-            if self.lowest_point < end.y() {
-                self.lowest_point = end.y();
+            if self.lowest_point < end.y {
+                self.lowest_point = end.y;
             }
 
-            if start.x() == end.x() {
-                let x = start.x();
-                let mut y = start.y();
+            if start.x == end.x {
+                let x = start.x;
+                let mut y = start.y;
 
-                if y <= end.y() {
-                    while y <= end.y() {
-                        self.map.insert(Point::new(x, y), Element::Rock);
+                if y <= end.y {
+                    while y <= end.y {
+                        self.map.insert(pos(Point::new(x, y)), Element::Rock);
                         y += 1;
                     }
                 } else {
-                    while y >= end.y() {
-                        self.map.insert(Point::new(x, y), Element::Rock);
+                    while y >= end.y {
+                        self.map.insert(pos(Point::new(x, y)), Element::Rock);
                         y -= 1;
                     }
                 }
-            } else if start.y() == end.y() {
-                let mut x = start.x();
-                let y = start.y();
+            } else if start.y == end.y {
+                let mut x = start.x;
+                let y = start.y;
 
-                if x <= end.x() {
-                    while x <= end.x() {
-                        self.map.insert(Point::new(x, y), Element::Rock);
+                if x <= end.x {
+                    while x <= end.x {
+                        self.map.insert(pos(Point::new(x, y)), Element::Rock);
                         x += 1;
                     }
                 } else {
-                    while x >= end.x() {
-                        self.map.insert(Point::new(x, y), Element::Rock);
+                    while x >= end.x {
+                        self.map.insert(pos(Point::new(x, y)), Element::Rock);
                         x -= 1;
                     }
                 }
-            } else {
-                panic!("Points are not aligned !!!");
             }
         }
     }
@@ -211,30 +201,30 @@ impl Map {
         // Sand logic
         loop {
             // 0. Compute y and check if we reached the floor
-            let y = sand.y() + 1;
+            let y = sand.y + 1;
             if y == self.lowest_point {
                 break;
             }
 
             // 1. Check for tile just below
-            let below = Point::new(sand.x(), y);
-            let can_fall = self.map.get(&below);
+            let below = Point::new(sand.x, y);
+            let can_fall = self.map.get(pos(below));
             if can_fall.is_none() {
                 sand = below;
                 continue;
             }
 
             // 2. Check for tile one step down and to the left
-            let below_left = Point::new(sand.x() - 1, y);
-            let can_fall = self.map.get(&below_left);
+            let below_left = Point::new(sand.x - 1, y);
+            let can_fall = self.map.get(pos(below_left));
             if can_fall.is_none() {
                 sand = below_left;
                 continue;
             }
 
             // 3. Check for tile one step down and to the right
-            let below_right = Point::new(sand.x() + 1, y);
-            let can_fall = self.map.get(&below_right);
+            let below_right = Point::new(sand.x + 1, y);
+            let can_fall = self.map.get(pos(below_right));
             if can_fall.is_none() {
                 sand = below_right;
                 continue;
@@ -244,10 +234,9 @@ impl Map {
             break;
         }
 
-        self.map.insert(sand, Element::Sand);
-        println!("Inserted sand at {:?}", sand);
+        self.map.insert(pos(sand), Element::Sand);
 
-        return sand;
+        sand
     }
 
     fn generate_floor(&mut self) {
@@ -259,11 +248,28 @@ impl Map {
 mod tests {
     use super::*;
 
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(93i32));
+    }
+
+    #[test]
+    fn single_point() {
+        assert_eq!(
+            PartTwo::solve("498,4 -> 498,6\n503,4\n")
+                .unwrap_err()
+                .to_string(),
+            "Invalid input: line 2, column 1: expected a path of at least two points"
+        );
+    }
+
     #[test]
     fn point_zero_zero() {
         let input = "0,0";
 
-        let point = Point::parse(input);
+        let point = parse_point(input);
 
         assert_eq!(point, Ok(Point::new(0, 0)));
     }
@@ -272,7 +278,7 @@ mod tests {
     fn point_simple() {
         let input = "5,8";
 
-        let point = Point::parse(input);
+        let point = parse_point(input);
 
         assert_eq!(point, Ok(Point::new(5, 8)));
     }
@@ -281,7 +287,7 @@ mod tests {
     fn point_hard() {
         let input = "488,164";
 
-        let point = Point::parse(input);
+        let point = parse_point(input);
 
         assert_eq!(point, Ok(Point::new(488, 164)));
     }
@@ -290,7 +296,7 @@ mod tests {
     fn point_failed_no_comma() {
         let input = "488164";
 
-        let point = Point::parse(input);
+        let point = parse_point(input);
 
         assert!(point.is_err());
     }
@@ -299,7 +305,7 @@ mod tests {
     fn point_failed_empty() {
         let input = "";
 
-        let point = Point::parse(input);
+        let point = parse_point(input);
 
         assert!(point.is_err());
     }
@@ -308,7 +314,7 @@ mod tests {
     fn point_failed_left_empty() {
         let input = ",45";
 
-        let point = Point::parse(input);
+        let point = parse_point(input);
 
         assert!(point.is_err());
     }
@@ -317,7 +323,7 @@ mod tests {
     fn point_failed_right_empty() {
         let input = "45,";
 
-        let point = Point::parse(input);
+        let point = parse_point(input);
 
         assert!(point.is_err());
     }
@@ -329,7 +335,7 @@ mod tests {
         expected.add_point(Point::new(0, 0));
         expected.add_point(Point::new(1, 1));
 
-        let path = Path::parse(input);
+        let path = Path::parse(input, 1);
 
         assert_eq!(path, Ok(expected));
     }
@@ -342,7 +348,7 @@ mod tests {
         expected.add_point(Point::new(498, 6));
         expected.add_point(Point::new(496, 6));
 
-        let path = Path::parse(input);
+        let path = Path::parse(input, 1);
 
         assert_eq!(path, Ok(expected));
     }
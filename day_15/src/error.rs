@@ -0,0 +1,27 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading the sensors report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Text other than the expected part of a report line
+    UnexpectedText {
+        expected: &'static str,
+        found: String,
+    },
+    /// A coordinate that isn't an integer
+    InvalidCoordinate(String),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnexpectedText { expected, found } => {
+                write!(f, "expected '{}', found '{}'", expected, found)
+            }
+            ParseError::InvalidCoordinate(s) => write!(f, "expected a coordinate, found '{}'", s),
+        }
+    }
+}
+
+impl Error for ParseError {}
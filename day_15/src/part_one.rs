@@ -1,32 +1,29 @@
 use std::collections::HashMap;
-use std::error::Error;
-use std::io;
 
-pub fn main_p1() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
+use aoc_core::{column, Answer, Locate, Located, Solution, SolveError};
+use aoc_point::Point2;
 
-    let mut items = HashMap::<Point, Type>::new();
-
-    for line in lines {
-        let line = line.unwrap();
+use crate::error::ParseError;
 
-        let line: Vec<&str> = line.split(':').collect();
+pub struct PartOne;
 
-        let sensor = line[0];
-        let beacon = line[1];
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        no_beacon_positions(input, 2_000_000)
+    }
+}
 
-        let (_, sensor) = sensor.split_at(10);
-        let (_, beacon) = beacon.split_at(22);
+/// Count the positions of the `row` where no beacon can be present
+fn no_beacon_positions(input: &str, row: i32) -> Result<Answer, SolveError> {
+    let mut items = HashMap::<Point, Type>::new();
 
-        let sensor = Point::parse(sensor);
-        let beacon = Point::parse(beacon);
+    for (index, line) in input.lines().enumerate() {
+        let (sensor, beacon) = parse_report(line, index + 1)?;
 
-        items.insert(sensor, Type::Sensor(sensor.compare(&beacon)));
+        items.insert(sensor, Type::Sensor(sensor.manhattan(&beacon)));
         items.insert(beacon, Type::Beacon);
     }
 
-    items.iter().for_each(|i| println!("{:?}", i));
-
     let mut min_x = 0;
     let mut max_x = 0;
     let mut min_y = 0;
@@ -35,26 +32,20 @@ pub fn main_p1() -> Result<(), Box<dyn Error>> {
     for (point, t) in &items {
         match t {
             Type::Beacon => {
-                min_x = min_x.min(point.x());
-                min_y = min_y.min(point.y());
-                max_x = max_x.max(point.x());
-                max_y = max_y.max(point.y());
+                min_x = min_x.min(point.x);
+                min_y = min_y.min(point.y);
+                max_x = max_x.max(point.x);
+                max_y = max_y.max(point.y);
             }
             Type::Sensor(value) => {
-                min_x = min_x.min(point.x() - *value as i32);
-                min_y = min_y.min(point.y() - *value as i32);
-                max_x = max_x.max(point.x() + *value as i32);
-                max_y = max_y.max(point.y() + *value as i32);
+                min_x = min_x.min(point.x - *value);
+                min_y = min_y.min(point.y - *value);
+                max_x = max_x.max(point.x + *value);
+                max_y = max_y.max(point.y + *value);
             }
         }
     }
 
-    println!("min_x: {}", min_x);
-    println!("max_x: {}", max_x);
-    println!("min_y: {}", min_y);
-    println!("max_y: {}", max_y);
-
-    let row = 2_000_000;
     let mut taken = 0;
 
     // Algorithm
@@ -66,7 +57,6 @@ pub fn main_p1() -> Result<(), Box<dyn Error>> {
     for x in min_x..=max_x {
         // 1.
         let p = Point::new(x, row);
-        print!("x: {}\r", x);
 
         // 2.
         if let Some(item) = items.get(&p) {
@@ -80,65 +70,69 @@ pub fn main_p1() -> Result<(), Box<dyn Error>> {
 
         // 3+4+5.
         for (point, t) in &items {
-            match t {
-                Type::Sensor(value) => {
-                    if p.compare(point) <= *value {
-                        taken += 1;
-                        break;
-                    }
+            if let Type::Sensor(value) = t {
+                if p.manhattan(point) <= *value {
+                    taken += 1;
+                    break;
                 }
-                _ => (),
             }
         }
     }
 
-    println!(
-        "There are {} positions where a beacon cannot be present.",
-        taken
-    );
-
-    Ok(())
+    Ok(taken.into())
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point {
-    x: i32,
-    y: i32,
-}
+type Point = Point2<i32>;
 
-impl Point {
-    fn new(x: i32, y: i32) -> Self {
-        Point { x, y }
-    }
+/// Read a point written as "x=<x>, y=<y>"
+fn parse_point(s: &str) -> Result<Point, ParseError> {
+    let (x, y) = s
+        .split_once(", ")
+        .ok_or_else(|| unexpected("x=<x>, y=<y>", s))?;
 
-    fn parse(s: &str) -> Self {
-        let input: Vec<&str> = s.split(", ").collect();
+    let x = x.strip_prefix("x=").ok_or_else(|| unexpected("x=", x))?;
+    let y = y.strip_prefix("y=").ok_or_else(|| unexpected("y=", y))?;
 
-        let x_value: Vec<&str> = input[0].split('=').collect();
-        let y_value: Vec<&str> = input[1].split('=').collect();
+    let x = x
+        .parse()
+        .map_err(|_| ParseError::InvalidCoordinate(x.to_string()))?;
+    let y = y
+        .parse()
+        .map_err(|_| ParseError::InvalidCoordinate(y.to_string()))?;
 
-        let x = x_value[1].parse().unwrap();
-        let y = y_value[1].parse().unwrap();
+    Ok(Point::new(x, y))
+}
 
-        Point::new(x, y)
-    }
+/// Read the sensor and its closest beacon from a line of the report
+fn parse_report(line: &str, line_number: usize) -> Result<(Point, Point), Located<ParseError>> {
+    const SENSOR: &str = "Sensor at ";
+    const BEACON: &str = ": closest beacon is at ";
 
-    fn compare(&self, other: &Self) -> u32 {
-        (self.x - other.x).abs() as u32 + (self.y - other.y).abs() as u32
-    }
+    let report = line
+        .strip_prefix(SENSOR)
+        .ok_or_else(|| unexpected(SENSOR.trim(), line))
+        .at(line_number, 1)?;
+    let (sensor, beacon) = report
+        .split_once(BEACON)
+        .ok_or_else(|| unexpected(BEACON.trim(), report))
+        .at(line_number, column(line, report))?;
 
-    fn x(&self) -> i32 {
-        self.x
-    }
+    let sensor = parse_point(sensor).at(line_number, column(line, sensor))?;
+    let beacon = parse_point(beacon).at(line_number, column(line, beacon))?;
+
+    Ok((sensor, beacon))
+}
 
-    fn y(&self) -> i32 {
-        self.y
+fn unexpected(expected: &'static str, found: &str) -> ParseError {
+    ParseError::UnexpectedText {
+        expected,
+        found: found.to_string(),
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Type {
-    Sensor(u32),
+    Sensor(i32),
     Beacon,
 }
 
@@ -146,13 +140,34 @@ enum Type {
 mod tests {
     use super::*;
 
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(no_beacon_positions(input, 10).unwrap(), Answer::from(26u32));
+    }
+
+    #[test]
+    fn invalid_coordinate() {
+        let input = "Sensor at x=2, y=18: closest beacon is at x=-2, y=1five\n";
+
+        assert_eq!(
+            PartOne::solve(input).unwrap_err().to_string(),
+            "Invalid input: line 1, column 43: expected a coordinate, found '1five'"
+        );
+    }
+
     #[test]
     fn point_pos() {
         let input = "x=2, y=18";
+
+        assert_eq!(parse_point(input), Ok(Point::new(2, 18)));
     }
 
     #[test]
     fn point_neg() {
         let input = "x=-2, y=15";
+
+        assert_eq!(parse_point(input), Ok(Point::new(-2, 15)));
     }
 }
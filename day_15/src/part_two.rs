@@ -1,64 +1,29 @@
 use std::collections::HashMap;
-use std::error::Error;
-use std::io;
 
-pub fn main_p2() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
+use aoc_core::{column, Answer, Locate, Located, Solution, SolveError};
+use aoc_point::Point2;
 
-    let mut items = HashMap::<Point, Type>::new();
-
-    for line in lines {
-        let line = line.unwrap();
-
-        let line: Vec<&str> = line.split(':').collect();
-
-        let sensor = line[0];
-        let beacon = line[1];
-
-        let (_, sensor) = sensor.split_at(10);
-        let (_, beacon) = beacon.split_at(22);
+use crate::error::ParseError;
 
-        let sensor = Point::parse(sensor);
-        let beacon = Point::parse(beacon);
+pub struct PartTwo;
 
-        items.insert(sensor, Type::Sensor(sensor.compare(&beacon)));
-        items.insert(beacon, Type::Beacon);
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        tuning_frequency(input, 4_000_000)
     }
+}
 
-    items.iter().for_each(|i| println!("{:?}", i));
+/// Find the distress beacon, whose coordinates are between 0 and `max`, and compute its tuning frequency
+fn tuning_frequency(input: &str, max: i32) -> Result<Answer, SolveError> {
+    let mut items = HashMap::<Point, Type>::new();
 
-    let mut min_x = 0;
-    let mut max_x = 0;
-    let mut min_y = 0;
-    let mut max_y = 0;
+    for (index, line) in input.lines().enumerate() {
+        let (sensor, beacon) = parse_report(line, index + 1)?;
 
-    for (point, t) in &items {
-        match t {
-            Type::Beacon => {
-                min_x = min_x.min(point.x());
-                min_y = min_y.min(point.y());
-                max_x = max_x.max(point.x());
-                max_y = max_y.max(point.y());
-            }
-            Type::Sensor(value) => {
-                min_x = min_x.min(point.x() - *value as i32);
-                min_y = min_y.min(point.y() - *value as i32);
-                max_x = max_x.max(point.x() + *value as i32);
-                max_y = max_y.max(point.y() + *value as i32);
-            }
-        }
+        items.insert(sensor, Type::Sensor(sensor.manhattan(&beacon)));
+        items.insert(beacon, Type::Beacon);
     }
 
-    min_x = min_x.max(0);
-    min_y = min_y.max(0);
-    max_x = max_x.min(4_000_000);
-    max_y = max_y.min(4_000_000);
-
-    println!("min_x: {}", min_x);
-    println!("max_x: {}", max_x);
-    println!("min_y: {}", min_y);
-    println!("max_y: {}", max_y);
-
     let mut free_spot = Point::new(-1, -1);
 
     // Algorithm
@@ -70,12 +35,11 @@ pub fn main_p2() -> Result<(), Box<dyn Error>> {
         match *t {
             Type::Beacon => continue,
             Type::Sensor(radius) => {
-                println!("Looking at Sensor at position {:?}", point);
                 let points = compute_point_outside_radius(point, radius);
 
                 for p in points {
-                    // 1. Check if in range (0,4_000_000)
-                    if p.x() < 0 || p.x() > 4_000_000 || p.y() < 0 || p.y() > 4_000_000 {
+                    // 1. Check if in range (0,max)
+                    if p.x < 0 || p.x > max || p.y < 0 || p.y > max {
                         continue;
                     }
 
@@ -91,7 +55,7 @@ pub fn main_p2() -> Result<(), Box<dyn Error>> {
                         match *t {
                             Type::Beacon => continue,
                             Type::Sensor(value) => {
-                                if p.compare(point) <= value {
+                                if p.manhattan(point) <= value {
                                     is_in_range = true;
                                     break;
                                 }
@@ -114,101 +78,101 @@ pub fn main_p2() -> Result<(), Box<dyn Error>> {
         }
     }
 
-    println!("Found free spot at {:?}", free_spot);
-    println!(
-        "Tuning frequency: {}",
-        free_spot.x() as u64 * 4_000_000 + free_spot.y() as u64
-    );
+    if free_spot == Point::new(-1, -1) {
+        return Err(SolveError::NoSolution(String::from(
+            "no position left for the distress beacon",
+        )));
+    }
 
-    Ok(())
+    Ok((free_spot.x as u64 * 4_000_000 + free_spot.y as u64).into())
 }
 
-fn compute_point_outside_radius(p: &Point, radius: u32) -> Vec<Point> {
+fn compute_point_outside_radius(p: &Point, radius: i32) -> Vec<Point> {
     let mut points = Vec::new();
 
-    let mut left = Point::new(p.x() - radius as i32 - 1, p.y());
-    let mut right = Point::new(p.x() + radius as i32 + 1, p.y());
-    let mut up = Point::new(p.x(), p.y() - radius as i32 - 1);
-    let mut down = Point::new(p.x(), p.y() + radius as i32 + 1);
+    let mut left = Point::new(p.x - radius - 1, p.y);
+    let mut right = Point::new(p.x + radius + 1, p.y);
+    let mut up = Point::new(p.x, p.y - radius - 1);
+    let mut down = Point::new(p.x, p.y + radius + 1);
 
-    while left.x() != p.x() {
+    while left.x != p.x {
         points.push(left);
 
-        *left.x_mut() += 1;
-        *left.y_mut() -= 1;
+        left += Point::new(1, -1);
     }
 
-    while up.y() != p.y() {
+    while up.y != p.y {
         points.push(up);
 
-        *up.x_mut() += 1;
-        *up.y_mut() += 1;
+        up += Point::new(1, 1);
     }
 
-    while right.x() != p.x() {
+    while right.x != p.x {
         points.push(right);
 
-        *right.x_mut() -= 1;
-        *right.y_mut() += 1;
+        right += Point::new(-1, 1);
     }
 
-    while down.x() != p.x() {
+    while down.x != p.x {
         points.push(down);
 
-        *down.x_mut() -= 1;
-        *down.y_mut() -= 1;
+        down += Point::new(-1, -1);
     }
 
     points
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    fn new(x: i32, y: i32) -> Self {
-        Point { x, y }
-    }
+type Point = Point2<i32>;
 
-    fn parse(s: &str) -> Self {
-        let input: Vec<&str> = s.split(", ").collect();
+/// Read a point written as "x=<x>, y=<y>"
+fn parse_point(s: &str) -> Result<Point, ParseError> {
+    let (x, y) = s
+        .split_once(", ")
+        .ok_or_else(|| unexpected("x=<x>, y=<y>", s))?;
 
-        let x_value: Vec<&str> = input[0].split('=').collect();
-        let y_value: Vec<&str> = input[1].split('=').collect();
+    let x = x.strip_prefix("x=").ok_or_else(|| unexpected("x=", x))?;
+    let y = y.strip_prefix("y=").ok_or_else(|| unexpected("y=", y))?;
 
-        let x = x_value[1].parse().unwrap();
-        let y = y_value[1].parse().unwrap();
+    let x = x
+        .parse()
+        .map_err(|_| ParseError::InvalidCoordinate(x.to_string()))?;
+    let y = y
+        .parse()
+        .map_err(|_| ParseError::InvalidCoordinate(y.to_string()))?;
 
-        Point::new(x, y)
-    }
+    Ok(Point::new(x, y))
+}
 
-    fn compare(&self, other: &Self) -> u32 {
-        (self.x - other.x).abs() as u32 + (self.y - other.y).abs() as u32
-    }
+/// Read the sensor and its closest beacon from a line of the report
+fn parse_report(line: &str, line_number: usize) -> Result<(Point, Point), Located<ParseError>> {
+    const SENSOR: &str = "Sensor at ";
+    const BEACON: &str = ": closest beacon is at ";
 
-    fn x(&self) -> i32 {
-        self.x
-    }
+    let report = line
+        .strip_prefix(SENSOR)
+        .ok_or_else(|| unexpected(SENSOR.trim(), line))
+        .at(line_number, 1)?;
+    let (sensor, beacon) = report
+        .split_once(BEACON)
+        .ok_or_else(|| unexpected(BEACON.trim(), report))
+        .at(line_number, column(line, report))?;
 
-    fn x_mut(&mut self) -> &mut i32 {
-        &mut self.x
-    }
+    let sensor = parse_point(sensor).at(line_number, column(line, sensor))?;
+    let beacon = parse_point(beacon).at(line_number, column(line, beacon))?;
 
-    fn y(&self) -> i32 {
-        self.y
-    }
+    Ok((sensor, beacon))
+}
 
-    fn y_mut(&mut self) -> &mut i32 {
-        &mut self.y
+fn unexpected(expected: &'static str, found: &str) -> ParseError {
+    ParseError::UnexpectedText {
+        expected,
+        found: found.to_string(),
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Type {
-    Sensor(u32),
+    Sensor(i32),
     Beacon,
 }
 
@@ -216,13 +180,37 @@ enum Type {
 mod tests {
     use super::*;
 
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(
+            tuning_frequency(input, 20).unwrap(),
+            Answer::from(56000011u64)
+        );
+    }
+
+    #[test]
+    fn missing_beacon() {
+        let input = "Sensor at x=2, y=18\n";
+
+        assert_eq!(
+            PartTwo::solve(input).unwrap_err().to_string(),
+            "Invalid input: line 1, column 11: expected ': closest beacon is at', found 'x=2, y=18'"
+        );
+    }
+
     #[test]
     fn point_pos() {
         let input = "x=2, y=18";
+
+        assert_eq!(parse_point(input), Ok(Point::new(2, 18)));
     }
 
     #[test]
     fn point_neg() {
         let input = "x=-2, y=15";
+
+        assert_eq!(parse_point(input), Ok(Point::new(-2, 15)));
     }
 }
@@ -0,0 +1,33 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading the valves scan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Text other than the expected part of a scan line
+    UnexpectedText {
+        expected: &'static str,
+        found: String,
+    },
+    /// A flow rate that isn't a positive integer
+    InvalidFlowRate(String),
+    /// A tunnel leading to a valve that isn't in the scan
+    UnknownValve(String),
+    /// The scan has no valve AA to start from
+    MissingStart,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnexpectedText { expected, found } => {
+                write!(f, "expected '{}', found '{}'", expected, found)
+            }
+            ParseError::InvalidFlowRate(s) => write!(f, "expected a flow rate, found '{}'", s),
+            ParseError::UnknownValve(s) => write!(f, "valve {} doesn't exist", s),
+            ParseError::MissingStart => write!(f, "expected a valve AA to start from, found none"),
+        }
+    }
+}
+
+impl Error for ParseError {}
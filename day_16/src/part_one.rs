@@ -1,54 +1,52 @@
 use std::collections::{HashSet, VecDeque};
-use std::error::Error;
-use std::fs::OpenOptions;
 use std::hash::Hash;
-use std::io::{self, Write};
 
-pub fn main_p1() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
+use aoc_core::{column, Answer, Locate, Located, Solution, SolveError};
 
-    let mut nodes = HashSet::new();
-    let mut edges = HashSet::new();
+use crate::error::ParseError;
 
-    for line in lines {
-        let line = line.unwrap();
+pub struct PartOne;
 
-        let (node, node_edges) = parse_valve(line).unwrap();
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut nodes = HashSet::new();
+        let mut edges = HashSet::new();
 
-        nodes.insert(node);
+        let mut tunnels = Vec::new();
 
-        for node_edge in node_edges {
-            edges.insert(node_edge);
-        }
-    }
+        for (index, line) in input.lines().enumerate() {
+            let (node, node_edges) = parse_valve(line, index + 1)?;
 
-    write_graph("graph_1_parsed.dot", &edges)?;
+            nodes.insert(node);
 
-    let nodes_to_remove = simplify_graph(&nodes, &mut edges);
-
-    // Remove useless nodes with flow_rate=0
-    for node in nodes_to_remove {
-        nodes.remove(&node);
-    }
+            for node_edge in node_edges {
+                tunnels.push((index + 1, line, node_edge.b().clone()));
+                edges.insert(node_edge);
+            }
+        }
 
-    write_graph("graph_2_simplified.dot", &edges)?;
+        check_valves(&nodes, &tunnels)?;
 
-    // Modify the Graph to make all nodes linked to each others.
-    // This way we can do a simple BFS to find the best path.
-    //
-    edges = link_all(&nodes, &mut edges);
+        let nodes_to_remove = simplify_graph(&nodes, &mut edges);
 
-    write_graph("graph_3_all_linked.dot", &edges)?;
+        // Remove useless nodes with flow_rate=0
+        for node in nodes_to_remove {
+            nodes.remove(&node);
+        }
 
-    // Now perform a BFS to find the best path.
-    // We need to brute force and test every possibilities but this shouldn't take too long thanks
-    // to previus steps.
+        // Modify the Graph to make all nodes linked to each others.
+        // This way we can do a simple BFS to find the best path.
+        //
+        edges = link_all(&nodes, &mut edges)?;
 
-    let max_pressure = find_most_pressure(&nodes, &edges);
+        // Now perform a BFS to find the best path.
+        // We need to brute force and test every possibilities but this shouldn't take too long thanks
+        // to previus steps.
 
-    println!("Max pressure: {}", max_pressure);
+        let max_pressure = find_most_pressure(&nodes, &edges);
 
-    Ok(())
+        Ok(max_pressure.into())
+    }
 }
 
 fn simplify_graph(nodes: &HashSet<Node>, edges: &mut HashSet<Edge>) -> Vec<Node> {
@@ -56,7 +54,7 @@ fn simplify_graph(nodes: &HashSet<Node>, edges: &mut HashSet<Edge>) -> Vec<Node>
 
     // Reduce the graph by removing the node with flow_rate of 0.
     for node in nodes {
-        if node.flow_rate() != 0 || *node.label() == String::from("AA") {
+        if node.flow_rate() != 0 || node.label() == "AA" {
             continue;
         }
 
@@ -89,8 +87,7 @@ fn simplify_graph(nodes: &HashSet<Node>, edges: &mut HashSet<Edge>) -> Vec<Node>
                     node_edge.a()
                 };
 
-                for next_nodes in index + 1..node_edges.len() {
-                    let b_node = &node_edges[next_nodes];
+                for b_node in &node_edges[index + 1..] {
                     let b = if b_node.a() == node.label() {
                         b_node.b()
                     } else {
@@ -110,7 +107,7 @@ fn simplify_graph(nodes: &HashSet<Node>, edges: &mut HashSet<Edge>) -> Vec<Node>
     nodes_to_remove
 }
 
-fn link_all(nodes: &HashSet<Node>, edges: &mut HashSet<Edge>) -> HashSet<Edge> {
+fn link_all(nodes: &HashSet<Node>, edges: &mut HashSet<Edge>) -> Result<HashSet<Edge>, SolveError> {
     let mut linked_edges = HashSet::new();
 
     for node in nodes {
@@ -119,7 +116,7 @@ fn link_all(nodes: &HashSet<Node>, edges: &mut HashSet<Edge>) -> HashSet<Edge> {
                 continue;
             }
 
-            let time_cost = find_shortest_time(&node, &other, edges);
+            let time_cost = find_shortest_time(node, other, edges);
 
             if let Ok(time_cost) = time_cost {
                 linked_edges.insert(Edge::new(
@@ -128,12 +125,16 @@ fn link_all(nodes: &HashSet<Node>, edges: &mut HashSet<Edge>) -> HashSet<Edge> {
                     time_cost,
                 ));
             } else {
-                panic!("Path not found between {:?} and {:?}", node, other);
+                return Err(SolveError::NoSolution(format!(
+                    "no path between valves {} and {}",
+                    node.label(),
+                    other.label()
+                )));
             }
         }
     }
 
-    linked_edges
+    Ok(linked_edges)
 }
 
 fn find_shortest_time(a: &Node, b: &Node, edges: &HashSet<Edge>) -> Result<usize, ()> {
@@ -211,10 +212,6 @@ impl STNode {
         self.time_cost
     }
 
-    fn visited(&self) -> &Vec<String> {
-        &self.visited
-    }
-
     fn add_time(&mut self, time: usize) {
         self.time_cost += time
     }
@@ -290,7 +287,7 @@ fn find_most_pressure(nodes: &HashSet<Node>, edges: &HashSet<Edge>) -> usize {
     max_pressure
 }
 
-#[derive(Debug, Eq, Ord)]
+#[derive(Debug, Eq)]
 struct MPNode {
     label: String,
     minutes: usize,
@@ -347,7 +344,7 @@ impl MPNode {
     fn current_pression(&self, minutes: usize) -> usize {
         let mut pression = 0;
         for (min, p) in &self.valve_opened {
-            pression += p * (minutes - *min as usize);
+            pression += p * (minutes - *min);
         }
         pression
     }
@@ -360,54 +357,35 @@ impl PartialEq for MPNode {
 }
 
 impl PartialOrd for MPNode {
-    fn lt(&self, other: &Self) -> bool {
-        self.pression() < other.pression()
-    }
-
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-fn write_graph(path: &str, edges: &HashSet<Edge>) -> Result<(), Box<dyn Error>> {
-    let mut dot_file = OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)?;
-
-    dot_file.write("graph Day16 {\n".as_bytes())?;
-
-    for edge in edges {
-        dot_file.write(
-            format!(
-                "\t{} -- {} [label={}];\n",
-                edge.a(),
-                edge.b(),
-                edge.weight()
-            )
-            .as_bytes(),
-        )?;
+        self.pression().partial_cmp(&other.pression())
     }
-
-    dot_file.write("}".as_bytes())?;
-
-    Ok(())
 }
 
-fn parse_valve(s: String) -> Result<(Node, Vec<Edge>), ()> {
-    let line: Vec<&str> = s.split(';').collect();
-
-    let valve_data: Vec<&str> = line[0].split(' ').collect();
-    let (_, mut tunnels_data) = line[1].split_at(23);
-    if tunnels_data.chars().nth(0).unwrap() == ' ' {
-        (_, tunnels_data) = tunnels_data.split_at(1);
-    }
-
-    let name = valve_data[1];
-    let flow_rate = valve_data[4];
-    let (_, flow_rate) = flow_rate.split_at(5);
-    let flow_rate = flow_rate.parse().unwrap();
+fn parse_valve(s: &str, line: usize) -> Result<(Node, Vec<Edge>), Located<ParseError>> {
+    let valve = s
+        .strip_prefix("Valve ")
+        .ok_or_else(|| unexpected("Valve", s))
+        .at(line, 1)?;
+    let (name, flow_rate) = valve
+        .split_once(" has flow rate=")
+        .ok_or_else(|| unexpected("has flow rate=", valve))
+        .at(line, column(s, valve))?;
+    let (flow_rate, tunnels_data) = flow_rate
+        .split_once("; ")
+        .ok_or_else(|| unexpected(";", flow_rate))
+        .at(line, column(s, flow_rate))?;
+
+    let flow_rate = flow_rate
+        .parse()
+        .map_err(|_| ParseError::InvalidFlowRate(flow_rate.to_string()))
+        .at(line, column(s, flow_rate))?;
+
+    let tunnels_data = tunnels_data
+        .strip_prefix("tunnels lead to valves ")
+        .or_else(|| tunnels_data.strip_prefix("tunnel leads to valve "))
+        .ok_or_else(|| unexpected("tunnels lead to valves", tunnels_data))
+        .at(line, column(s, tunnels_data))?;
 
     let node = Node::new(String::from(name), flow_rate);
 
@@ -423,6 +401,33 @@ fn parse_valve(s: String) -> Result<(Node, Vec<Edge>), ()> {
     Ok((node, edges))
 }
 
+fn unexpected(expected: &'static str, found: &str) -> ParseError {
+    ParseError::UnexpectedText {
+        expected,
+        found: found.to_string(),
+    }
+}
+
+/// Make sure every tunnel leads to a valve of the scan and that there is a valve to start from
+fn check_valves(
+    nodes: &HashSet<Node>,
+    tunnels: &[(usize, &str, String)],
+) -> Result<(), SolveError> {
+    for (line, s, label) in tunnels {
+        if !nodes.contains(&Node::new(label.clone(), 0)) {
+            let col = s.rfind(label.as_str()).map_or(1, |i| column(s, &s[i..]));
+            let error = ParseError::UnknownValve(label.clone());
+            return Err(Located::new(*line, col, error).into());
+        }
+    }
+
+    if !nodes.contains(&Node::new(String::from("AA"), 0)) {
+        return Err(SolveError::parse(ParseError::MissingStart));
+    }
+
+    Ok(())
+}
+
 // Node
 #[derive(Debug, Eq, Clone)]
 struct Node {
@@ -504,11 +509,39 @@ impl Hash for Edge {
 mod tests {
     use super::*;
 
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(1651usize));
+    }
+
+    #[test]
+    fn invalid_flow_rate() {
+        let input = "Valve AA has flow rate=O; tunnel leads to valve BB\n";
+
+        assert_eq!(
+            PartOne::solve(input).unwrap_err().to_string(),
+            "Invalid input: line 1, column 24: expected a flow rate, found 'O'"
+        );
+    }
+
+    #[test]
+    fn unknown_valve() {
+        let input = "Valve AA has flow rate=0; tunnels lead to valves BB, CC\n\
+                     Valve BB has flow rate=3; tunnel leads to valve AA\n";
+
+        assert_eq!(
+            PartOne::solve(input).unwrap_err().to_string(),
+            "Invalid input: line 1, column 54: valve CC doesn't exist"
+        );
+    }
+
     #[test]
     fn valve() {
         let input = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB";
 
-        let (node, edges) = parse_valve(input.to_string()).unwrap();
+        let (node, edges) = parse_valve(input, 1).unwrap();
 
         let tunnels = Vec::from([
             Edge::new(String::from("AA"), String::from("DD"), 1),
@@ -1,106 +1,79 @@
+use std::cmp::Reverse;
 use std::collections::{HashSet, VecDeque};
-use std::error::Error;
-use std::fs::OpenOptions;
 use std::hash::Hash;
-use std::io::{self, Write};
 
-pub fn main_p2() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
+use aoc_core::{column, Answer, Locate, Located, Solution, SolveError};
 
-    let mut nodes = HashSet::new();
-    let mut edges = HashSet::new();
+use crate::error::ParseError;
 
-    for line in lines {
-        let line = line.unwrap();
+pub struct PartTwo;
 
-        let (node, node_edges) = parse_valve(line).unwrap();
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut nodes = HashSet::new();
+        let mut edges = HashSet::new();
 
-        nodes.insert(node);
+        let mut tunnels = Vec::new();
 
-        for node_edge in node_edges {
-            edges.insert(node_edge);
-        }
-    }
-
-    write_graph("graph_1_parsed.dot", &edges)?;
-
-    let nodes_to_remove = simplify_graph(&nodes, &mut edges);
-
-    // Remove useless nodes with flow_rate=0
-    for node in nodes_to_remove {
-        nodes.remove(&node);
-    }
-
-    write_graph("graph_2_simplified.dot", &edges)?;
+        for (index, line) in input.lines().enumerate() {
+            let (node, node_edges) = parse_valve(line, index + 1)?;
 
-    // Modify the Graph to make all nodes linked to each others.
-    // This way we can do a simple BFS to find the best path.
-    //
-    edges = link_all(&nodes, &mut edges);
+            nodes.insert(node);
 
-    write_graph("graph_3_all_linked.dot", &edges)?;
-
-    // Now perform a BFS to find the best path.
-    // We need to brute force and test every possibilities but this shouldn't take too long thanks
-    // to previus steps.
+            for node_edge in node_edges {
+                tunnels.push((index + 1, line, node_edge.b().clone()));
+                edges.insert(node_edge);
+            }
+        }
 
-    let mut possible_paths = find_most_pressure(&nodes, &edges);
+        check_valves(&nodes, &tunnels)?;
 
-    println!("Number of paths: {}", possible_paths.len());
+        let nodes_to_remove = simplify_graph(&nodes, &mut edges);
 
-    possible_paths.sort_by(|a, b| compute_pression(b, 26).cmp(&compute_pression(a, 26)));
+        // Remove useless nodes with flow_rate=0
+        for node in nodes_to_remove {
+            nodes.remove(&node);
+        }
 
-    println!("Possible paths has been sorted");
+        // Modify the Graph to make all nodes linked to each others.
+        // This way we can do a simple BFS to find the best path.
+        //
+        edges = link_all(&nodes, &mut edges)?;
 
-    let total_paths = possible_paths.len();
-    let mut current_path = 0;
+        // Now perform a BFS to find the best path.
+        // We need to brute force and test every possibilities but this shouldn't take too long thanks
+        // to previus steps.
 
-    let mut max_pression = 0;
+        let mut possible_paths = find_most_pressure(&nodes, &edges);
 
-    let mut paths_it = possible_paths.iter();
-    loop {
-        if let Some(path) = paths_it.next() {
-            let mut next_path = paths_it.clone();
+        possible_paths.sort_by_key(|p| Reverse(compute_pression(p, 26)));
 
-            print!(
-                "Max pression: {} | Remaining: {}/{}\r",
-                max_pression, current_path, total_paths
-            );
-            std::io::stdout().flush().unwrap();
+        let mut max_pression = 0;
 
-            loop {
-                if let Some(next_path) = next_path.next() {
-                    match combine_paths(&path, &next_path, nodes.len() - 1) {
-                        Some(pression) => max_pression = max_pression.max(pression),
-                        None => (),
-                    }
-                } else {
-                    break;
+        let mut paths_it = possible_paths.iter();
+        while let Some(path) = paths_it.next() {
+            for next_path in paths_it.clone() {
+                if let Some(pression) = combine_paths(path, next_path, nodes.len() - 1) {
+                    max_pression = max_pression.max(pression)
                 }
             }
-
-            current_path += 1;
-        } else {
-            break;
         }
-    }
 
-    println!("Max pression: {}", max_pression);
-
-    Ok(())
+        Ok(max_pression.into())
+    }
 }
 
-fn compute_pression(path: &Vec<(String, usize, usize)>, minutes: usize) -> usize {
+fn compute_pression(path: &[(String, usize, usize)], minutes: usize) -> usize {
     let mut pression = 0;
     for (_, min, p) in path {
-        pression += p * (minutes - *min as usize);
+        pression += p * (minutes - *min);
     }
     pression
 }
 
 fn combine_paths(
-    a: &Vec<(String, usize, usize)>,
-    b: &Vec<(String, usize, usize)>,
+    a: &[(String, usize, usize)],
+    b: &[(String, usize, usize)],
     number_of_valves: usize,
 ) -> Option<usize> {
     let mut minutes = 0;
@@ -157,7 +130,7 @@ fn simplify_graph(nodes: &HashSet<Node>, edges: &mut HashSet<Edge>) -> Vec<Node>
 
     // Reduce the graph by removing the node with flow_rate of 0.
     for node in nodes {
-        if node.flow_rate() != 0 || *node.label() == String::from("AA") {
+        if node.flow_rate() != 0 || node.label() == "AA" {
             continue;
         }
 
@@ -190,8 +163,7 @@ fn simplify_graph(nodes: &HashSet<Node>, edges: &mut HashSet<Edge>) -> Vec<Node>
                     node_edge.a()
                 };
 
-                for next_nodes in index + 1..node_edges.len() {
-                    let b_node = &node_edges[next_nodes];
+                for b_node in &node_edges[index + 1..] {
                     let b = if b_node.a() == node.label() {
                         b_node.b()
                     } else {
@@ -211,7 +183,7 @@ fn simplify_graph(nodes: &HashSet<Node>, edges: &mut HashSet<Edge>) -> Vec<Node>
     nodes_to_remove
 }
 
-fn link_all(nodes: &HashSet<Node>, edges: &mut HashSet<Edge>) -> HashSet<Edge> {
+fn link_all(nodes: &HashSet<Node>, edges: &mut HashSet<Edge>) -> Result<HashSet<Edge>, SolveError> {
     let mut linked_edges = HashSet::new();
 
     for node in nodes {
@@ -220,7 +192,7 @@ fn link_all(nodes: &HashSet<Node>, edges: &mut HashSet<Edge>) -> HashSet<Edge> {
                 continue;
             }
 
-            let time_cost = find_shortest_time(&node, &other, edges);
+            let time_cost = find_shortest_time(node, other, edges);
 
             if let Ok(time_cost) = time_cost {
                 linked_edges.insert(Edge::new(
@@ -229,12 +201,16 @@ fn link_all(nodes: &HashSet<Node>, edges: &mut HashSet<Edge>) -> HashSet<Edge> {
                     time_cost,
                 ));
             } else {
-                panic!("Path not found between {:?} and {:?}", node, other);
+                return Err(SolveError::NoSolution(format!(
+                    "no path between valves {} and {}",
+                    node.label(),
+                    other.label()
+                )));
             }
         }
     }
 
-    linked_edges
+    Ok(linked_edges)
 }
 
 fn find_shortest_time(a: &Node, b: &Node, edges: &HashSet<Edge>) -> Result<usize, ()> {
@@ -312,10 +288,6 @@ impl STNode {
         self.time_cost
     }
 
-    fn visited(&self) -> &Vec<String> {
-        &self.visited
-    }
-
     fn add_time(&mut self, time: usize) {
         self.time_cost += time
     }
@@ -394,7 +366,7 @@ fn find_most_pressure(
     paths
 }
 
-#[derive(Debug, Eq, Ord)]
+#[derive(Debug, Eq)]
 struct MPNode {
     label: String,
     minutes: usize,
@@ -456,7 +428,7 @@ impl MPNode {
     fn current_pression(&self, minutes: usize) -> usize {
         let mut pression = 0;
         for (_, min, p) in &self.valve_opened {
-            pression += p * (minutes - *min as usize);
+            pression += p * (minutes - *min);
         }
         pression
     }
@@ -469,54 +441,35 @@ impl PartialEq for MPNode {
 }
 
 impl PartialOrd for MPNode {
-    fn lt(&self, other: &Self) -> bool {
-        self.pression() < other.pression()
-    }
-
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-fn write_graph(path: &str, edges: &HashSet<Edge>) -> Result<(), Box<dyn Error>> {
-    let mut dot_file = OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)?;
-
-    dot_file.write("graph Day16 {\n".as_bytes())?;
-
-    for edge in edges {
-        dot_file.write(
-            format!(
-                "\t{} -- {} [label={}];\n",
-                edge.a(),
-                edge.b(),
-                edge.weight()
-            )
-            .as_bytes(),
-        )?;
+        self.pression().partial_cmp(&other.pression())
     }
-
-    dot_file.write("}".as_bytes())?;
-
-    Ok(())
 }
 
-fn parse_valve(s: String) -> Result<(Node, Vec<Edge>), ()> {
-    let line: Vec<&str> = s.split(';').collect();
-
-    let valve_data: Vec<&str> = line[0].split(' ').collect();
-    let (_, mut tunnels_data) = line[1].split_at(23);
-    if tunnels_data.chars().nth(0).unwrap() == ' ' {
-        (_, tunnels_data) = tunnels_data.split_at(1);
-    }
-
-    let name = valve_data[1];
-    let flow_rate = valve_data[4];
-    let (_, flow_rate) = flow_rate.split_at(5);
-    let flow_rate = flow_rate.parse().unwrap();
+fn parse_valve(s: &str, line: usize) -> Result<(Node, Vec<Edge>), Located<ParseError>> {
+    let valve = s
+        .strip_prefix("Valve ")
+        .ok_or_else(|| unexpected("Valve", s))
+        .at(line, 1)?;
+    let (name, flow_rate) = valve
+        .split_once(" has flow rate=")
+        .ok_or_else(|| unexpected("has flow rate=", valve))
+        .at(line, column(s, valve))?;
+    let (flow_rate, tunnels_data) = flow_rate
+        .split_once("; ")
+        .ok_or_else(|| unexpected(";", flow_rate))
+        .at(line, column(s, flow_rate))?;
+
+    let flow_rate = flow_rate
+        .parse()
+        .map_err(|_| ParseError::InvalidFlowRate(flow_rate.to_string()))
+        .at(line, column(s, flow_rate))?;
+
+    let tunnels_data = tunnels_data
+        .strip_prefix("tunnels lead to valves ")
+        .or_else(|| tunnels_data.strip_prefix("tunnel leads to valve "))
+        .ok_or_else(|| unexpected("tunnels lead to valves", tunnels_data))
+        .at(line, column(s, tunnels_data))?;
 
     let node = Node::new(String::from(name), flow_rate);
 
@@ -532,6 +485,33 @@ fn parse_valve(s: String) -> Result<(Node, Vec<Edge>), ()> {
     Ok((node, edges))
 }
 
+fn unexpected(expected: &'static str, found: &str) -> ParseError {
+    ParseError::UnexpectedText {
+        expected,
+        found: found.to_string(),
+    }
+}
+
+/// Make sure every tunnel leads to a valve of the scan and that there is a valve to start from
+fn check_valves(
+    nodes: &HashSet<Node>,
+    tunnels: &[(usize, &str, String)],
+) -> Result<(), SolveError> {
+    for (line, s, label) in tunnels {
+        if !nodes.contains(&Node::new(label.clone(), 0)) {
+            let col = s.rfind(label.as_str()).map_or(1, |i| column(s, &s[i..]));
+            let error = ParseError::UnknownValve(label.clone());
+            return Err(Located::new(*line, col, error).into());
+        }
+    }
+
+    if !nodes.contains(&Node::new(String::from("AA"), 0)) {
+        return Err(SolveError::parse(ParseError::MissingStart));
+    }
+
+    Ok(())
+}
+
 // Node
 #[derive(Debug, Eq, Clone)]
 struct Node {
@@ -613,11 +593,29 @@ impl Hash for Edge {
 mod tests {
     use super::*;
 
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(1707usize));
+    }
+
+    #[test]
+    fn missing_start() {
+        let input = "Valve BB has flow rate=3; tunnel leads to valve CC\n\
+                     Valve CC has flow rate=0; tunnel leads to valve BB\n";
+
+        assert_eq!(
+            PartTwo::solve(input).unwrap_err().to_string(),
+            "Invalid input: expected a valve AA to start from, found none"
+        );
+    }
+
     #[test]
     fn valve() {
         let input = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB";
 
-        let (node, edges) = parse_valve(input.to_string()).unwrap();
+        let (node, edges) = parse_valve(input, 1).unwrap();
 
         let tunnels = Vec::from([
             Edge::new(String::from("AA"), String::from("DD"), 1),
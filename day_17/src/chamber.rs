@@ -0,0 +1,259 @@
+use std::fmt::Display;
+
+use aoc_core::{Locate, Located};
+
+use crate::error::ParseError;
+
+/// Number of rows at the top of the tower kept in a `State`
+const SURFACE: usize = 32;
+
+/// The five rocks in the order they fall, bottom row first.
+///
+/// A row is a bitmask of the 7 columns of the chamber, the left wall side being the highest bit,
+/// and every rock starts two units away from the left wall.
+const ROCKS: [&[u8]; 5] = [
+    // ####
+    &[0b0011110],
+    // .#.
+    // ###
+    // .#.
+    &[0b0001000, 0b0011100, 0b0001000],
+    // ..#
+    // ..#
+    // ###
+    &[0b0011100, 0b0000100, 0b0000100],
+    // #
+    // #
+    // #
+    // #
+    &[0b0010000, 0b0010000, 0b0010000, 0b0010000],
+    // ##
+    // ##
+    &[0b0011000, 0b0011000],
+];
+
+/// Direction a jet of hot gas pushes the falling rock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+impl TryFrom<char> for Jet {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '<' => Ok(Jet::Left),
+            '>' => Ok(Jet::Right),
+            _ => Err(ParseError::InvalidJet(value)),
+        }
+    }
+}
+
+/// Read the jet pattern, made of '<' and '>'
+pub fn parse_jets(input: &str) -> Result<Vec<Jet>, Located<ParseError>> {
+    let mut jets = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        for (col, c) in line.chars().enumerate() {
+            jets.push(Jet::try_from(c).at(index + 1, col + 1)?);
+        }
+    }
+
+    if jets.is_empty() {
+        return Err(Located::new(1, 1, ParseError::Empty));
+    }
+
+    Ok(jets)
+}
+
+/// Everything deciding how the next rocks fall: the next rock, the next jet and the top of the tower
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct State {
+    rock: usize,
+    jet: usize,
+    surface: Vec<u8>,
+}
+
+/// The tall and narrow chamber where the rocks fall
+#[derive(Debug, Clone)]
+pub struct Chamber {
+    /// Rows of the tower, bottom first
+    rows: Vec<u8>,
+    jets: Vec<Jet>,
+    /// Index of the next jet
+    jet: usize,
+    /// Number of rocks at rest
+    rocks: usize,
+}
+
+impl Chamber {
+    pub fn new(jets: Vec<Jet>) -> Self {
+        assert!(!jets.is_empty());
+
+        Chamber {
+            rows: Vec::new(),
+            jets,
+            jet: 0,
+            rocks: 0,
+        }
+    }
+
+    /// Height of the tower of rocks
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Number of rocks that came to rest
+    pub fn rocks(&self) -> usize {
+        self.rocks
+    }
+
+    /// State of the simulation, assuming that no rock falls deeper than the `SURFACE` top rows
+    pub fn state(&self) -> State {
+        let start = self.rows.len().saturating_sub(SURFACE);
+
+        State {
+            rock: self.rocks % ROCKS.len(),
+            jet: self.jet,
+            surface: self.rows[start..].to_vec(),
+        }
+    }
+
+    /// Let the next rock fall until it comes to rest
+    pub fn drop_rock(&mut self) {
+        let mut rock = ROCKS[self.rocks % ROCKS.len()].to_vec();
+        let mut bottom = self.rows.len() + 3;
+
+        loop {
+            // 1. Pushed by a jet of hot gas, if nothing is in the way
+            let jet = self.jets[self.jet];
+            self.jet = (self.jet + 1) % self.jets.len();
+
+            if let Some(pushed) = push(&rock, jet) {
+                if !self.collides(&pushed, bottom) {
+                    rock = pushed;
+                }
+            }
+
+            // 2. Falls one unit down, unless it lands on the floor or another rock
+            if bottom == 0 || self.collides(&rock, bottom - 1) {
+                break;
+            }
+            bottom -= 1;
+        }
+
+        for (index, row) in rock.iter().enumerate() {
+            let y = bottom + index;
+
+            if y >= self.rows.len() {
+                self.rows.resize(y + 1, 0);
+            }
+            self.rows[y] |= row;
+        }
+
+        self.rocks += 1;
+    }
+
+    fn collides(&self, rock: &[u8], bottom: usize) -> bool {
+        rock.iter()
+            .enumerate()
+            .any(|(index, row)| self.rows.get(bottom + index).is_some_and(|r| r & row != 0))
+    }
+}
+
+/// Rock moved one unit by `jet`, `None` when a wall is in the way
+fn push(rock: &[u8], jet: Jet) -> Option<Vec<u8>> {
+    match jet {
+        Jet::Left if rock.iter().all(|row| row & 0b1000000 == 0) => {
+            Some(rock.iter().map(|row| row << 1).collect())
+        }
+        Jet::Right if rock.iter().all(|row| row & 0b0000001 == 0) => {
+            Some(rock.iter().map(|row| row >> 1).collect())
+        }
+        _ => None,
+    }
+}
+
+impl Display for Chamber {
+    /// Draw the tower as in the puzzle, top row first
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows.iter().rev() {
+            let cells: String = (0..7)
+                .map(|x| {
+                    if row & (0b1000000 >> x) != 0 {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            writeln!(f, "|{}|", cells)?;
+        }
+
+        write!(f, "+-------+")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Chamber {
+        Chamber::new(parse_jets(include_str!("../simple_input.txt")).unwrap())
+    }
+
+    #[test]
+    fn invalid_jet() {
+        assert_eq!(
+            parse_jets(">><x>").unwrap_err().to_string(),
+            "line 1, column 4: expected '<' or '>', found 'x'"
+        );
+    }
+
+    #[test]
+    fn empty_pattern() {
+        assert_eq!(parse_jets("").unwrap_err().error(), &ParseError::Empty);
+    }
+
+    #[test]
+    fn push_against_walls() {
+        assert_eq!(push(&[0b0011110], Jet::Right), Some(vec![0b0001111]));
+        assert_eq!(push(&[0b0001111], Jet::Right), None);
+        assert_eq!(push(&[0b1111000], Jet::Left), None);
+    }
+
+    #[test]
+    fn first_rocks() {
+        let mut chamber = example();
+
+        for _ in 0..3 {
+            chamber.drop_rock();
+        }
+
+        assert_eq!(chamber.rocks(), 3);
+        assert_eq!(chamber.height(), 6);
+        assert_eq!(
+            chamber.to_string(),
+            "|..#....|\n\
+             |..#....|\n\
+             |####...|\n\
+             |..###..|\n\
+             |...#...|\n\
+             |..####.|\n\
+             +-------+"
+        );
+    }
+
+    #[test]
+    fn state_after_a_rock() {
+        let mut chamber = example();
+        let first = chamber.state();
+
+        chamber.drop_rock();
+
+        assert_ne!(chamber.state(), first);
+        assert_eq!(chamber.state().rock, 1);
+    }
+}
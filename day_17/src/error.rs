@@ -0,0 +1,22 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading the jet pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A jet that pushes neither left (<) nor right (>)
+    InvalidJet(char),
+    /// The pattern has no jet
+    Empty,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidJet(c) => write!(f, "expected '<' or '>', found '{}'", c),
+            ParseError::Empty => write!(f, "expected a jet pattern, found nothing"),
+        }
+    }
+}
+
+impl Error for ParseError {}
@@ -0,0 +1,4 @@
+pub mod chamber;
+pub mod error;
+pub mod part_one;
+pub mod part_two;
@@ -0,0 +1,39 @@
+use aoc_core::{Answer, Solution, SolveError};
+
+use crate::chamber::{parse_jets, Chamber};
+
+const ROCKS: usize = 2022;
+
+pub struct PartOne;
+
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut chamber = Chamber::new(parse_jets(input)?);
+
+        while chamber.rocks() < ROCKS {
+            chamber.drop_rock();
+        }
+
+        Ok(chamber.height().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(3068usize));
+    }
+
+    #[test]
+    fn invalid_jet() {
+        assert_eq!(
+            PartOne::solve(">><>\n<<v\n").unwrap_err().to_string(),
+            "Invalid input: line 2, column 3: expected '<' or '>', found 'v'"
+        );
+    }
+}
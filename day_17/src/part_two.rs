@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use aoc_core::{Answer, Solution, SolveError};
+
+use crate::chamber::{parse_jets, Chamber, Jet};
+
+const ROCKS: usize = 1_000_000_000_000;
+
+pub struct PartTwo;
+
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        Ok(tower_height(parse_jets(input)?, ROCKS).into())
+    }
+}
+
+/// Height of the tower once `rocks` rocks came to rest.
+///
+/// The rocks and the jets both repeat, so the simulation ends up in a state it has already been in:
+/// from there every cycle adds the same height, and only the rocks left after the last cycle are simulated.
+fn tower_height(jets: Vec<Jet>, rocks: usize) -> usize {
+    let mut chamber = Chamber::new(jets);
+    let mut seen = HashMap::new();
+
+    while chamber.rocks() < rocks {
+        if let Some((start_rocks, start_height)) =
+            seen.insert(chamber.state(), (chamber.rocks(), chamber.height()))
+        {
+            let cycle_rocks = chamber.rocks() - start_rocks;
+            let cycle_height = chamber.height() - start_height;
+
+            let cycles = (rocks - chamber.rocks()) / cycle_rocks;
+            let left = (rocks - chamber.rocks()) % cycle_rocks;
+
+            for _ in 0..left {
+                chamber.drop_rock();
+            }
+
+            return chamber.height() + cycles * cycle_height;
+        }
+
+        chamber.drop_rock();
+    }
+
+    chamber.height()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(
+            PartTwo::solve(input).unwrap(),
+            Answer::from(1514285714288usize)
+        );
+    }
+
+    #[test]
+    fn cycles_match_the_simulation() {
+        let jets = parse_jets(include_str!("../simple_input.txt")).unwrap();
+        let mut chamber = Chamber::new(jets.clone());
+
+        for _ in 0..5000 {
+            chamber.drop_rock();
+        }
+
+        assert_eq!(tower_height(jets, 5000), chamber.height());
+    }
+
+    #[test]
+    fn empty_pattern() {
+        assert_eq!(
+            PartTwo::solve("\n").unwrap_err().to_string(),
+            "Invalid input: line 1, column 1: expected a jet pattern, found nothing"
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use aoc_point::error::ParsePointError;
+
+/// Errors found while reading the cubes of the lava droplet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A cube without exactly three coordinates
+    InvalidCount(usize),
+    /// A coordinate that isn't an integer between -128 and 127
+    InvalidCoordinate(String),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidCount(n) => write!(f, "expected 3 coordinates, found {}", n),
+            ParseError::InvalidCoordinate(s) => write!(f, "expected a coordinate, found '{}'", s),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<ParsePointError> for ParseError {
+    fn from(e: ParsePointError) -> Self {
+        match e {
+            ParsePointError::InvalidCount { found, .. } => ParseError::InvalidCount(found),
+            ParsePointError::InvalidCoordinate { found, .. } => {
+                ParseError::InvalidCoordinate(found)
+            }
+        }
+    }
+}
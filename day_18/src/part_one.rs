@@ -1,99 +1,82 @@
 use std::collections::HashSet;
-use std::error::Error;
-use std::io;
+
+use aoc_core::{Answer, Located, Solution, SolveError};
+use aoc_point::error::ParsePointError;
+use aoc_point::Point3;
+
+use crate::error::ParseError;
 
 // Position are as followed
 // x right
 // y up
 // z back
 
-pub fn main_p1() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
-
-    let mut positions = HashSet::<Position>::new();
+pub struct PartOne;
 
-    for line in lines {
-        let line = line.unwrap();
-
-        let pos = Position::from(line.as_str());
-        positions.insert(pos);
-    }
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut positions = HashSet::<Position>::new();
 
-    let mut surface_area: u32 = 0;
-
-    for pos in &positions {
-        let top = Position::new(pos.x(), pos.y() + 1, pos.z());
-        let bot = Position::new(pos.x(), pos.y() - 1, pos.z());
-
-        let right = Position::new(pos.x() + 1, pos.y(), pos.z());
-        let left = Position::new(pos.x() - 1, pos.y(), pos.z());
-
-        let back = Position::new(pos.x(), pos.y(), pos.z() + 1);
-        let front = Position::new(pos.x(), pos.y(), pos.z() - 1);
+        for (index, line) in input.lines().enumerate() {
+            let pos = parse_position(line, index + 1)?;
+            positions.insert(pos);
+        }
 
-        let neigbhors = vec![top, bot, right, left, back, front];
+        let mut surface_area: u32 = 0;
 
-        for neigbhor in neigbhors {
-            if !positions.contains(&neigbhor) {
-                surface_area += 1;
+        for pos in &positions {
+            for neigbhor in pos.neighbours6() {
+                if !positions.contains(&neigbhor) {
+                    surface_area += 1;
+                }
             }
         }
-    }
-
-    println!("Total Surface Area: {}", surface_area);
 
-    Ok(())
+        Ok(surface_area.into())
+    }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Position {
-    x: i8,
-    y: i8,
-    z: i8,
-}
+type Position = Point3<i8>;
 
-impl Position {
-    fn new(x: i8, y: i8, z: i8) -> Self {
-        Self { x, y, z }
-    }
+/// Read a cube written as "x,y,z"
+fn parse_position(value: &str, line: usize) -> Result<Position, Located<ParseError>> {
+    value
+        .parse()
+        .map_err(|e: ParsePointError| Located::new(line, e.column(), e.into()))
+}
 
-    fn x(&self) -> i8 {
-        self.x
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    fn y(&self) -> i8 {
-        self.y
-    }
+    #[test]
+    fn two_cubes() {
+        let input = "1,1,1\n2,1,1\n";
 
-    fn z(&self) -> i8 {
-        self.z
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(10u32));
     }
-}
-
-impl From<&str> for Position {
-    fn from(value: &str) -> Self {
-        let values: Vec<&str> = value.split(',').collect();
 
-        assert_eq!(values.len(), 3);
-
-        let x = values[0].parse().unwrap();
-        let y = values[1].parse().unwrap();
-        let z = values[2].parse().unwrap();
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
 
-        Position::new(x, y, z)
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(64u32));
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
     #[test]
     fn parse_one_cube() {
         let input = "5,2,12";
 
-        let pos = Position::from(input);
+        let pos = parse_position(input, 1);
+
+        assert_eq!(pos, Ok(Position::new(5, 2, 12)));
+    }
 
-        assert_eq!(pos, Position::new(5, 2, 12));
+    #[test]
+    fn parse_invalid_coordinate() {
+        assert_eq!(
+            PartOne::solve("1,1,1\n2,1,300\n").unwrap_err().to_string(),
+            "Invalid input: line 2, column 5: expected a coordinate, found '300'"
+        );
     }
 }
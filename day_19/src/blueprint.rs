@@ -0,0 +1,268 @@
+use aoc_core::{column, Locate, Located};
+
+use crate::error::ParseError;
+
+const ORE: usize = 0;
+const CLAY: usize = 1;
+const OBSIDIAN: usize = 2;
+const GEODE: usize = 3;
+
+/// Text around the 7 numbers of a blueprint
+const TEMPLATE: [&str; 8] = [
+    "Blueprint ",
+    ": Each ore robot costs ",
+    " ore. Each clay robot costs ",
+    " ore. Each obsidian robot costs ",
+    " ore and ",
+    " clay. Each geode robot costs ",
+    " ore and ",
+    " obsidian.",
+];
+
+/// Costs of the four robots a factory can build
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blueprint {
+    id: u32,
+    /// Minerals needed to build each robot, indexed by the mineral the robot collects
+    costs: [[u32; 4]; 4],
+}
+
+/// Outcome of the search for the most geodes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Search {
+    /// Most geodes that can be opened
+    pub geodes: u32,
+    /// Number of states explored to find them
+    pub nodes: usize,
+}
+
+impl Blueprint {
+    /// Read a blueprint written on a single line
+    pub fn parse(s: &str, line: usize) -> Result<Self, Located<ParseError>> {
+        let mut numbers = [0; 7];
+        let mut rest = s;
+
+        for (index, text) in TEMPLATE.iter().enumerate() {
+            rest = rest
+                .strip_prefix(text)
+                .ok_or_else(|| unexpected(text.trim(), rest))
+                .at(line, column(s, rest))?;
+
+            if let Some(number) = numbers.get_mut(index) {
+                let end = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                let token = rest.split([' ', ':']).next().unwrap_or(rest);
+
+                *number = rest[..end]
+                    .parse()
+                    .map_err(|_| ParseError::InvalidNumber(token.to_string()))
+                    .at(line, column(s, rest))?;
+                rest = &rest[end..];
+            }
+        }
+
+        if !rest.is_empty() {
+            return Err(unexpected("end of the blueprint", rest)).at(line, column(s, rest));
+        }
+
+        let [id, ore, clay, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian] = numbers;
+
+        Ok(Blueprint {
+            id,
+            costs: [
+                [ore, 0, 0, 0],
+                [clay, 0, 0, 0],
+                [obsidian_ore, obsidian_clay, 0, 0],
+                [geode_ore, 0, geode_obsidian, 0],
+            ],
+        })
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Most geodes that can be opened in `minutes`, starting with a single ore robot.
+    ///
+    /// Like `day_16::find_most_pressure`, each state of the search jumps straight to the next
+    /// decision, here the next robot to build, instead of going minute by minute.
+    /// A branch is cut as soon as even a new geode robot every minute left can't beat the best
+    /// found so far, and robots are never built beyond what can be spent in a minute.
+    pub fn max_geodes(&self, minutes: u32) -> Search {
+        let mut max_robots = [u32::MAX; 4];
+        for mineral in [ORE, CLAY, OBSIDIAN] {
+            max_robots[mineral] = self.costs.iter().map(|cost| cost[mineral]).max().unwrap();
+        }
+
+        let mut stack = vec![State::new()];
+        let mut best = 0;
+        let mut nodes = 0;
+
+        while let Some(state) = stack.pop() {
+            nodes += 1;
+
+            let left = minutes - state.minutes;
+
+            // The geode robots keep opening geodes even if nothing else is built
+            best = best.max(state.minerals[GEODE] + state.robots[GEODE] * left);
+
+            if state.upper_bound(minutes) <= best {
+                continue;
+            }
+
+            // The geode robot is pushed last so that it is explored first
+            for robot in [ORE, CLAY, OBSIDIAN, GEODE] {
+                if state.robots[robot] >= max_robots[robot] {
+                    continue;
+                }
+
+                if let Some(next) = state.build(&self.costs[robot], robot, minutes) {
+                    stack.push(next);
+                }
+            }
+        }
+
+        Search {
+            geodes: best,
+            nodes,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct State {
+    /// Minutes elapsed
+    minutes: u32,
+    /// Robots collecting each mineral
+    robots: [u32; 4],
+    /// Minerals collected and not spent yet
+    minerals: [u32; 4],
+}
+
+impl State {
+    fn new() -> Self {
+        State {
+            minutes: 0,
+            robots: [1, 0, 0, 0],
+            minerals: [0; 4],
+        }
+    }
+
+    /// Geodes opened if a geode robot could be built every minute left
+    fn upper_bound(&self, minutes: u32) -> u32 {
+        let left = minutes - self.minutes;
+
+        self.minerals[GEODE] + self.robots[GEODE] * left + left * left.saturating_sub(1) / 2
+    }
+
+    /// State once the minerals for `robot` are collected and it is built,
+    /// `None` if it can't be built or would be ready too late to collect anything
+    fn build(&self, cost: &[u32; 4], robot: usize, minutes: u32) -> Option<Self> {
+        let mut wait = 0;
+
+        for mineral in [ORE, CLAY, OBSIDIAN] {
+            let missing = cost[mineral].saturating_sub(self.minerals[mineral]);
+
+            if missing > 0 {
+                if self.robots[mineral] == 0 {
+                    return None;
+                }
+                wait = wait.max(missing.div_ceil(self.robots[mineral]));
+            }
+        }
+
+        // The robot takes a minute to build and collects from the next one
+        let elapsed = wait + 1;
+        if self.minutes + elapsed >= minutes {
+            return None;
+        }
+
+        let mut next = *self;
+        next.minutes += elapsed;
+        for mineral in [ORE, CLAY, OBSIDIAN, GEODE] {
+            next.minerals[mineral] += self.robots[mineral] * elapsed;
+            next.minerals[mineral] -= cost[mineral];
+        }
+        next.robots[robot] += 1;
+
+        Some(next)
+    }
+}
+
+fn unexpected(expected: &'static str, found: &str) -> ParseError {
+    ParseError::UnexpectedText {
+        expected,
+        found: found.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example(index: usize) -> Blueprint {
+        let line = include_str!("../simple_input.txt")
+            .lines()
+            .nth(index)
+            .unwrap();
+
+        Blueprint::parse(line, index + 1).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            example(0),
+            Blueprint {
+                id: 1,
+                costs: [[4, 0, 0, 0], [2, 0, 0, 0], [3, 14, 0, 0], [2, 0, 7, 0]],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_invalid_cost() {
+        let line = "Blueprint 3: Each ore robot costs four ore.";
+
+        assert_eq!(
+            Blueprint::parse(line, 3).unwrap_err().to_string(),
+            "line 3, column 35: expected a number, found 'four'"
+        );
+    }
+
+    #[test]
+    fn parse_unexpected_text() {
+        let line = "Blueprint 3: Each ore robot costs 4 ore. Each clay robot costs 2 clay.";
+
+        assert_eq!(
+            Blueprint::parse(line, 1).unwrap_err().to_string(),
+            "line 1, column 65: expected 'ore. Each obsidian robot costs', found ' clay.'"
+        );
+    }
+
+    #[test]
+    fn build_waits_for_minerals() {
+        let state = State::new();
+        let next = state.build(&[2, 0, 0, 0], CLAY, 24).unwrap();
+
+        assert_eq!(next.minutes, 3);
+        assert_eq!(next.robots, [1, 1, 0, 0]);
+        assert_eq!(next.minerals, [1, 0, 0, 0]);
+        assert_eq!(state.build(&[3, 14, 0, 0], OBSIDIAN, 24), None);
+    }
+
+    #[test]
+    fn max_geodes() {
+        assert_eq!(example(0).max_geodes(24).geodes, 9);
+        assert_eq!(example(1).max_geodes(24).geodes, 12);
+    }
+
+    #[test]
+    fn search_stays_small() {
+        let search = example(0).max_geodes(24);
+
+        assert!(search.nodes > 0);
+        assert!(search.nodes < 50_000, "explored {} nodes", search.nodes);
+    }
+}
@@ -0,0 +1,27 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading the blueprints
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Text other than the expected part of a blueprint
+    UnexpectedText {
+        expected: &'static str,
+        found: String,
+    },
+    /// A blueprint id or a cost that isn't a positive integer
+    InvalidNumber(String),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnexpectedText { expected, found } => {
+                write!(f, "expected '{}', found '{}'", expected, found)
+            }
+            ParseError::InvalidNumber(s) => write!(f, "expected a number, found '{}'", s),
+        }
+    }
+}
+
+impl Error for ParseError {}
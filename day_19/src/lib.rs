@@ -0,0 +1,4 @@
+pub mod blueprint;
+pub mod error;
+pub mod part_one;
+pub mod part_two;
@@ -0,0 +1,66 @@
+use aoc_core::{Answer, Solution, SolveError};
+
+use crate::blueprint::Blueprint;
+
+const MINUTES: u32 = 24;
+
+pub struct PartOne;
+
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut blueprints = Vec::new();
+
+        for (index, line) in input.lines().enumerate() {
+            blueprints.push(Blueprint::parse(line, index + 1)?);
+        }
+
+        if blueprints.is_empty() {
+            return Err(SolveError::NoSolution(String::from(
+                "no blueprint to choose from",
+            )));
+        }
+
+        // Wide enough for the largest ids times any number of geodes
+        let quality_levels: i128 = blueprints
+            .iter()
+            .map(|blueprint| {
+                i128::from(blueprint.id()) * i128::from(blueprint.max_geodes(MINUTES).geodes)
+            })
+            .sum();
+
+        Ok(quality_levels.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(33u32));
+    }
+
+    #[test]
+    fn large_id() {
+        let input =
+            include_str!("../simple_input.txt").replace("Blueprint 2:", "Blueprint 4294967295:");
+
+        assert_eq!(
+            PartOne::solve(&input).unwrap(),
+            Answer::from(9 + 12 * 4294967295u64)
+        );
+    }
+
+    #[test]
+    fn invalid_id() {
+        assert_eq!(
+            PartOne::solve("Blueprint one: Each ore robot costs 4 ore.\n")
+                .unwrap_err()
+                .to_string(),
+            "Invalid input: line 1, column 11: expected a number, found 'one'"
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use aoc_core::{Answer, Solution, SolveError};
+
+use crate::blueprint::Blueprint;
+
+const MINUTES: u32 = 32;
+
+/// The elephants ate all the blueprints but the first ones
+const BLUEPRINTS: usize = 3;
+
+pub struct PartTwo;
+
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut blueprints = Vec::new();
+
+        for (index, line) in input.lines().take(BLUEPRINTS).enumerate() {
+            blueprints.push(Blueprint::parse(line, index + 1)?);
+        }
+
+        if blueprints.is_empty() {
+            return Err(SolveError::NoSolution(String::from(
+                "no blueprint to choose from",
+            )));
+        }
+
+        let geodes: u32 = blueprints
+            .iter()
+            .map(|blueprint| blueprint.max_geodes(MINUTES).geodes)
+            .product();
+
+        Ok(geodes.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(56u32 * 62));
+    }
+
+    #[test]
+    fn no_blueprint() {
+        assert_eq!(
+            PartTwo::solve("").unwrap_err().to_string(),
+            "No solution: no blueprint to choose from"
+        );
+    }
+}
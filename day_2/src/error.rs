@@ -0,0 +1,31 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading the strategy guide
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The first column isn't one of `A`, `B` or `C`
+    InvalidOpponent(char),
+    /// The second column isn't one of `X`, `Y` or `Z`
+    InvalidResponse(char),
+    /// The line stops before the named column
+    MissingColumn(&'static str),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidOpponent(c) => {
+                write!(f, "expected the opponent (A, B or C), found '{}'", c)
+            }
+            ParseError::InvalidResponse(c) => {
+                write!(f, "expected the response (X, Y or Z), found '{}'", c)
+            }
+            ParseError::MissingColumn(column) => {
+                write!(f, "expected the {}, found the end of the line", column)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
@@ -0,0 +1,143 @@
+use aoc_core::{Answer, Locate, Solution, SolveError};
+
+use crate::error::ParseError;
+
+const LOSE: u32 = 0;
+const DRAW: u32 = 3;
+const WIN: u32 = 6;
+
+/// Opponent
+/// A => Rock
+/// B => Paper
+/// C => Scissors
+enum Opponent {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl TryFrom<char> for Opponent {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'A' => Ok(Opponent::Rock),
+            'B' => Ok(Opponent::Paper),
+            'C' => Ok(Opponent::Scissors),
+            _ => Err(ParseError::InvalidOpponent(c)),
+        }
+    }
+}
+
+/// Player
+/// Y => Paper
+/// X => Rock
+/// Z => Scissors
+enum Player {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl TryFrom<char> for Player {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'X' => Ok(Player::Rock),
+            'Y' => Ok(Player::Paper),
+            'Z' => Ok(Player::Scissors),
+            _ => Err(ParseError::InvalidResponse(c)),
+        }
+    }
+}
+
+fn fight_part_one(opponent: Opponent, player: Player) -> u32 {
+    match opponent {
+        Opponent::Rock => match player {
+            Player::Rock => 1 + DRAW,
+            Player::Paper => 2 + WIN,
+            Player::Scissors => 3 + LOSE,
+        },
+        Opponent::Paper => match player {
+            Player::Rock => 1 + LOSE,
+            Player::Paper => 2 + DRAW,
+            Player::Scissors => 3 + WIN,
+        },
+        Opponent::Scissors => match player {
+            Player::Rock => 1 + WIN,
+            Player::Paper => 2 + LOSE,
+            Player::Scissors => 3 + DRAW,
+        },
+    }
+}
+
+pub struct PartOne;
+
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut score: u32 = 0;
+
+        for (index, line) in input.lines().enumerate() {
+            let mut it = line.chars();
+
+            let opponent = it
+                .next()
+                .ok_or(ParseError::MissingColumn("opponent"))
+                .and_then(Opponent::try_from)
+                .at(index + 1, 1)?;
+            let _ = it.next();
+            let player = it
+                .next()
+                .ok_or(ParseError::MissingColumn("response"))
+                .and_then(Player::try_from)
+                .at(index + 1, 3)?;
+
+            let round = fight_part_one(opponent, player);
+
+            score += round;
+        }
+
+        Ok(score.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = "A Y\nB X\nC Z\n";
+
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(15u32));
+    }
+
+    #[test]
+    fn invalid_opponent() {
+        assert_eq!(
+            Opponent::try_from('D').err(),
+            Some(ParseError::InvalidOpponent('D'))
+        );
+    }
+
+    #[test]
+    fn invalid_response_line() {
+        let error = PartOne::solve("A Y\nB W\n").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid input: line 2, column 3: expected the response (X, Y or Z), found 'W'"
+        );
+    }
+
+    #[test]
+    fn missing_response() {
+        let error = PartOne::solve("A Y\nC\n").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid input: line 2, column 3: expected the response, found the end of the line"
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use aoc_core::{Answer, Locate, Solution, SolveError};
+
+use crate::error::ParseError;
+
+const LOSE: u32 = 0;
+const DRAW: u32 = 3;
+const WIN: u32 = 6;
+
+const ROCK: u32 = 1;
+const PAPER: u32 = 2;
+const SCISSORS: u32 = 3;
+
+/// Opponent
+/// A => Rock
+/// B => Paper
+/// C => Scissors
+enum Opponent {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl TryFrom<char> for Opponent {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'A' => Ok(Opponent::Rock),
+            'B' => Ok(Opponent::Paper),
+            'C' => Ok(Opponent::Scissors),
+            _ => Err(ParseError::InvalidOpponent(c)),
+        }
+    }
+}
+
+/// Player
+/// Y => DRAW
+/// X => LOSE
+/// Z => WIN
+enum RoundStatus {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl TryFrom<char> for RoundStatus {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'X' => Ok(RoundStatus::Lose),
+            'Y' => Ok(RoundStatus::Draw),
+            'Z' => Ok(RoundStatus::Win),
+            _ => Err(ParseError::InvalidResponse(c)),
+        }
+    }
+}
+
+fn fight_part_two(opponent: Opponent, round_status: RoundStatus) -> u32 {
+    match opponent {
+        Opponent::Rock => match round_status {
+            RoundStatus::Win => WIN + PAPER,
+            RoundStatus::Draw => DRAW + ROCK,
+            RoundStatus::Lose => LOSE + SCISSORS,
+        },
+        Opponent::Paper => match round_status {
+            RoundStatus::Win => WIN + SCISSORS,
+            RoundStatus::Draw => DRAW + PAPER,
+            RoundStatus::Lose => LOSE + ROCK,
+        },
+        Opponent::Scissors => match round_status {
+            RoundStatus::Win => WIN + ROCK,
+            RoundStatus::Draw => DRAW + SCISSORS,
+            RoundStatus::Lose => LOSE + PAPER,
+        },
+    }
+}
+
+pub struct PartTwo;
+
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut score: u32 = 0;
+
+        for (index, line) in input.lines().enumerate() {
+            let mut it = line.chars();
+
+            let opponent = it
+                .next()
+                .ok_or(ParseError::MissingColumn("opponent"))
+                .and_then(Opponent::try_from)
+                .at(index + 1, 1)?;
+            let _ = it.next();
+            let round_status = it
+                .next()
+                .ok_or(ParseError::MissingColumn("response"))
+                .and_then(RoundStatus::try_from)
+                .at(index + 1, 3)?;
+
+            let round = fight_part_two(opponent, round_status);
+
+            score += round;
+        }
+
+        Ok(score.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = "A Y\nB X\nC Z\n";
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(12u32));
+    }
+
+    #[test]
+    fn invalid_opponent_line() {
+        let error = PartTwo::solve("E X\n").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid input: line 1, column 1: expected the opponent (A, B or C), found 'E'"
+        );
+    }
+}
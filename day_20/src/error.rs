@@ -0,0 +1,22 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading the encrypted file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A line that isn't an integer
+    InvalidNumber(String),
+    /// The file has no 0 to find the grove coordinates from
+    MissingZero,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidNumber(s) => write!(f, "expected a number, found '{}'", s),
+            ParseError::MissingZero => write!(f, "expected a 0 in the file, found none"),
+        }
+    }
+}
+
+impl Error for ParseError {}
@@ -1,40 +1,48 @@
-use std::error::Error;
-use std::io;
+use aoc_core::{Answer, Locate, Solution, SolveError};
 
-pub fn main_p1() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
+use crate::error::ParseError;
 
-    let mut numbers = Vec::<(i32, bool)>::new();
+pub struct PartOne;
 
-    for line in lines {
-        let line = line.unwrap();
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut numbers = Vec::<(i32, bool)>::new();
 
-        numbers.push((line.parse().unwrap(), false));
-    }
+        for (index, line) in input.lines().enumerate() {
+            let number = line
+                .parse()
+                .map_err(|_| ParseError::InvalidNumber(line.to_string()))
+                .at(index + 1, 1)?;
+            numbers.push((number, false));
+        }
 
-    let instructions = numbers.clone();
+        if !numbers.iter().any(|n| n.0 == 0) {
+            return Err(SolveError::parse(ParseError::MissingZero));
+        }
+        if numbers.len() < 2 {
+            return Err(SolveError::NoSolution(String::from(
+                "mixing needs at least two numbers",
+            )));
+        }
 
-    for instruction in &instructions {
-        apply_instruction(instruction.0, &mut numbers);
-    }
+        let instructions = numbers.clone();
 
-    let (index, _) = numbers.iter().enumerate().find(|&n| (*n.1).0 == 0).unwrap();
+        for instruction in &instructions {
+            apply_instruction(instruction.0, &mut numbers);
+        }
 
-    let index_1000 = (index + 1000) % numbers.len();
-    let index_2000 = (index + 2000) % numbers.len();
-    let index_3000 = (index + 3000) % numbers.len();
+        let (index, _) = numbers.iter().enumerate().find(|&n| n.1 .0 == 0).unwrap();
 
-    let number_1000 = numbers.get(index_1000).unwrap();
-    let number_2000 = numbers.get(index_2000).unwrap();
-    let number_3000 = numbers.get(index_3000).unwrap();
+        let index_1000 = (index + 1000) % numbers.len();
+        let index_2000 = (index + 2000) % numbers.len();
+        let index_3000 = (index + 3000) % numbers.len();
 
-    println!(
-        "1000 {} 2000 {} 3000 {}",
-        number_1000.0, number_2000.0, number_3000.0
-    );
-    println!("Sum: {}", number_1000.0 + number_2000.0 + number_3000.0);
+        let number_1000 = numbers.get(index_1000).unwrap();
+        let number_2000 = numbers.get(index_2000).unwrap();
+        let number_3000 = numbers.get(index_3000).unwrap();
 
-    Ok(())
+        Ok((number_1000.0 + number_2000.0 + number_3000.0).into())
+    }
 }
 
 fn apply_instruction(instruction: i32, numbers: &mut Vec<(i32, bool)>) {
@@ -42,7 +50,7 @@ fn apply_instruction(instruction: i32, numbers: &mut Vec<(i32, bool)>) {
     let (index, _) = numbers
         .iter()
         .enumerate()
-        .find(|&n| !(*n.1).1 && (*n.1).0 == instruction)
+        .find(|&n| !n.1 .1 && n.1 .0 == instruction)
         .unwrap();
 
     numbers.remove(index);
@@ -63,6 +71,21 @@ fn apply_instruction(instruction: i32, numbers: &mut Vec<(i32, bool)>) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(3i32));
+    }
+
+    #[test]
+    fn invalid_number() {
+        assert_eq!(
+            PartOne::solve("1\n2\n-x\n0\n").unwrap_err().to_string(),
+            "Invalid input: line 3, column 1: expected a number, found '-x'"
+        );
+    }
+
     /*
     #[test]
     fn simple() {
@@ -1,46 +1,51 @@
-use std::error::Error;
-use std::io;
+use aoc_core::{Answer, Locate, Solution, SolveError};
 
-pub fn main_p2() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
+use crate::error::ParseError;
 
-    let decryption_key = 811_589_153;
-    let mut numbers = Vec::<i64>::new();
+pub struct PartTwo;
 
-    for line in lines {
-        let line = line.unwrap();
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let decryption_key = 811_589_153;
+        let mut numbers = Vec::<i64>::new();
 
-        numbers.push(line.parse().unwrap());
-    }
+        for (index, line) in input.lines().enumerate() {
+            let number = line
+                .parse()
+                .map_err(|_| ParseError::InvalidNumber(line.to_string()))
+                .at(index + 1, 1)?;
+            numbers.push(number);
+        }
 
-    let mut numbers = apply_encryption_key(numbers, decryption_key);
+        if !numbers.contains(&0) {
+            return Err(SolveError::parse(ParseError::MissingZero));
+        }
+        if numbers.len() < 2 {
+            return Err(SolveError::NoSolution(String::from(
+                "mixing needs at least two numbers",
+            )));
+        }
 
-    println!("Initial arrangement:\n{:?}", numbers);
+        let mut numbers = apply_encryption_key(numbers, decryption_key);
 
-    for round in 1..11 {
-        for instruction in 0..numbers.len() {
-            apply_instruction(instruction, &mut numbers);
+        for _ in 1..11 {
+            for instruction in 0..numbers.len() {
+                apply_instruction(instruction, &mut numbers);
+            }
         }
-        println!("After {} rounds of mixing:\n{:?}", round, numbers);
-    }
-
-    let (index, _) = numbers.iter().enumerate().find(|&n| (*n.1).1 == 0).unwrap();
 
-    let index_1000 = (index + 1000) % numbers.len();
-    let index_2000 = (index + 2000) % numbers.len();
-    let index_3000 = (index + 3000) % numbers.len();
+        let (index, _) = numbers.iter().enumerate().find(|&n| n.1 .1 == 0).unwrap();
 
-    let number_1000 = numbers.get(index_1000).unwrap();
-    let number_2000 = numbers.get(index_2000).unwrap();
-    let number_3000 = numbers.get(index_3000).unwrap();
+        let index_1000 = (index + 1000) % numbers.len();
+        let index_2000 = (index + 2000) % numbers.len();
+        let index_3000 = (index + 3000) % numbers.len();
 
-    println!(
-        "1000 {} 2000 {} 3000 {}",
-        number_1000.1, number_2000.1, number_3000.1
-    );
-    println!("Sum: {}", number_1000.1 + number_2000.1 + number_3000.1);
+        let number_1000 = numbers.get(index_1000).unwrap();
+        let number_2000 = numbers.get(index_2000).unwrap();
+        let number_3000 = numbers.get(index_3000).unwrap();
 
-    Ok(())
+        Ok((number_1000.1 + number_2000.1 + number_3000.1).into())
+    }
 }
 
 fn apply_instruction(instruction: usize, numbers: &mut Vec<(usize, i64)>) {
@@ -78,14 +83,30 @@ fn apply_encryption_key(arr: Vec<i64>, decryption_key: i64) -> Vec<(usize, i64)>
 mod tests {
     use super::*;
 
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(1623178306i64));
+    }
+
+    #[test]
+    fn missing_zero() {
+        assert_eq!(
+            PartTwo::solve("1\n2\n-3\n").unwrap_err().to_string(),
+            "Invalid input: expected a 0 in the file, found none"
+        );
+    }
+
     static DECRYPTION_KEY: i64 = 811_589_153;
 
     #[test]
     fn simple() {
-        let mut numbers = vec![1, 2, -3, 3, -2, 0, 4];
-        let mut numbers = apply_encryption_key(numbers, DECRYPTION_KEY);
+        let numbers = vec![1, 2, -3, 3, -2, 0, 4];
+        let numbers = apply_encryption_key(numbers, DECRYPTION_KEY);
 
-        for instruction in 1..numbers.len() {}
+        assert_eq!(numbers[2], (2, -2_434_767_459));
+        assert_eq!(numbers[5], (5, 0));
     }
 
     /*
@@ -0,0 +1,47 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading the monkeys jobs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A line that isn't `name: job`
+    MissingSeparator(String),
+    /// A job that is neither a number nor an operation between two monkeys
+    InvalidExpression(String),
+    /// A number that isn't an integer
+    InvalidNumber(String),
+    /// An operator other than +, -, * or /
+    InvalidOperator(String),
+    /// An operation waiting for a monkey that isn't in the list
+    UnknownMonkey(String),
+    /// A monkey the puzzle needs isn't in the list
+    MissingMonkey(&'static str),
+    /// A monkey waiting, through other monkeys, for its own number
+    CyclicMonkey(String),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::MissingSeparator(s) => write!(f, "expected 'name: job', found '{}'", s),
+            ParseError::InvalidExpression(s) => {
+                write!(
+                    f,
+                    "expected a number or 'left <operator> right', found '{}'",
+                    s
+                )
+            }
+            ParseError::InvalidNumber(s) => write!(f, "expected a number, found '{}'", s),
+            ParseError::InvalidOperator(s) => {
+                write!(f, "expected '+', '-', '*' or '/', found '{}'", s)
+            }
+            ParseError::UnknownMonkey(s) => write!(f, "monkey '{}' doesn't exist", s),
+            ParseError::MissingMonkey(s) => {
+                write!(f, "expected a monkey named '{}', found none", s)
+            }
+            ParseError::CyclicMonkey(s) => write!(f, "monkey '{}' waits for itself", s),
+        }
+    }
+}
+
+impl Error for ParseError {}
@@ -1,62 +1,105 @@
-use std::collections::HashMap;
-use std::error::Error;
-use std::io;
-
-pub fn main_p1() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
-
-    let mut monkeys = HashMap::<String, Expression>::new();
-
-    for line in lines {
-        let line = line.unwrap();
-
-        let (monkey, expr) = line.split_once(": ").unwrap();
-
-        let expr: Vec<&str> = expr.split(' ').collect();
-
-        if expr.len() == 1 {
-            monkeys.insert(
-                monkey.to_string(),
-                Expression::Number(expr[0].parse().unwrap()),
-            );
-        } else if expr.len() == 3 {
-            monkeys.insert(
-                monkey.to_string(),
-                Expression::Operation(
-                    expr[0].to_string(),
-                    Operator::from(expr[1]),
-                    expr[2].to_string(),
-                ),
-            );
-        } else {
-            panic!("Expression not recognized '{:?}'", expr);
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::str::FromStr;
+
+use aoc_core::{column, Answer, Locate, Located, Solution, SolveError};
+
+use crate::error::ParseError;
+
+pub struct PartOne;
+
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut monkeys = HashMap::<String, Expression>::new();
+
+        // Monkeys waited by an operation, checked once every monkey is known
+        let mut references = Vec::<(usize, usize, &str)>::new();
+
+        for (index, line) in input.lines().enumerate() {
+            let (monkey, expr) = line
+                .split_once(": ")
+                .ok_or_else(|| ParseError::MissingSeparator(line.to_string()))
+                .at(index + 1, 1)?;
+
+            let expression = match expr.split_whitespace().collect::<Vec<&str>>()[..] {
+                [number] => number
+                    .parse()
+                    .map(Expression::Number)
+                    .map_err(|_| ParseError::InvalidNumber(number.to_string()))
+                    .at(index + 1, column(line, number))?,
+                [left, op, right] => {
+                    let op = Operator::from_str(op).at(index + 1, column(line, op))?;
+
+                    references.push((index + 1, column(line, left), left));
+                    references.push((index + 1, column(line, right), right));
+
+                    Expression::Operation(left.to_string(), op, right.to_string())
+                }
+                _ => {
+                    let error = ParseError::InvalidExpression(expr.to_string());
+                    return Err(Located::new(index + 1, column(line, expr), error).into());
+                }
+            };
+
+            monkeys.insert(monkey.to_string(), expression);
         }
-    }
 
-    let result = eval_monkey(String::from("root"), &monkeys);
-    println!("Result: {}", result);
+        for (line, col, monkey) in references {
+            if !monkeys.contains_key(monkey) {
+                let error = ParseError::UnknownMonkey(monkey.to_string());
+                return Err(Located::new(line, col, error).into());
+            }
+        }
 
-    Ok(())
-}
+        if !monkeys.contains_key("root") {
+            return Err(SolveError::parse(ParseError::MissingMonkey("root")));
+        }
+
+        let result = eval("root", &monkeys, &mut HashSet::new())?;
 
-fn eval_monkey(monkey: String, monkeys: &HashMap<String, Expression>) -> i64 {
-    eval(monkeys.get(&monkey).unwrap(), monkeys)
+        Ok(result.into())
+    }
 }
 
-fn eval(expr: &Expression, monkeys: &HashMap<String, Expression>) -> i64 {
-    match expr {
+/// Number yelled by a monkey, `visiting` holding the monkeys waiting for it
+fn eval<'a>(
+    monkey: &'a str,
+    monkeys: &'a HashMap<String, Expression>,
+    visiting: &mut HashSet<&'a str>,
+) -> Result<i64, SolveError> {
+    let expr = monkeys
+        .get(monkey)
+        .ok_or_else(|| SolveError::parse(ParseError::UnknownMonkey(monkey.to_string())))?;
+
+    if !visiting.insert(monkey) {
+        return Err(SolveError::parse(ParseError::CyclicMonkey(
+            monkey.to_string(),
+        )));
+    }
+
+    let result = match expr {
         Expression::Number(value) => *value,
         Expression::Operation(lmonkey, op, rmonkey) => {
-            let left = eval(monkeys.get(lmonkey).unwrap(), monkeys);
-            let right = eval(monkeys.get(rmonkey).unwrap(), monkeys);
-            match op {
-                Operator::Plus => left + right,
-                Operator::Minus => left - right,
-                Operator::Mul => left * right,
-                Operator::Div => left / right,
-            }
+            let left = eval(lmonkey, monkeys, visiting)?;
+            let right = eval(rmonkey, monkeys, visiting)?;
+            let result = match op {
+                Operator::Plus => left.checked_add(right),
+                Operator::Minus => left.checked_sub(right),
+                Operator::Mul => left.checked_mul(right),
+                Operator::Div => left.checked_div(right),
+            };
+
+            result.ok_or_else(|| {
+                SolveError::NoSolution(format!(
+                    "monkey '{}' can't compute {} {} {}",
+                    monkey, left, op, right
+                ))
+            })?
         }
-    }
+    };
+
+    visiting.remove(monkey);
+    Ok(result)
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -67,17 +110,27 @@ enum Operator {
     Div,
 }
 
-impl From<&str> for Operator {
-    fn from(value: &str) -> Self {
-        match value.chars().nth(0) {
-            Some(value) => match value {
-                '+' => Operator::Plus,
-                '-' => Operator::Minus,
-                '*' => Operator::Mul,
-                '/' => Operator::Div,
-                _ => panic!("Character {} invalid", value),
-            },
-            None => panic!("Number invalid"),
+impl FromStr for Operator {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "+" => Ok(Operator::Plus),
+            "-" => Ok(Operator::Minus),
+            "*" => Ok(Operator::Mul),
+            "/" => Ok(Operator::Div),
+            _ => Err(ParseError::InvalidOperator(value.to_string())),
+        }
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operator::Plus => f.write_str("+"),
+            Operator::Minus => f.write_str("-"),
+            Operator::Mul => f.write_str("*"),
+            Operator::Div => f.write_str("/"),
         }
     }
 }
@@ -92,6 +145,53 @@ enum Expression {
 mod tests {
     use super::*;
 
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(152i64));
+    }
+
     #[test]
     fn simple() {}
+
+    #[test]
+    fn invalid_operator() {
+        let input = "root: aaaa % bbbb\naaaa: 5\nbbbb: 3\n";
+
+        assert_eq!(
+            PartOne::solve(input).unwrap_err().to_string(),
+            "Invalid input: line 1, column 12: expected '+', '-', '*' or '/', found '%'"
+        );
+    }
+
+    #[test]
+    fn division_by_zero() {
+        let input = "root: aaaa / bbbb\naaaa: 5\nbbbb: cccc - cccc\ncccc: 3\n";
+
+        assert_eq!(
+            PartOne::solve(input).unwrap_err().to_string(),
+            "No solution: monkey 'root' can't compute 5 / 0"
+        );
+    }
+
+    #[test]
+    fn cyclic_monkeys() {
+        let input = "root: aaaa + bbbb\naaaa: 5\nbbbb: cccc * aaaa\ncccc: root - aaaa\n";
+
+        assert_eq!(
+            PartOne::solve(input).unwrap_err().to_string(),
+            "Invalid input: monkey 'root' waits for itself"
+        );
+    }
+
+    #[test]
+    fn unknown_monkey() {
+        let input = "root: aaaa + bbbb\naaaa: 5\nbbbc: 3\n";
+
+        assert_eq!(
+            PartOne::solve(input).unwrap_err().to_string(),
+            "Invalid input: line 1, column 14: monkey 'bbbb' doesn't exist"
+        );
+    }
 }
@@ -1,95 +1,90 @@
-use std::collections::HashMap;
-use std::error::Error;
-use std::fmt::{Display, Write};
-use std::io;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::str::FromStr;
+
+use aoc_core::{column, Answer, Locate, Located, Solution, SolveError};
+
+use crate::error::ParseError;
 
 //TODO: Reduce the tree to have a node with : humn + <number>
 // Then, swap the nodes to the other side of the tree, to compute humn
 
-pub fn main_p2() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
-
-    let mut monkeys = HashMap::<String, Expression>::new();
-
-    for line in lines {
-        let line = line.unwrap();
-
-        let (monkey, expr) = line.split_once(": ").unwrap();
-
-        let expr: Vec<&str> = expr.split(' ').collect();
-
-        if expr.len() == 1 {
-            monkeys.insert(
-                monkey.to_string(),
-                Expression::Number(expr[0].parse().unwrap()),
-            );
-        } else if expr.len() == 3 {
-            monkeys.insert(
-                monkey.to_string(),
-                Expression::Operation(
-                    expr[0].to_string(),
-                    Operator::from(expr[1]),
-                    expr[2].to_string(),
-                ),
-            );
-        } else {
-            panic!("Expression not recognized '{:?}'", expr);
-        }
-    }
+pub struct PartTwo;
 
-    let tree_expr = build_expr_tree(String::from("root"), &monkeys);
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut monkeys = HashMap::<String, Expression>::new();
 
-    println!("Tree Expr (before reduction): {}", tree_expr);
+        // Monkeys waited by an operation, checked once every monkey is known
+        let mut references = Vec::<(usize, usize, &str)>::new();
 
-    let tree_expr = reduce_tree(tree_expr);
+        for (index, line) in input.lines().enumerate() {
+            let (monkey, expr) = line
+                .split_once(": ")
+                .ok_or_else(|| ParseError::MissingSeparator(line.to_string()))
+                .at(index + 1, 1)?;
 
-    println!("Tree Expr (after reduction): {}", tree_expr);
+            let expression = match expr.split_whitespace().collect::<Vec<&str>>()[..] {
+                [number] => number
+                    .parse()
+                    .map(Expression::Number)
+                    .map_err(|_| ParseError::InvalidNumber(number.to_string()))
+                    .at(index + 1, column(line, number))?,
+                [left, op, right] => {
+                    let op = Operator::from_str(op).at(index + 1, column(line, op))?;
 
-    let (value, path) = match tree_expr {
-        TreeExpr::Root(l, r) => match (*l, *r) {
-            (expr, TreeExpr::Number(value)) | (TreeExpr::Number(value), expr) => (value, expr),
-            (_, _) => panic!("Error: Root malformed"),
-        },
-        _ => panic!("Root is malformed"),
-    };
+                    references.push((index + 1, column(line, left), left));
+                    references.push((index + 1, column(line, right), right));
 
-    let result = compute_variable(path, value);
-
-    println!("The variable should be: {}", result);
+                    Expression::Operation(left.to_string(), op, right.to_string())
+                }
+                _ => {
+                    let error = ParseError::InvalidExpression(expr.to_string());
+                    return Err(Located::new(index + 1, column(line, expr), error).into());
+                }
+            };
 
-    Ok(())
-}
-fn eval_root(monkeys: &HashMap<String, Expression>) -> i64 {
-    let root = monkeys.get(&String::from("root")).unwrap();
+            monkeys.insert(monkey.to_string(), expression);
+        }
 
-    match root {
-        Expression::Operation(l, _, r) => {
-            let lmonkey = eval(monkeys.get(l).unwrap(), monkeys);
-            let rmonkey = eval(monkeys.get(r).unwrap(), monkeys);
+        for (line, col, monkey) in references {
+            if !monkeys.contains_key(monkey) {
+                let error = ParseError::UnknownMonkey(monkey.to_string());
+                return Err(Located::new(line, col, error).into());
+            }
+        }
 
-            rmonkey - lmonkey
+        if !monkeys.contains_key("root") {
+            return Err(SolveError::parse(ParseError::MissingMonkey("root")));
         }
-        _ => panic!("Root should be an Operation"),
-    }
-}
+        if !monkeys.contains_key("humn") {
+            return Err(SolveError::parse(ParseError::MissingMonkey("humn")));
+        }
+        let (lm, rm) = match monkeys.get("root") {
+            Some(Expression::Operation(lm, _, rm)) => (lm, rm),
+            _ => {
+                return Err(SolveError::NoSolution(String::from(
+                    "root doesn't compare two monkeys",
+                )))
+            }
+        };
 
-fn eval_monkey(monkey: String, monkeys: &HashMap<String, Expression>) -> i64 {
-    eval(monkeys.get(&monkey).unwrap(), monkeys)
-}
+        let mut visiting = HashSet::from(["root"]);
+        let left = reduce_tree(build_expr_tree(lm, &monkeys, &mut visiting)?)?;
+        let right = reduce_tree(build_expr_tree(rm, &monkeys, &mut visiting)?)?;
 
-fn eval(expr: &Expression, monkeys: &HashMap<String, Expression>) -> i64 {
-    match expr {
-        Expression::Number(value) => *value,
-        Expression::Operation(lmonkey, op, rmonkey) => {
-            let left = eval(monkeys.get(lmonkey).unwrap(), monkeys);
-            let right = eval(monkeys.get(rmonkey).unwrap(), monkeys);
-            match op {
-                Operator::Plus => left + right,
-                Operator::Minus => left - right,
-                Operator::Mul => left * right,
-                Operator::Div => left / right,
+        let (value, path) = match (left, right) {
+            (expr, TreeExpr::Number(value)) | (TreeExpr::Number(value), expr) => (value, expr),
+            (_, _) => {
+                return Err(SolveError::NoSolution(String::from(
+                    "both sides of root depend on humn",
+                )))
             }
-        }
+        };
+
+        let result = compute_variable(path, value)?;
+
+        Ok(result.into())
     }
 }
 
@@ -101,17 +96,16 @@ enum Operator {
     Div,
 }
 
-impl From<&str> for Operator {
-    fn from(value: &str) -> Self {
-        match value.chars().nth(0) {
-            Some(value) => match value {
-                '+' => Operator::Plus,
-                '-' => Operator::Minus,
-                '*' => Operator::Mul,
-                '/' => Operator::Div,
-                _ => panic!("Character {} invalid", value),
-            },
-            None => panic!("Number invalid"),
+impl FromStr for Operator {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "+" => Ok(Operator::Plus),
+            "-" => Ok(Operator::Minus),
+            "*" => Ok(Operator::Mul),
+            "/" => Ok(Operator::Div),
+            _ => Err(ParseError::InvalidOperator(value.to_string())),
         }
     }
 }
@@ -138,13 +132,11 @@ enum TreeExpr {
     Number(i64),
     Variable(),
     Operation(Box<TreeExpr>, Operator, Box<TreeExpr>),
-    Root(Box<TreeExpr>, Box<TreeExpr>),
 }
 
 impl Display for TreeExpr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            TreeExpr::Root(l, r) => f.write_fmt(format_args!("({}) == ({})", l, r)),
             TreeExpr::Number(value) => f.write_fmt(format_args!("{}", value)),
             TreeExpr::Variable() => f.write_str("x"),
             TreeExpr::Operation(l, op, r) => f.write_fmt(format_args!("({} {} {})", l, op, r)),
@@ -152,109 +144,206 @@ impl Display for TreeExpr {
     }
 }
 
-fn build_expr_tree(monkey: String, monkeys: &HashMap<String, Expression>) -> TreeExpr {
-    let m = monkeys.get(&monkey).unwrap();
+/// Expression of the number yelled by a monkey, `visiting` holding the monkeys waiting for it
+fn build_expr_tree<'a>(
+    monkey: &'a str,
+    monkeys: &'a HashMap<String, Expression>,
+    visiting: &mut HashSet<&'a str>,
+) -> Result<TreeExpr, SolveError> {
+    if monkey == "humn" {
+        return Ok(TreeExpr::Variable());
+    }
 
-    if *monkey == String::from("root") {
-        if let Expression::Operation(lm, _, rm) = m {
-            let lexpr = build_expr_tree(lm.clone(), monkeys);
-            let rexpr = build_expr_tree(rm.clone(), monkeys);
+    let m = monkeys
+        .get(monkey)
+        .ok_or_else(|| SolveError::parse(ParseError::UnknownMonkey(monkey.to_string())))?;
 
-            TreeExpr::Root(Box::new(lexpr), Box::new(rexpr))
-        } else {
-            panic!("Root is malformed");
-        }
-    } else if *monkey == String::from("humn") {
-        TreeExpr::Variable()
-    } else {
-        match m {
-            Expression::Number(value) => TreeExpr::Number(*value),
-            Expression::Operation(lm, op, rm) => {
-                let lexpr = build_expr_tree(lm.clone(), monkeys);
-                let rexpr = build_expr_tree(rm.clone(), monkeys);
-
-                TreeExpr::Operation(Box::new(lexpr), *op, Box::new(rexpr))
-            }
-        }
+    if !visiting.insert(monkey) {
+        return Err(SolveError::parse(ParseError::CyclicMonkey(
+            monkey.to_string(),
+        )));
     }
+
+    let expr = match m {
+        Expression::Number(value) => TreeExpr::Number(*value),
+        Expression::Operation(lm, op, rm) => {
+            let lexpr = build_expr_tree(lm, monkeys, visiting)?;
+            let rexpr = build_expr_tree(rm, monkeys, visiting)?;
+
+            TreeExpr::Operation(Box::new(lexpr), *op, Box::new(rexpr))
+        }
+    };
+
+    visiting.remove(monkey);
+    Ok(expr)
 }
 
-fn reduce_tree(expr: TreeExpr) -> TreeExpr {
+/// Compute the operations that don't depend on humn
+fn reduce_tree(expr: TreeExpr) -> Result<TreeExpr, SolveError> {
     match expr {
         TreeExpr::Operation(l, op, r) => {
-            let l = reduce_tree(*l);
-            let r = reduce_tree(*r);
+            let l = reduce_tree(*l)?;
+            let r = reduce_tree(*r)?;
 
             match (l, r) {
-                (TreeExpr::Number(lvalue), TreeExpr::Number(rvalue)) => match op {
-                    Operator::Plus => TreeExpr::Number(lvalue + rvalue),
-                    Operator::Minus => TreeExpr::Number(lvalue - rvalue),
-                    Operator::Mul => TreeExpr::Number(lvalue * rvalue),
-                    Operator::Div => TreeExpr::Number(lvalue / rvalue),
-                },
-                (l, r) => TreeExpr::Operation(Box::new(l), op, Box::new(r)),
+                (TreeExpr::Number(lvalue), TreeExpr::Number(rvalue)) => {
+                    let value = match op {
+                        Operator::Plus => lvalue.checked_add(rvalue),
+                        Operator::Minus => lvalue.checked_sub(rvalue),
+                        Operator::Mul => lvalue.checked_mul(rvalue),
+                        Operator::Div => lvalue.checked_div(rvalue),
+                    };
+
+                    value
+                        .map(TreeExpr::Number)
+                        .ok_or_else(|| impossible(lvalue, op, rvalue))
+                }
+                (l, r) => Ok(TreeExpr::Operation(Box::new(l), op, Box::new(r))),
             }
         }
-        TreeExpr::Root(l, r) => {
-            let l = reduce_tree(*l);
-            let r = reduce_tree(*r);
+        expr => Ok(expr),
+    }
+}
+
+/// Value of humn for which `expr` is `value`, once the tree is reduced
+fn compute_variable(expr: TreeExpr, value: i64) -> Result<i64, SolveError> {
+    let (l, op, r) = match expr {
+        TreeExpr::Operation(l, op, r) => (*l, op, *r),
+        TreeExpr::Number(_) | TreeExpr::Variable() => return Ok(value),
+    };
 
-            TreeExpr::Root(Box::new(l), Box::new(r))
+    // The side without humn has been reduced to a number, humn is on the other side
+    let (value, expr) = match (l, op, r) {
+        (TreeExpr::Number(v), Operator::Plus, expr)
+        | (expr, Operator::Plus, TreeExpr::Number(v)) => (
+            value
+                .checked_sub(v)
+                .ok_or_else(|| impossible(value, Operator::Minus, v))?,
+            expr,
+        ),
+        (TreeExpr::Number(v), Operator::Minus, expr) => (
+            v.checked_sub(value)
+                .ok_or_else(|| impossible(v, Operator::Minus, value))?,
+            expr,
+        ),
+        (expr, Operator::Minus, TreeExpr::Number(v)) => (
+            value
+                .checked_add(v)
+                .ok_or_else(|| impossible(value, Operator::Plus, v))?,
+            expr,
+        ),
+        (TreeExpr::Number(v), Operator::Mul, expr) | (expr, Operator::Mul, TreeExpr::Number(v)) => {
+            (exact_div(value, v)?, expr)
         }
-        expr => expr,
-    }
+        (TreeExpr::Number(v), Operator::Div, expr) => (exact_div(v, value)?, expr),
+        (expr, Operator::Div, TreeExpr::Number(v)) => (
+            value
+                .checked_mul(v)
+                .ok_or_else(|| impossible(value, Operator::Mul, v))?,
+            expr,
+        ),
+        (_, _, _) => {
+            return Err(SolveError::NoSolution(String::from(
+                "both sides of an operation depend on humn",
+            )))
+        }
+    };
+
+    compute_variable(expr, value)
 }
 
-fn compute_variable(expr: TreeExpr, mut value: i64) -> i64 {
-    match expr {
-        TreeExpr::Operation(l, op, r) => match op {
-            Operator::Plus => match (*l, *r) {
-                (TreeExpr::Number(v), expr) | (expr, TreeExpr::Number(v)) => {
-                    value -= v;
-                    compute_variable(expr, value)
-                }
-                (_, _) => panic!(),
-            },
-            Operator::Minus => match (*l, *r) {
-                (TreeExpr::Number(v), expr) => {
-                    value = -(value - v);
-                    compute_variable(expr, value)
-                }
-                (expr, TreeExpr::Number(v)) => {
-                    value += v;
-                    compute_variable(expr, value)
-                }
-                (_, _) => panic!(),
-            },
-            Operator::Mul => match (*l, *r) {
-                (TreeExpr::Number(v), expr) | (expr, TreeExpr::Number(v)) => {
-                    value /= v;
-                    compute_variable(expr, value)
-                }
-                (_, _) => panic!(),
-            },
-            Operator::Div => match (*l, *r) {
-                (TreeExpr::Number(v), expr) => {
-                    value /= v;
-                    compute_variable(expr, value)
-                }
-                (expr, TreeExpr::Number(v)) => {
-                    value *= v;
-                    compute_variable(expr, value)
-                }
-                (_, _) => panic!(),
-            },
-        },
-        TreeExpr::Number(_) => value,
-        TreeExpr::Variable() => value,
-        _ => panic!("Tree Expr shouldn't be reached"),
+/// `left / right`, when it is an integer
+fn exact_div(left: i64, right: i64) -> Result<i64, SolveError> {
+    match left.checked_rem(right) {
+        Some(0) => left
+            .checked_div(right)
+            .ok_or_else(|| impossible(left, Operator::Div, right)),
+        _ => Err(impossible(left, Operator::Div, right)),
     }
 }
 
+fn impossible(left: i64, op: Operator, right: i64) -> SolveError {
+    SolveError::NoSolution(format!("can't compute {} {} {}", left, op, right))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(301i64));
+    }
+
+    #[test]
+    fn missing_humn() {
+        let input = "root: aaaa + bbbb\naaaa: 5\nbbbb: 3\n";
+
+        assert_eq!(
+            PartTwo::solve(input).unwrap_err().to_string(),
+            "Invalid input: expected a monkey named 'humn', found none"
+        );
+    }
+
+    #[test]
+    fn invalid_expression() {
+        let input = "root: aaaa + \nhumn: 5\n";
+
+        assert_eq!(
+            PartTwo::solve(input).unwrap_err().to_string(),
+            "Invalid input: line 1, column 7: expected a number or 'left <operator> right', found 'aaaa + '"
+        );
+    }
+
+    #[test]
+    fn cyclic_monkeys() {
+        let input = "root: aaaa + bbbb\naaaa: humn * cccc\nbbbb: 3\ncccc: aaaa - bbbb\nhumn: 1\n";
+
+        assert_eq!(
+            PartTwo::solve(input).unwrap_err().to_string(),
+            "Invalid input: monkey 'aaaa' waits for itself"
+        );
+    }
+
+    #[test]
+    fn humn_on_both_sides() {
+        let input = "root: aaaa + bbbb\naaaa: humn * humn\nbbbb: 3\nhumn: 1\n";
+
+        assert_eq!(
+            PartTwo::solve(input).unwrap_err().to_string(),
+            "No solution: both sides of an operation depend on humn"
+        );
+    }
+
+    #[test]
+    fn no_integer_solution() {
+        let input = "root: aaaa = bbbb\naaaa: humn * cccc\nbbbb: 7\ncccc: 2\nhumn: 1\n";
+
+        assert_eq!(
+            PartTwo::solve(input).unwrap_err().to_string(),
+            "Invalid input: line 1, column 12: expected '+', '-', '*' or '/', found '='"
+        );
+        assert_eq!(
+            PartTwo::solve(&input.replace('=', "+"))
+                .unwrap_err()
+                .to_string(),
+            "No solution: can't compute 7 / 2"
+        );
+    }
+
+    #[test]
+    fn division_by_zero() {
+        let input =
+            "root: aaaa + bbbb\naaaa: humn - bbbb\nbbbb: cccc / dddd\ncccc: 3\ndddd: 0\nhumn: 1\n";
+
+        assert_eq!(
+            PartTwo::solve(input).unwrap_err().to_string(),
+            "No solution: can't compute 3 / 0"
+        );
+    }
+
     #[test]
     fn addition_left() {
         let tree = TreeExpr::Operation(
@@ -263,7 +352,7 @@ mod tests {
             Box::new(TreeExpr::Number(15)),
         );
 
-        let result = compute_variable(tree, 150);
+        let result = compute_variable(tree, 150).unwrap();
 
         assert_eq!(result, 135);
     }
@@ -276,7 +365,7 @@ mod tests {
             Box::new(TreeExpr::Variable()),
         );
 
-        let result = compute_variable(tree, 150);
+        let result = compute_variable(tree, 150).unwrap();
 
         assert_eq!(result, 130);
     }
@@ -293,7 +382,7 @@ mod tests {
             Box::new(TreeExpr::Number(10)),
         );
 
-        let result = compute_variable(tree, 150);
+        let result = compute_variable(tree, 150).unwrap();
 
         assert_eq!(result, 130);
     }
@@ -306,7 +395,7 @@ mod tests {
             Box::new(TreeExpr::Number(15)),
         );
 
-        let result = compute_variable(tree, 150);
+        let result = compute_variable(tree, 150).unwrap();
 
         assert_eq!(result, 10);
     }
@@ -319,7 +408,7 @@ mod tests {
             Box::new(TreeExpr::Variable()),
         );
 
-        let result = compute_variable(tree, 150);
+        let result = compute_variable(tree, 150).unwrap();
 
         assert_eq!(result, 10);
     }
@@ -336,7 +425,7 @@ mod tests {
             Box::new(TreeExpr::Number(10)),
         );
 
-        let result = compute_variable(tree, 200);
+        let result = compute_variable(tree, 200).unwrap();
 
         assert_eq!(result, 2);
     }
@@ -349,7 +438,7 @@ mod tests {
             Box::new(TreeExpr::Number(15)),
         );
 
-        let result = compute_variable(tree, 150);
+        let result = compute_variable(tree, 150).unwrap();
 
         assert_eq!(result, 165);
     }
@@ -362,7 +451,7 @@ mod tests {
             Box::new(TreeExpr::Variable()),
         );
 
-        let result = compute_variable(tree, 150);
+        let result = compute_variable(tree, 150).unwrap();
 
         assert_eq!(result, -135);
     }
@@ -379,7 +468,7 @@ mod tests {
             Box::new(TreeExpr::Number(10)),
         );
 
-        let result = compute_variable(tree, 200);
+        let result = compute_variable(tree, 200).unwrap();
 
         assert_eq!(result, -200);
     }
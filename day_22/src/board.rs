@@ -0,0 +1,263 @@
+use aoc_core::{column, Locate, Located};
+use aoc_grid::{Grid, Pos, DOWN, LEFT, RIGHT, UP};
+
+use crate::error::ParseError;
+
+/// Facings in the order of their value in the password
+const FACINGS: [Pos; 4] = [RIGHT, DOWN, LEFT, UP];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Open,
+    Wall,
+}
+
+/// A step of the path to follow
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    /// Move forward this number of tiles, or until a wall is in the way
+    Forward(usize),
+    /// Turn 90 degrees counterclockwise
+    Left,
+    /// Turn 90 degrees clockwise
+    Right,
+}
+
+/// How to get back on the board after walking off one of its edges
+pub trait Wrap {
+    /// Tile and facing reached when leaving the board from `pos` while facing `dir`
+    fn wrap(&self, pos: Pos, dir: Pos) -> (Pos, Pos);
+}
+
+/// The strange map of the monkeys, tiles outside of the map being absent
+#[derive(Debug, Clone)]
+pub struct Board {
+    tiles: Grid<Tile>,
+}
+
+impl Board {
+    pub fn tiles(&self) -> &Grid<Tile> {
+        &self.tiles
+    }
+
+    /// Leftmost open tile of the top row, where the path starts
+    pub fn start(&self) -> Option<Pos> {
+        self.tiles
+            .row(0)
+            .find(|(_, tile)| **tile == Tile::Open)
+            .map(|(pos, _)| pos)
+    }
+
+    /// Tile and facing after a single step from `pos` while facing `dir`, whatever is there
+    pub fn step(&self, pos: Pos, dir: Pos, wrap: &impl Wrap) -> (Pos, Pos) {
+        let next = pos + dir;
+
+        if self.tiles.contains(next) {
+            (next, dir)
+        } else {
+            wrap.wrap(pos, dir)
+        }
+    }
+
+    /// Follow `path` from the start facing right, returning the final tile and facing.
+    ///
+    /// A move going back to where it started only does the steps left after its last whole
+    /// lap, which needs a wrap leading back to every tile it leaves, like the ones of the
+    /// puzzle.
+    pub fn walk(&self, path: &[Instruction], wrap: &impl Wrap) -> Option<(Pos, Pos)> {
+        let mut pos = self.start()?;
+        let mut dir = RIGHT;
+
+        for instruction in path {
+            match instruction {
+                Instruction::Left => dir = Pos::new(dir.y, -dir.x),
+                Instruction::Right => dir = Pos::new(-dir.y, dir.x),
+                Instruction::Forward(tiles) => {
+                    let start = (pos, dir);
+                    let mut left = *tiles;
+
+                    while left > 0 {
+                        let (next, facing) = self.step(pos, dir, wrap);
+
+                        // Nothing changes anymore once the walk is stuck in place
+                        if self.tiles[next] == Tile::Wall || (next, facing) == (pos, dir) {
+                            break;
+                        }
+                        (pos, dir) = (next, facing);
+                        left -= 1;
+
+                        if (pos, dir) == start {
+                            left %= tiles - left;
+                        }
+                    }
+                }
+            }
+        }
+
+        Some((pos, dir))
+    }
+}
+
+/// Password made of 1000 times the row, 4 times the column (both starting at 1) and the facing
+pub fn password(pos: Pos, dir: Pos) -> usize {
+    let facing = FACINGS.iter().position(|&facing| facing == dir).unwrap();
+
+    1000 * (pos.y as usize + 1) + 4 * (pos.x as usize + 1) + facing
+}
+
+/// Read the notes of the monkeys: the board, an empty line and the path
+pub fn parse(input: &str) -> Result<(Board, Vec<Instruction>), Located<ParseError>> {
+    let missing_path = || Located::new(input.lines().count() + 1, 1, ParseError::MissingPath);
+
+    let (map, path) = input.split_once("\n\n").ok_or_else(missing_path)?;
+    let path = path.trim_end();
+
+    if path.is_empty() {
+        return Err(missing_path());
+    }
+
+    let tiles = Grid::parse_sparse(map, |_, c| match c {
+        '.' => Ok(Some(Tile::Open)),
+        '#' => Ok(Some(Tile::Wall)),
+        ' ' => Ok(None),
+        _ => Err(ParseError::InvalidTile(c)),
+    })
+    .map_err(|e| e.map(ParseError::from))?;
+
+    let path = parse_path(path, map.lines().count() + 2)?;
+
+    Ok((Board { tiles }, path))
+}
+
+/// Read a path like "10R5L5", found on the line `line`
+fn parse_path(s: &str, line: usize) -> Result<Vec<Instruction>, Located<ParseError>> {
+    let mut path = Vec::new();
+    let mut rest = s;
+
+    while let Some(c) = rest.chars().next() {
+        let instruction = match c {
+            'L' => Instruction::Left,
+            'R' => Instruction::Right,
+            '0'..='9' => {
+                let end = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                let tiles = &rest[..end];
+
+                let tiles = tiles
+                    .parse()
+                    .map_err(|_| ParseError::InvalidSteps(tiles.to_string()))
+                    .at(line, column(s, rest))?;
+                rest = &rest[end..];
+                path.push(Instruction::Forward(tiles));
+                continue;
+            }
+            _ => return Err(ParseError::InvalidTurn(c)).at(line, column(s, rest)),
+        };
+
+        path.push(instruction);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Walls don't stop anyone outside of the board
+    struct Nowhere;
+
+    impl Wrap for Nowhere {
+        fn wrap(&self, pos: Pos, dir: Pos) -> (Pos, Pos) {
+            (pos, dir)
+        }
+    }
+
+    #[test]
+    fn parse_example() {
+        let (board, path) = parse(include_str!("../simple_input.txt")).unwrap();
+
+        assert_eq!(board.tiles().len(), 96);
+        assert_eq!(board.tiles()[Pos::new(11, 0)], Tile::Wall);
+        assert_eq!(board.tiles().get(Pos::new(0, 0)), None);
+        assert_eq!(board.start(), Some(Pos::new(8, 0)));
+        assert_eq!(
+            path[..4],
+            [
+                Instruction::Forward(10),
+                Instruction::Right,
+                Instruction::Forward(5),
+                Instruction::Left
+            ]
+        );
+        assert_eq!(path.len(), 13);
+    }
+
+    #[test]
+    fn invalid_tile() {
+        assert_eq!(
+            parse("..\n.x\n\n1").unwrap_err().to_string(),
+            "line 2, column 2: expected '.', '#' or ' ', found 'x'"
+        );
+    }
+
+    #[test]
+    fn invalid_turn() {
+        assert_eq!(
+            parse("..\n..\n\n1R2U3").unwrap_err().to_string(),
+            "line 4, column 4: expected a number of tiles, 'L' or 'R', found 'U'"
+        );
+    }
+
+    #[test]
+    fn missing_path() {
+        assert_eq!(
+            parse("..\n..\n").unwrap_err().to_string(),
+            "line 3, column 1: expected a path after the map, found nothing"
+        );
+    }
+
+    #[test]
+    fn walls_stop_the_walk() {
+        let (board, _) = parse("...#\n\n1").unwrap();
+
+        assert_eq!(
+            board.walk(&[Instruction::Forward(5)], &Nowhere),
+            Some((Pos::new(2, 0), RIGHT))
+        );
+        assert_eq!(
+            board.walk(&[Instruction::Left, Instruction::Left], &Nowhere),
+            Some((Pos::new(0, 0), LEFT))
+        );
+    }
+
+    /// Leaving a row of 4 tiles on one side leads back on the other side
+    struct Around;
+
+    impl Wrap for Around {
+        fn wrap(&self, pos: Pos, dir: Pos) -> (Pos, Pos) {
+            (Pos::new((pos.x + dir.x).rem_euclid(4), pos.y), dir)
+        }
+    }
+
+    #[test]
+    fn long_moves() {
+        let (board, _) = parse("....\n\n1").unwrap();
+        let far = Instruction::Forward(999_999_999_999);
+
+        // 999999999999 steps are 249999999999 laps and 3 steps
+        assert_eq!(board.walk(&[far], &Around), Some((Pos::new(3, 0), RIGHT)));
+        assert_eq!(
+            board.walk(&[Instruction::Forward(8)], &Around),
+            Some((Pos::new(0, 0), RIGHT))
+        );
+        assert_eq!(board.walk(&[far], &Nowhere), Some((Pos::new(3, 0), RIGHT)));
+    }
+
+    #[test]
+    fn password_of_the_example() {
+        assert_eq!(password(Pos::new(7, 5), RIGHT), 6032);
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::{HashMap, VecDeque};
+
+use aoc_grid::{Pos, DIRECTIONS4, DOWN, LEFT, RIGHT, UP};
+use aoc_point::Point3;
+
+use crate::board::{Board, Wrap};
+
+type Vector = Point3<i32>;
+
+/// Where a face of the net ends up once the cube is folded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Face {
+    /// Direction the face looks at, from the center of the cube
+    normal: Vector,
+    /// Direction of the right of the net on the face
+    right: Vector,
+    /// Direction of the bottom of the net on the face
+    down: Vector,
+}
+
+impl Face {
+    /// Direction of the edge towards `dir` in the net, from the center of the face
+    fn edge(&self, dir: Pos) -> Vector {
+        match dir {
+            RIGHT => self.right,
+            DOWN => self.down,
+            LEFT => -self.right,
+            UP => -self.down,
+            _ => unreachable!("{} is not a direction", dir),
+        }
+    }
+
+    /// Face reached by rolling the cube over the edge towards `dir` in the net
+    fn roll(&self, dir: Pos) -> Face {
+        let normal = self.edge(dir);
+        let (mut right, mut down) = (self.right, self.down);
+
+        // Walking on across the edge leads to the opposite of the face left behind
+        match dir {
+            RIGHT => right = -self.normal,
+            LEFT => right = self.normal,
+            DOWN => down = -self.normal,
+            UP => down = self.normal,
+            _ => unreachable!("{} is not a direction", dir),
+        }
+
+        Face {
+            normal,
+            right,
+            down,
+        }
+    }
+}
+
+/// The board folded into a cube, walking off a face leading onto the next one.
+///
+/// The faces are squares of `size` tiles, found at `size` times their position in the net.
+#[derive(Debug, Clone)]
+pub struct Cube {
+    size: isize,
+    faces: HashMap<Pos, Face>,
+}
+
+impl Cube {
+    /// Fold the board, whatever the shape of its net, `None` if it isn't the net of a cube
+    pub fn fold(board: &Board) -> Option<Self> {
+        let tiles = board.tiles().len();
+        let size = (tiles as f64 / 6.0).sqrt().round() as isize;
+
+        if size == 0 || 6 * size * size != tiles as isize {
+            return None;
+        }
+
+        let mut net: Vec<Pos> = board
+            .tiles()
+            .iter()
+            .map(|(pos, _)| Pos::new(pos.x.div_euclid(size), pos.y.div_euclid(size)))
+            .collect();
+        net.sort();
+        net.dedup();
+
+        if net.len() != 6 {
+            return None;
+        }
+
+        // Roll the cube over the net from its first face
+        let first = Face {
+            normal: Vector::new(0, 0, 1),
+            right: Vector::new(1, 0, 0),
+            down: Vector::new(0, 1, 0),
+        };
+        let mut faces = HashMap::from([(net[0], first)]);
+        let mut queue = VecDeque::from([net[0]]);
+
+        while let Some(pos) = queue.pop_front() {
+            let face = faces[&pos];
+
+            for dir in DIRECTIONS4 {
+                let next = pos + dir;
+
+                if net.contains(&next) && !faces.contains_key(&next) {
+                    faces.insert(next, face.roll(dir));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let mut normals: Vec<Vector> = faces.values().map(|face| face.normal).collect();
+        normals.sort();
+        normals.dedup();
+
+        if normals.len() != 6 {
+            return None;
+        }
+
+        Some(Cube { size, faces })
+    }
+}
+
+impl Wrap for Cube {
+    fn wrap(&self, pos: Pos, dir: Pos) -> (Pos, Pos) {
+        let size = self.size;
+        let from = Pos::new(pos.x.div_euclid(size), pos.y.div_euclid(size));
+        let face = self.faces[&from];
+
+        // The next face looks where the edge is, and its edge glued back looks at the first face
+        let normal = face.edge(dir);
+        let (to, next) = self
+            .faces
+            .iter()
+            .find(|(_, next)| next.normal == normal)
+            .unwrap();
+        let edge = DIRECTIONS4
+            .into_iter()
+            .find(|&edge| next.edge(edge) == face.normal)
+            .unwrap();
+
+        // Going clockwise around each face, both sides of an edge are walked in opposite ways
+        let local = Pos::new(pos.x.rem_euclid(size), pos.y.rem_euclid(size));
+        let offset = size - 1 - clockwise(local, dir, size);
+        let local = on_edge(offset, edge, size);
+
+        (
+            Pos::new(to.x * size + local.x, to.y * size + local.y),
+            -edge,
+        )
+    }
+}
+
+/// Distance of `local` from the start of the edge of its face towards `dir`, going clockwise
+fn clockwise(local: Pos, dir: Pos, size: isize) -> isize {
+    match dir {
+        UP => local.x,
+        RIGHT => local.y,
+        DOWN => size - 1 - local.x,
+        LEFT => size - 1 - local.y,
+        _ => unreachable!("{} is not a direction", dir),
+    }
+}
+
+/// Position on the edge of a face towards `dir`, `offset` tiles from its start going clockwise
+fn on_edge(offset: isize, dir: Pos, size: isize) -> Pos {
+    match dir {
+        UP => Pos::new(offset, 0),
+        RIGHT => Pos::new(size - 1, offset),
+        DOWN => Pos::new(size - 1 - offset, size - 1),
+        LEFT => Pos::new(0, size - 1 - offset),
+        _ => unreachable!("{} is not a direction", dir),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::parse;
+
+    /// The 11 nets of a cube, one character per face
+    const NETS: [&str; 11] = [
+        "#...\n####\n#...",
+        "#...\n####\n.#..",
+        "#...\n####\n..#.",
+        "#...\n####\n...#",
+        ".#..\n####\n.#..",
+        ".#..\n####\n..#.",
+        "##..\n.###\n.#..",
+        "##..\n.###\n..#.",
+        "##..\n.###\n...#",
+        "##..\n.##.\n..##",
+        "###..\n..###",
+    ];
+
+    /// Layout of the net in every real input
+    const REAL_NET: &str = ".##\n.#.\n##.\n#..";
+
+    /// Board without walls drawing `net` with faces of `size` tiles
+    fn board(net: &str, size: usize) -> Board {
+        let mut map = String::new();
+
+        for row in net.lines() {
+            let line: String = row
+                .chars()
+                .flat_map(|c| std::iter::repeat_n(if c == '#' { '.' } else { ' ' }, size))
+                .collect();
+
+            for _ in 0..size {
+                map.push_str(&line);
+                map.push('\n');
+            }
+        }
+        map.push_str("\n1\n");
+
+        parse(&map).unwrap().0
+    }
+
+    /// Walking straight ahead around a cube leads back to where it started
+    fn assert_loops(net: &str, size: usize) {
+        let board = board(net, size);
+        let cube = Cube::fold(&board).unwrap();
+
+        for (start, _) in board.tiles().iter() {
+            for dir in DIRECTIONS4 {
+                let (mut pos, mut facing) = (start, dir);
+
+                for _ in 0..4 * size {
+                    (pos, facing) = board.step(pos, facing, &cube);
+                    assert!(board.tiles().contains(pos));
+                }
+
+                assert_eq!((pos, facing), (start, dir), "{:?} in\n{}", dir, net);
+            }
+        }
+    }
+
+    #[test]
+    fn example_edges() {
+        let (board, _) = parse(include_str!("../simple_input.txt")).unwrap();
+        let cube = Cube::fold(&board).unwrap();
+
+        // From A to B and from C to D in the puzzle
+        assert_eq!(cube.wrap(Pos::new(11, 5), RIGHT), (Pos::new(14, 8), DOWN));
+        assert_eq!(cube.wrap(Pos::new(10, 11), DOWN), (Pos::new(1, 7), UP));
+    }
+
+    #[test]
+    fn real_edges() {
+        let board = board(REAL_NET, 50);
+        let cube = Cube::fold(&board).unwrap();
+
+        assert_eq!(cube.wrap(Pos::new(57, 0), UP), (Pos::new(0, 157), RIGHT));
+        assert_eq!(cube.wrap(Pos::new(0, 157), LEFT), (Pos::new(57, 0), DOWN));
+        assert_eq!(
+            cube.wrap(Pos::new(149, 3), RIGHT),
+            (Pos::new(99, 146), LEFT)
+        );
+        assert_eq!(cube.wrap(Pos::new(99, 60), RIGHT), (Pos::new(110, 49), UP));
+    }
+
+    #[test]
+    fn example_net_loops() {
+        assert_loops("..#.\n###.\n..##", 4);
+    }
+
+    #[test]
+    fn real_net_loops() {
+        assert_loops(REAL_NET, 5);
+    }
+
+    #[test]
+    fn every_net_loops() {
+        for net in NETS {
+            assert_loops(net, 3);
+        }
+    }
+
+    #[test]
+    fn not_a_cube() {
+        assert!(Cube::fold(&board("####\n.##.", 2)).is_none());
+        assert!(Cube::fold(&board("######", 2)).is_none());
+        assert!(Cube::fold(&board("##", 3)).is_none());
+    }
+}
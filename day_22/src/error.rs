@@ -0,0 +1,42 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use aoc_grid::error::GridError;
+
+/// Errors found while reading the map and the path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A tile that is neither open (.), a wall (#) nor outside of the map ( )
+    InvalidTile(char),
+    /// A number of tiles to move too large to be walked
+    InvalidSteps(String),
+    /// A turn other than left (L) or right (R)
+    InvalidTurn(char),
+    /// The notes have no path after the map
+    MissingPath,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidTile(c) => write!(f, "expected '.', '#' or ' ', found '{}'", c),
+            ParseError::InvalidSteps(s) => write!(f, "expected a number of tiles, found '{}'", s),
+            ParseError::InvalidTurn(c) => {
+                write!(f, "expected a number of tiles, 'L' or 'R', found '{}'", c)
+            }
+            ParseError::MissingPath => write!(f, "expected a path after the map, found nothing"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<GridError<ParseError>> for ParseError {
+    fn from(e: GridError<ParseError>) -> Self {
+        match e {
+            GridError::Cell(e) => e,
+            // Only dense grids check the width and emptiness of the map
+            e => unreachable!("{}", e),
+        }
+    }
+}
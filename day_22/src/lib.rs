@@ -0,0 +1,5 @@
+pub mod board;
+pub mod cube;
+pub mod error;
+pub mod part_one;
+pub mod part_two;
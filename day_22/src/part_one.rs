@@ -0,0 +1,68 @@
+use aoc_core::{Answer, Solution, SolveError};
+use aoc_grid::Pos;
+
+use crate::board::{parse, password, Board, Wrap};
+
+pub struct PartOne;
+
+/// Walking off the board leads back on the other side of its row or column
+struct Flat<'a>(&'a Board);
+
+impl Wrap for Flat<'_> {
+    fn wrap(&self, pos: Pos, dir: Pos) -> (Pos, Pos) {
+        let opposite = self.0.tiles().ray(pos, -dir).last();
+
+        (opposite.map_or(pos, |(pos, _)| pos), dir)
+    }
+}
+
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let (board, path) = parse(input)?;
+
+        let (pos, dir) = board
+            .walk(&path, &Flat(&board))
+            .ok_or_else(|| SolveError::NoSolution(String::from("no open tile to start from")))?;
+
+        Ok(password(pos, dir).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(6032));
+    }
+
+    #[test]
+    fn wraps_around_rows_and_columns() {
+        let (board, _) = parse("  ...\n  ..\n....\n\n1").unwrap();
+        let flat = Flat(&board);
+
+        assert_eq!(
+            flat.wrap(Pos::new(2, 0), aoc_grid::LEFT),
+            (Pos::new(4, 0), aoc_grid::LEFT)
+        );
+        assert_eq!(
+            flat.wrap(Pos::new(0, 2), aoc_grid::UP),
+            (Pos::new(0, 2), aoc_grid::UP)
+        );
+        assert_eq!(
+            flat.wrap(Pos::new(3, 2), aoc_grid::DOWN),
+            (Pos::new(3, 0), aoc_grid::DOWN)
+        );
+    }
+
+    #[test]
+    fn no_open_tile() {
+        assert_eq!(
+            PartOne::solve("##\n\n1").unwrap_err().to_string(),
+            "No solution: no open tile to start from"
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use aoc_core::{Answer, Solution, SolveError};
+
+use crate::board::{parse, password};
+use crate::cube::Cube;
+
+pub struct PartTwo;
+
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let (board, path) = parse(input)?;
+
+        let cube = Cube::fold(&board).ok_or_else(|| {
+            SolveError::NoSolution(String::from("the map doesn't fold into a cube"))
+        })?;
+
+        let (pos, dir) = board
+            .walk(&path, &cube)
+            .ok_or_else(|| SolveError::NoSolution(String::from("no open tile to start from")))?;
+
+        Ok(password(pos, dir).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(5031));
+    }
+
+    #[test]
+    fn not_a_cube() {
+        assert_eq!(
+            PartTwo::solve("....\n\n1").unwrap_err().to_string(),
+            "No solution: the map doesn't fold into a cube"
+        );
+    }
+}
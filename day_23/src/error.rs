@@ -0,0 +1,31 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use aoc_grid::error::GridError;
+
+/// Errors found while reading the scan of the grove
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A tile that is neither an elf (#) nor ground (.)
+    InvalidTile(char),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidTile(c) => write!(f, "expected '#' or '.', found '{}'", c),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<GridError<ParseError>> for ParseError {
+    fn from(e: GridError<ParseError>) -> Self {
+        match e {
+            GridError::Cell(e) => e,
+            // Only dense grids check the width and emptiness of the map
+            e => unreachable!("{}", e),
+        }
+    }
+}
@@ -1,103 +1,78 @@
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::error::Error;
-use std::io;
+use std::collections::{HashMap, VecDeque};
 
-pub fn main_p1() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
+use aoc_core::{Answer, Solution, SolveError};
+use aoc_grid::{Grid, Pos, DOWN, LEFT, RIGHT, UP};
 
-    let mut elves = HashSet::<Point>::new();
+use crate::error::ParseError;
 
-    let mut row = 0;
+pub struct PartOne;
 
-    for line in lines {
-        let line = line.unwrap();
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut elves = Grid::parse_sparse(input, |_, c| match c {
+            '#' => Ok(Some(())),
+            '.' => Ok(None),
+            _ => Err(ParseError::InvalidTile(c)),
+        })
+        .map_err(|e| e.map(ParseError::from))?;
 
-        for (idx, c) in line.chars().enumerate() {
-            match c {
-                '#' => {
-                    elves.insert(Point::new(idx as i32, row));
-                    ()
-                }
-                _ => continue,
-            }
+        if elves.is_empty() {
+            return Err(SolveError::NoSolution(String::from("no elf in the grove")));
         }
 
-        row += 1;
-    }
+        let mut directions = VecDeque::from(vec![
+            Direction::North,
+            Direction::South,
+            Direction::West,
+            Direction::East,
+        ]);
 
-    let mut directions = VecDeque::from(vec![
-        Direction::North,
-        Direction::South,
-        Direction::West,
-        Direction::East,
-    ]);
-
-    for round in 0..10 {
-        let mut propositions = HashMap::<Point, i32>::new();
-        let mut moves = HashMap::<Point, Point>::new();
-
-        // 1st half
-        for elve in &elves {
-            if !is_alone(&elves, elve) {
-                for dir in &directions {
-                    if can_move(&elves, elve, *dir) {
-                        let position = match dir {
-                            Direction::North => Point::new(elve.x(), elve.y() - 1),
-                            Direction::South => Point::new(elve.x(), elve.y() + 1),
-                            Direction::West => Point::new(elve.x() - 1, elve.y()),
-                            Direction::East => Point::new(elve.x() + 1, elve.y()),
-                        };
-
-                        moves.insert(*elve, position);
-
-                        let value = if let Some(value) = propositions.get(&position) {
-                            *value
-                        } else {
-                            0
-                        };
-
-                        propositions.insert(position, value + 1);
-
-                        break;
-                    }
-                }
-            }
-        }
+        for _round in 0..10 {
+            let mut propositions = HashMap::<Pos, i32>::new();
+            let mut moves = HashMap::<Pos, Pos>::new();
 
-        // 2nd half
-        for m in moves {
-            if let Some(value) = propositions.get(&m.1) {
-                if *value == 1 {
-                    elves.remove(&m.0);
-                    elves.insert(m.1);
-                }
-            }
-        }
+            // 1st half
+            for (elve, _) in &elves {
+                if !is_alone(&elves, elve) {
+                    for dir in &directions {
+                        if can_move(&elves, elve, *dir) {
+                            let position = elve + dir.step();
 
-        // Cycle the directions
-        let dir = directions.pop_front().unwrap();
-        directions.push_back(dir);
-    }
+                            moves.insert(elve, position);
 
-    let x_min = elves.iter().min_by_key(|p| p.x()).unwrap().x();
-    let x_max = elves.iter().max_by_key(|p| p.x()).unwrap().x();
+                            let value = if let Some(value) = propositions.get(&position) {
+                                *value
+                            } else {
+                                0
+                            };
 
-    let y_min = elves.iter().min_by_key(|p| p.y()).unwrap().y();
-    let y_max = elves.iter().max_by_key(|p| p.y()).unwrap().y();
+                            propositions.insert(position, value + 1);
 
-    let mut empty_tile = 0;
+                            break;
+                        }
+                    }
+                }
+            }
 
-    for y in y_min..=y_max {
-        for x in x_min..=x_max {
-            if let None = elves.get(&Point::new(x, y)) {
-                empty_tile += 1;
+            // 2nd half
+            for m in moves {
+                if let Some(value) = propositions.get(&m.1) {
+                    if *value == 1 {
+                        elves.remove(m.0);
+                        elves.insert(m.1, ());
+                    }
+                }
             }
+
+            // Cycle the directions
+            let dir = directions.pop_front().unwrap();
+            directions.push_back(dir);
         }
-    }
 
-    println!("Empty tiles: {}", empty_tile);
+        let empty_tile = elves.width() * elves.height() - elves.len();
 
-    Ok(())
+        Ok(empty_tile.into())
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -108,73 +83,63 @@ enum Direction {
     East,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
+impl Direction {
+    /// Step taken by an elf moving in this direction
+    fn step(&self) -> Pos {
+        match self {
+            Direction::North => UP,
+            Direction::South => DOWN,
+            Direction::West => LEFT,
+            Direction::East => RIGHT,
+        }
     }
 
-    fn x(&self) -> i32 {
-        self.x
-    }
+    /// Positions that must be free for an elf to move in this direction
+    fn looks(&self) -> [Pos; 3] {
+        let dir = self.step();
 
-    fn y(&self) -> i32 {
-        self.y
+        match self {
+            Direction::North | Direction::South => [dir, dir + LEFT, dir + RIGHT],
+            Direction::West | Direction::East => [dir, dir + UP, dir + DOWN],
+        }
     }
 }
 
-fn is_alone(elves: &HashSet<Point>, elve: &Point) -> bool {
-    let up = Point::new(elve.x(), elve.y() - 1);
-    let down = Point::new(elve.x(), elve.y() + 1);
-
-    let left = Point::new(elve.x() - 1, elve.y());
-    let right = Point::new(elve.x() + 1, elve.y());
-
-    let up_left = Point::new(left.x(), up.y());
-    let up_right = Point::new(right.x(), up.y());
-
-    let down_left = Point::new(left.x(), down.y());
-    let down_right = Point::new(right.x(), down.y());
-
-    let positions = vec![
-        up, down, left, right, up_left, up_right, down_left, down_right,
-    ];
-
-    !positions.iter().any(|p| elves.contains(p))
+fn is_alone(elves: &Grid<()>, elve: Pos) -> bool {
+    elves.neighbours8(elve).next().is_none()
 }
 
-fn can_move(elves: &HashSet<Point>, elve: &Point, dir: Direction) -> bool {
-    let up = Point::new(elve.x(), elve.y() - 1);
-    let down = Point::new(elve.x(), elve.y() + 1);
-
-    let left = Point::new(elve.x() - 1, elve.y());
-    let right = Point::new(elve.x() + 1, elve.y());
-
-    let up_left = Point::new(left.x(), up.y());
-    let up_right = Point::new(right.x(), up.y());
-
-    let down_left = Point::new(left.x(), down.y());
-    let down_right = Point::new(right.x(), down.y());
-
-    let positions = match dir {
-        Direction::North => vec![up, up_left, up_right],
-        Direction::South => vec![down, down_left, down_right],
-        Direction::West => vec![left, up_left, down_left],
-        Direction::East => vec![right, up_right, down_right],
-    };
-
-    !positions.iter().any(|p| elves.contains(p))
+fn can_move(elves: &Grid<()>, elve: Pos, dir: Direction) -> bool {
+    !dir.looks().iter().any(|&look| elves.contains(elve + look))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(110i32));
+    }
+
+    #[test]
+    fn invalid_tile() {
+        assert_eq!(
+            PartOne::solve("..#\n#o.\n").unwrap_err().to_string(),
+            "Invalid input: line 2, column 2: expected '#' or '.', found 'o'"
+        );
+    }
+
+    #[test]
+    fn no_elf() {
+        assert_eq!(
+            PartOne::solve("...\n...\n").unwrap_err().to_string(),
+            "No solution: no elf in the grove"
+        );
+    }
+
     #[test]
     fn simple() {}
 }
@@ -1,103 +1,81 @@
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::error::Error;
-use std::io;
+use std::collections::{HashMap, VecDeque};
 
-pub fn main_p2() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
+use aoc_core::{Answer, Solution, SolveError};
+use aoc_grid::{Grid, Pos, DOWN, LEFT, RIGHT, UP};
 
-    let mut elves = HashSet::<Point>::new();
+use crate::error::ParseError;
 
-    let mut row = 0;
+pub struct PartTwo;
 
-    for line in lines {
-        let line = line.unwrap();
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut elves = Grid::parse_sparse(input, |_, c| match c {
+            '#' => Ok(Some(())),
+            '.' => Ok(None),
+            _ => Err(ParseError::InvalidTile(c)),
+        })
+        .map_err(|e| e.map(ParseError::from))?;
 
-        for (idx, c) in line.chars().enumerate() {
-            match c {
-                '#' => {
-                    elves.insert(Point::new(idx as i32, row));
-                    ()
-                }
-                _ => continue,
-            }
-        }
-
-        row += 1;
-    }
-
-    println!("Initial layout:");
+        let mut directions = VecDeque::from(vec![
+            Direction::North,
+            Direction::South,
+            Direction::West,
+            Direction::East,
+        ]);
 
-    print_elves(&elves);
+        let mut round = 0;
 
-    let mut directions = VecDeque::from(vec![
-        Direction::North,
-        Direction::South,
-        Direction::West,
-        Direction::East,
-    ]);
+        loop {
+            let mut propositions = HashMap::<Pos, i32>::new();
+            let mut moves = HashMap::<Pos, Pos>::new();
 
-    let mut round = 0;
+            // 1st half
+            for (elve, _) in &elves {
+                if !is_alone(&elves, elve) {
+                    for dir in &directions {
+                        if can_move(&elves, elve, *dir) {
+                            let position = elve + dir.step();
 
-    loop {
-        let mut propositions = HashMap::<Point, i32>::new();
-        let mut moves = HashMap::<Point, Point>::new();
+                            moves.insert(elve, position);
 
-        // 1st half
-        for elve in &elves {
-            if !is_alone(&elves, elve) {
-                for dir in &directions {
-                    if can_move(&elves, elve, *dir) {
-                        let position = match dir {
-                            Direction::North => Point::new(elve.x(), elve.y() - 1),
-                            Direction::South => Point::new(elve.x(), elve.y() + 1),
-                            Direction::West => Point::new(elve.x() - 1, elve.y()),
-                            Direction::East => Point::new(elve.x() + 1, elve.y()),
-                        };
+                            let value = if let Some(value) = propositions.get(&position) {
+                                *value
+                            } else {
+                                0
+                            };
 
-                        moves.insert(*elve, position);
+                            propositions.insert(position, value + 1);
 
-                        let value = if let Some(value) = propositions.get(&position) {
-                            *value
-                        } else {
-                            0
-                        };
-
-                        propositions.insert(position, value + 1);
-
-                        break;
+                            break;
+                        }
                     }
                 }
             }
-        }
 
-        // Check if any elves moved
-        if moves.is_empty() {
-            break;
-        }
+            // Check if any elves moved
+            if moves.is_empty() {
+                break;
+            }
 
-        // 2nd half
-        for m in &moves {
-            if let Some(value) = propositions.get(&m.1) {
-                if *value == 1 {
-                    elves.remove(m.0);
-                    elves.insert(*m.1);
+            // 2nd half
+            for m in &moves {
+                if let Some(value) = propositions.get(m.1) {
+                    if *value == 1 {
+                        elves.remove(*m.0);
+                        elves.insert(*m.1, ());
+                    }
                 }
             }
-        }
 
-        // Cycle the directions
-        let dir = directions.pop_front().unwrap();
-        directions.push_back(dir);
+            // Cycle the directions
+            let dir = directions.pop_front().unwrap();
+            directions.push_back(dir);
 
-        round += 1;
+            round += 1;
+        }
 
-        //println!("End of round {}", round);
-        //print_elves(&elves);
+        Ok((round + 1).into())
     }
-
-    println!("Finished in round: {}", round + 1);
-
-    Ok(())
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -108,92 +86,47 @@ enum Direction {
     East,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
+impl Direction {
+    /// Step taken by an elf moving in this direction
+    fn step(&self) -> Pos {
+        match self {
+            Direction::North => UP,
+            Direction::South => DOWN,
+            Direction::West => LEFT,
+            Direction::East => RIGHT,
+        }
     }
 
-    fn x(&self) -> i32 {
-        self.x
-    }
+    /// Positions that must be free for an elf to move in this direction
+    fn looks(&self) -> [Pos; 3] {
+        let dir = self.step();
 
-    fn y(&self) -> i32 {
-        self.y
+        match self {
+            Direction::North | Direction::South => [dir, dir + LEFT, dir + RIGHT],
+            Direction::West | Direction::East => [dir, dir + UP, dir + DOWN],
+        }
     }
 }
 
-fn is_alone(elves: &HashSet<Point>, elve: &Point) -> bool {
-    let up = Point::new(elve.x(), elve.y() - 1);
-    let down = Point::new(elve.x(), elve.y() + 1);
-
-    let left = Point::new(elve.x() - 1, elve.y());
-    let right = Point::new(elve.x() + 1, elve.y());
-
-    let up_left = Point::new(left.x(), up.y());
-    let up_right = Point::new(right.x(), up.y());
-
-    let down_left = Point::new(left.x(), down.y());
-    let down_right = Point::new(right.x(), down.y());
-
-    let positions = vec![
-        up, down, left, right, up_left, up_right, down_left, down_right,
-    ];
-
-    !positions.iter().any(|p| elves.contains(p))
+fn is_alone(elves: &Grid<()>, elve: Pos) -> bool {
+    elves.neighbours8(elve).next().is_none()
 }
 
-fn can_move(elves: &HashSet<Point>, elve: &Point, dir: Direction) -> bool {
-    let up = Point::new(elve.x(), elve.y() - 1);
-    let down = Point::new(elve.x(), elve.y() + 1);
-
-    let left = Point::new(elve.x() - 1, elve.y());
-    let right = Point::new(elve.x() + 1, elve.y());
-
-    let up_left = Point::new(left.x(), up.y());
-    let up_right = Point::new(right.x(), up.y());
-
-    let down_left = Point::new(left.x(), down.y());
-    let down_right = Point::new(right.x(), down.y());
-
-    let positions = match dir {
-        Direction::North => vec![up, up_left, up_right],
-        Direction::South => vec![down, down_left, down_right],
-        Direction::West => vec![left, up_left, down_left],
-        Direction::East => vec![right, up_right, down_right],
-    };
-
-    !positions.iter().any(|p| elves.contains(p))
-}
-
-fn print_elves(elves: &HashSet<Point>) {
-    let x_min = elves.iter().min_by_key(|p| p.x()).unwrap().x();
-    let x_max = elves.iter().max_by_key(|p| p.x()).unwrap().x();
-
-    let y_min = elves.iter().min_by_key(|p| p.y()).unwrap().y();
-    let y_max = elves.iter().max_by_key(|p| p.y()).unwrap().y();
-
-    for y in y_min..=y_max {
-        for x in x_min..=x_max {
-            if let None = elves.get(&Point::new(x, y)) {
-                print!(".");
-            } else {
-                print!("#");
-            }
-        }
-        println!();
-    }
+fn can_move(elves: &Grid<()>, elve: Pos, dir: Direction) -> bool {
+    !dir.looks().iter().any(|&look| elves.contains(elve + look))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(20i32));
+    }
+
     #[test]
     fn simple() {}
 }
@@ -0,0 +1,51 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use aoc_grid::error::GridError;
+
+/// Errors found while reading the map of the valley
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A tile that is neither a wall (#), the ground (.) nor a blizzard (^, >, v or <)
+    InvalidTile(char),
+    /// A row that isn't as wide as the first one
+    InvalidWidth { expected: usize, found: usize },
+    /// A tile around the valley that isn't a wall, besides the entrance and the exit
+    MissingWall(char),
+    /// The top or bottom wall has no gap to enter or leave the valley
+    MissingGap(&'static str),
+    /// The walls leave no room for a valley
+    Empty,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidTile(c) => {
+                write!(f, "expected '#', '.', '^', '>', 'v' or '<', found '{}'", c)
+            }
+            ParseError::InvalidWidth { expected, found } => {
+                write!(f, "expected {} tiles in the row, found {}", expected, found)
+            }
+            ParseError::MissingWall(c) => write!(f, "expected a wall ('#'), found '{}'", c),
+            ParseError::MissingGap(wall) => {
+                write!(f, "expected a gap in the {} wall, found none", wall)
+            }
+            ParseError::Empty => write!(f, "expected a valley inside the walls, found nothing"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<GridError<ParseError>> for ParseError {
+    fn from(e: GridError<ParseError>) -> Self {
+        match e {
+            GridError::Cell(e) => e,
+            GridError::InvalidWidth { expected, found } => {
+                ParseError::InvalidWidth { expected, found }
+            }
+            GridError::Empty => ParseError::Empty,
+        }
+    }
+}
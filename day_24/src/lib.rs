@@ -0,0 +1,4 @@
+pub mod error;
+pub mod part_one;
+pub mod part_two;
+pub mod valley;
@@ -0,0 +1,40 @@
+use aoc_core::{Answer, Solution, SolveError};
+
+use crate::valley::{FindShortestPath, Valley, Visitor};
+
+pub struct PartOne;
+
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let valley = Valley::parse(input)?;
+        let mut path = FindShortestPath::new(&valley);
+
+        let minutes = path
+            .visit(valley.start(), valley.end(), 0)
+            .ok_or_else(|| SolveError::NoSolution(String::from("no way through the blizzards")))?;
+
+        Ok(minutes.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(18));
+    }
+
+    #[test]
+    fn no_way_through() {
+        assert_eq!(
+            PartOne::solve("#.###\n#<<<#\n#>>>#\n###.#")
+                .unwrap_err()
+                .to_string(),
+            "No solution: no way through the blizzards"
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use aoc_core::{Answer, Solution, SolveError};
+
+use crate::valley::{FindShortestPath, Valley, Visitor};
+
+pub struct PartTwo;
+
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let valley = Valley::parse(input)?;
+        let mut path = FindShortestPath::new(&valley);
+
+        // To the goal, back to the start for the snacks, and to the goal again
+        let (start, end) = (valley.start(), valley.end());
+        let mut minute = 0;
+
+        for (from, to) in [(start, end), (end, start), (start, end)] {
+            minute += path.visit(from, to, minute).ok_or_else(|| {
+                SolveError::NoSolution(String::from("no way through the blizzards"))
+            })?;
+        }
+
+        Ok(minute.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(54));
+    }
+}
@@ -0,0 +1,321 @@
+use std::collections::VecDeque;
+
+use aoc_core::Located;
+use aoc_grid::{Grid, Pos, DOWN, LEFT, RIGHT, UP};
+
+use crate::error::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Wall,
+    Ground,
+    /// A blizzard blowing in a direction
+    Blizzard(Pos),
+}
+
+/// The valley with its blizzards, known for every minute of their period
+#[derive(Debug, Clone)]
+pub struct Valley {
+    /// Positions free of walls and blizzards, for each minute of the period
+    free: Vec<Grid<bool>>,
+    start: Pos,
+    end: Pos,
+}
+
+impl Valley {
+    /// Read the map of the valley at minute 0, surrounded by walls with a gap at the top and
+    /// another at the bottom
+    pub fn parse(input: &str) -> Result<Self, Located<ParseError>> {
+        let height = input.lines().count() as isize;
+        let width = input.lines().next().map_or(0, |line| line.chars().count()) as isize;
+        let mut start = None;
+        let mut end = None;
+
+        let tiles = Grid::parse(input, |pos, c| {
+            let tile = match c {
+                '#' => Tile::Wall,
+                '.' => Tile::Ground,
+                '^' => Tile::Blizzard(UP),
+                '>' => Tile::Blizzard(RIGHT),
+                'v' => Tile::Blizzard(DOWN),
+                '<' => Tile::Blizzard(LEFT),
+                _ => return Err(ParseError::InvalidTile(c)),
+            };
+
+            let side = pos.x == 0 || pos.x == width - 1;
+            if tile != Tile::Wall && (side || pos.y == 0 || pos.y == height - 1) {
+                match tile {
+                    Tile::Ground if !side && pos.y == 0 && start.is_none() => start = Some(pos),
+                    Tile::Ground if !side && pos.y == height - 1 && end.is_none() => {
+                        end = Some(pos)
+                    }
+                    _ => return Err(ParseError::MissingWall(c)),
+                }
+            }
+
+            Ok(tile)
+        })
+        .map_err(|e| e.map(ParseError::from))?;
+
+        let Some(start) = start else {
+            return Err(Located::new(1, 1, ParseError::MissingGap("top")));
+        };
+        let Some(end) = end else {
+            let line = height as usize;
+            return Err(Located::new(line, 1, ParseError::MissingGap("bottom")));
+        };
+
+        // Blizzards wrap around inside the walls
+        let (inner_width, inner_height) = (width - 2, height - 2);
+        if inner_width <= 0 || inner_height <= 0 {
+            return Err(Located::new(1, 1, ParseError::Empty));
+        }
+
+        let blizzards: Vec<(Pos, Pos)> = tiles
+            .iter()
+            .filter_map(|(pos, tile)| match tile {
+                Tile::Blizzard(dir) => Some((pos, *dir)),
+                _ => None,
+            })
+            .collect();
+
+        // Every blizzard is back where it started after this number of minutes
+        let period = lcm(inner_width, inner_height);
+
+        let free = (0..period)
+            .map(|minute| {
+                let mut free = tiles.map(|tile| *tile != Tile::Wall);
+
+                for &(pos, dir) in &blizzards {
+                    let x = (pos.x - 1 + dir.x * minute).rem_euclid(inner_width) + 1;
+                    let y = (pos.y - 1 + dir.y * minute).rem_euclid(inner_height) + 1;
+
+                    free[Pos::new(x, y)] = false;
+                }
+
+                free
+            })
+            .collect();
+
+        Ok(Valley { free, start, end })
+    }
+
+    /// Gap in the top wall
+    pub fn start(&self) -> Pos {
+        self.start
+    }
+
+    /// Gap in the bottom wall
+    pub fn end(&self) -> Pos {
+        self.end
+    }
+
+    /// Number of minutes before the blizzards are back to their first positions
+    pub fn period(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Whether `pos` is in the valley and free of blizzards at `minute`
+    pub fn is_free(&self, pos: Pos, minute: usize) -> bool {
+        self.free[minute % self.period()].get(pos) == Some(&true)
+    }
+}
+
+pub trait Visitor {
+    /// Minutes needed to go from `from` to `to` when leaving at `minute`,
+    /// `None` if the blizzards never let the expedition through
+    fn visit(&mut self, from: Pos, to: Pos, minute: usize) -> Option<usize>;
+}
+
+/// Breadth first search over the positions at each minute of the period of the blizzards
+#[derive(Debug)]
+pub struct FindShortestPath<'a> {
+    valley: &'a Valley,
+    /// Positions already reached, for each minute of the period
+    visited: Vec<Grid<bool>>,
+    /// Position a minute before reaching a position, for each minute of the period
+    predecessor: Vec<Grid<Option<Pos>>>,
+    /// Positions of the expedition minute by minute along the last path found
+    path: Vec<Pos>,
+}
+
+impl<'a> FindShortestPath<'a> {
+    pub fn new(valley: &'a Valley) -> Self {
+        FindShortestPath {
+            valley,
+            visited: valley.free.iter().map(|free| free.map(|_| false)).collect(),
+            predecessor: valley.free.iter().map(|free| free.map(|_| None)).collect(),
+            path: Vec::new(),
+        }
+    }
+
+    /// Positions of the expedition at each minute of the last visit, waits included
+    pub fn path(&self) -> &[Pos] {
+        &self.path
+    }
+
+    /// Path ending on `pos` after `depth` minutes of a visit started at `minute`
+    fn trace(&self, mut pos: Pos, minute: usize, depth: usize) -> Vec<Pos> {
+        let period = self.valley.period();
+        let mut path = vec![pos];
+
+        for elapsed in (1..=depth).rev() {
+            pos = self.predecessor[(minute + elapsed) % period][pos].unwrap();
+            path.push(pos);
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+impl<'a> Visitor for FindShortestPath<'a> {
+    fn visit(&mut self, from: Pos, to: Pos, minute: usize) -> Option<usize> {
+        let valley = self.valley;
+        let period = valley.period();
+        let mut q = VecDeque::<Pos>::new();
+        let mut depth = 0;
+        let mut node_left = 1;
+
+        for visited in &mut self.visited {
+            *visited = visited.map(|_| false);
+        }
+
+        self.visited[minute % period][from] = true;
+        q.push_back(from);
+
+        while let Some(pos) = q.pop_front() {
+            if pos == to {
+                self.path = self.trace(to, minute, depth);
+                return Some(depth);
+            }
+
+            // The expedition either waits or moves, and nobody can stand in a blizzard
+            let next_minute = minute + depth + 1;
+
+            for next in std::iter::once(pos).chain(pos.neighbours4()) {
+                let index = next_minute % period;
+
+                if valley.is_free(next, next_minute) && !self.visited[index][next] {
+                    self.visited[index][next] = true;
+                    self.predecessor[index][next] = Some(pos);
+                    q.push_back(next);
+                }
+            }
+
+            node_left -= 1;
+            if node_left == 0 {
+                depth += 1;
+                node_left = q.len();
+            }
+        }
+
+        None
+    }
+}
+
+fn gcd(a: isize, b: isize) -> isize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(a: isize, b: isize) -> isize {
+    a / gcd(a, b) * b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Valley {
+        Valley::parse(include_str!("../simple_input.txt")).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let valley = example();
+
+        assert_eq!(valley.start(), Pos::new(1, 0));
+        assert_eq!(valley.end(), Pos::new(6, 5));
+        assert_eq!(valley.period(), 12);
+    }
+
+    #[test]
+    fn blizzards_move_and_wrap() {
+        let valley =
+            Valley::parse("#.#####\n#.....#\n#>....#\n#.....#\n#...v.#\n#.....#\n#####.#").unwrap();
+
+        assert!(!valley.is_free(Pos::new(1, 2), 0));
+        assert!(!valley.is_free(Pos::new(2, 2), 1));
+        assert!(valley.is_free(Pos::new(1, 2), 1));
+        assert!(!valley.is_free(Pos::new(4, 1), 2));
+        assert!(!valley.is_free(Pos::new(1, 2), 5));
+        assert!(!valley.is_free(Pos::new(0, 1), 0));
+        assert!(valley.is_free(Pos::new(5, 6), 3));
+    }
+
+    #[test]
+    fn invalid_tile() {
+        assert_eq!(
+            Valley::parse("#.###\n#.x.#\n###.#")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 3: expected '#', '.', '^', '>', 'v' or '<', found 'x'"
+        );
+    }
+
+    #[test]
+    fn missing_wall() {
+        assert_eq!(
+            Valley::parse("#.###\n#...>\n###.#")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 5: expected a wall ('#'), found '>'"
+        );
+        assert_eq!(
+            Valley::parse("#..##\n#...#\n###.#")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 3: expected a wall ('#'), found '.'"
+        );
+    }
+
+    #[test]
+    fn missing_gap() {
+        assert_eq!(
+            Valley::parse("#.###\n#...#\n#####")
+                .unwrap_err()
+                .to_string(),
+            "line 3, column 1: expected a gap in the bottom wall, found none"
+        );
+    }
+
+    #[test]
+    fn shortest_path() {
+        let valley = example();
+        let mut search = FindShortestPath::new(&valley);
+
+        assert_eq!(search.visit(valley.start(), valley.end(), 0), Some(18));
+
+        let path = search.path();
+        assert_eq!(path.len(), 19);
+        assert_eq!(path.first(), Some(&valley.start()));
+        assert_eq!(path.last(), Some(&valley.end()));
+
+        for (minute, step) in path.windows(2).enumerate() {
+            assert!(step[0].manhattan(&step[1]) <= 1);
+            assert!(valley.is_free(step[1], minute + 1));
+        }
+    }
+
+    #[test]
+    fn trapped() {
+        let valley = Valley::parse("#.###\n#<<<#\n#>>>#\n###.#").unwrap();
+        let mut search = FindShortestPath::new(&valley);
+
+        assert_eq!(search.visit(valley.start(), valley.end(), 0), None);
+    }
+}
@@ -0,0 +1,47 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading a SNAFU number
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A digit other than 2, 1, 0, - (minus one) or = (minus two), at `column` (from 1)
+    InvalidDigit { column: usize, found: char },
+    /// A number too large to be held in 128 bits
+    Overflow(String),
+    /// A number without any digit
+    Empty,
+}
+
+impl ParseError {
+    /// Column (starting at 1) of the error in the number
+    pub fn column(&self) -> usize {
+        match self {
+            ParseError::InvalidDigit { column, .. } => *column,
+            ParseError::Overflow(_) | ParseError::Empty => 1,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidDigit { found, .. } => {
+                write!(
+                    f,
+                    "expected a SNAFU digit (2, 1, 0, - or =), found '{}'",
+                    found
+                )
+            }
+            ParseError::Overflow(s) => {
+                write!(
+                    f,
+                    "expected a SNAFU number fitting in 128 bits, found '{}'",
+                    s
+                )
+            }
+            ParseError::Empty => write!(f, "expected a SNAFU number, found nothing"),
+        }
+    }
+}
+
+impl Error for ParseError {}
@@ -0,0 +1,55 @@
+use aoc_core::{Answer, Located, Solution, SolveError};
+
+use crate::snafu::Snafu;
+
+pub struct PartOne;
+
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut total = Snafu::default();
+
+        for (index, line) in input.lines().enumerate() {
+            let number: Snafu = line
+                .parse()
+                .map_err(|e: crate::error::ParseError| Located::new(index + 1, e.column(), e))?;
+
+            total = total.checked_add(number).ok_or_else(|| {
+                SolveError::NoSolution(format!("the sum overflows 128 bits at line {}", index + 1))
+            })?;
+        }
+
+        // The console of Bob expects the sum of the fuel requirements in SNAFU
+        Ok(total.to_string().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from("2=-1=0"));
+    }
+
+    #[test]
+    fn invalid_digit() {
+        assert_eq!(
+            PartOne::solve("1=\n2-3\n").unwrap_err().to_string(),
+            "Invalid input: line 2, column 3: expected a SNAFU digit (2, 1, 0, - or =), found '3'"
+        );
+    }
+
+    #[test]
+    fn sum_overflow() {
+        let max = Snafu::new(i128::MAX).to_string();
+        let input = format!("1\n{}\n-\n", max);
+
+        assert_eq!(
+            PartOne::solve(&input).unwrap_err().to_string(),
+            "No solution: the sum overflows 128 bits at line 2"
+        );
+    }
+}
@@ -0,0 +1,25 @@
+use aoc_core::{Answer, Solution, SolveError};
+
+pub struct PartTwo;
+
+impl Solution for PartTwo {
+    /// The last day has a single puzzle, its second star is given for all the others
+    fn solve(_input: &str) -> Result<Answer, SolveError> {
+        Ok(Answer::from(
+            "none, the last day has a single puzzle and its second star is given for the others",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_puzzle() {
+        assert_eq!(
+            PartTwo::solve("").unwrap().to_string(),
+            "none, the last day has a single puzzle and its second star is given for the others"
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use std::fmt::Display;
+use std::iter::Sum;
+use std::num::TryFromIntError;
+use std::ops::{Add, AddAssign};
+use std::str::FromStr;
+
+use crate::error::ParseError;
+
+/// A number written in SNAFU: powers of five, with digits going from minus two (=) to two
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Snafu(i128);
+
+impl Snafu {
+    pub const fn new(value: i128) -> Self {
+        Snafu(value)
+    }
+
+    pub fn value(&self) -> i128 {
+        self.0
+    }
+
+    /// `self + rhs`, `None` if it doesn't fit in 128 bits
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Snafu)
+    }
+
+    /// The sum of the numbers, `None` if it doesn't fit in 128 bits
+    pub fn checked_sum<I: IntoIterator<Item = Self>>(numbers: I) -> Option<Self> {
+        numbers
+            .into_iter()
+            .try_fold(Snafu::default(), Snafu::checked_add)
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = ParseError;
+
+    /// Read a number like "1=-0-2", most significant digit first
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let mut value: i128 = 0;
+
+        for (index, c) in s.chars().enumerate() {
+            let digit = match c {
+                '2' => 2,
+                '1' => 1,
+                '0' => 0,
+                '-' => -1,
+                '=' => -2,
+                _ => {
+                    return Err(ParseError::InvalidDigit {
+                        column: index + 1,
+                        found: c,
+                    })
+                }
+            };
+
+            // Adding the digit before the last fifth keeps i128::MIN and MAX readable
+            value = value
+                .checked_mul(4)
+                .and_then(|times4| times4.checked_add(digit))
+                .and_then(|times4| times4.checked_add(value))
+                .ok_or_else(|| ParseError::Overflow(s.to_string()))?;
+        }
+
+        Ok(Snafu(value))
+    }
+}
+
+impl Display for Snafu {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0 == 0 {
+            return write!(f, "0");
+        }
+
+        let mut digits = Vec::new();
+        let mut value = self.0;
+
+        while value != 0 {
+            // 3 and 4 are written as 5 minus 2 and 5 minus 1, carrying the 5 to the next digit
+            let (digit, carry) = match value.rem_euclid(5) {
+                0 => ('0', 0),
+                1 => ('1', 0),
+                2 => ('2', 0),
+                3 => ('=', 1),
+                _ => ('-', 1),
+            };
+
+            digits.push(digit);
+            value = value.div_euclid(5) + carry;
+        }
+
+        let number: String = digits.iter().rev().collect();
+        write!(f, "{}", number)
+    }
+}
+
+impl Add for Snafu {
+    type Output = Self;
+
+    /// Panics if the sum doesn't fit in 128 bits, see [`Snafu::checked_add`]
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("attempt to add SNAFU numbers with overflow")
+    }
+}
+
+impl AddAssign for Snafu {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sum for Snafu {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Snafu::default(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Snafu> for Snafu {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl From<i64> for Snafu {
+    fn from(value: i64) -> Self {
+        Snafu(value.into())
+    }
+}
+
+impl From<i128> for Snafu {
+    fn from(value: i128) -> Self {
+        Snafu(value)
+    }
+}
+
+impl From<Snafu> for i128 {
+    fn from(value: Snafu) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<Snafu> for i64 {
+    type Error = TryFromIntError;
+
+    fn try_from(value: Snafu) -> Result<Self, Self::Error> {
+        i64::try_from(value.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decimal and SNAFU numbers from the puzzle
+    const NUMBERS: [(i128, &str); 15] = [
+        (1, "1"),
+        (2, "2"),
+        (3, "1="),
+        (4, "1-"),
+        (5, "10"),
+        (6, "11"),
+        (7, "12"),
+        (8, "2="),
+        (9, "2-"),
+        (10, "20"),
+        (15, "1=0"),
+        (20, "1-0"),
+        (2022, "1=11-2"),
+        (12345, "1-0---0"),
+        (314159265, "1121-1110-1=0"),
+    ];
+
+    /// Deterministic pseudo-random numbers (xorshift) for the round-trip properties
+    fn numbers(seed: u64) -> impl Iterator<Item = i128> {
+        std::iter::successors(Some(seed), |&x| {
+            let x = x ^ (x << 13);
+            let x = x ^ (x >> 7);
+            Some(x ^ (x << 17))
+        })
+        .skip(1)
+        .map(|x| (x as i64 as i128) << (x % 64))
+    }
+
+    #[test]
+    fn puzzle_numbers() {
+        for (decimal, snafu) in NUMBERS {
+            assert_eq!(snafu.parse::<Snafu>().unwrap().value(), decimal);
+            assert_eq!(Snafu::new(decimal).to_string(), snafu);
+        }
+    }
+
+    #[test]
+    fn zero_and_negatives() {
+        assert_eq!(Snafu::new(0).to_string(), "0");
+        assert_eq!(Snafu::new(-1).to_string(), "-");
+        assert_eq!(Snafu::new(-3).to_string(), "-2");
+        assert_eq!("=".parse::<Snafu>().unwrap().value(), -2);
+    }
+
+    #[test]
+    fn round_trip() {
+        let extremes = [i128::MIN, i128::MIN + 1, i128::MAX - 1, i128::MAX];
+
+        for value in (-1000..=1000)
+            .chain(extremes)
+            .chain(numbers(2022).take(1000))
+        {
+            let snafu = Snafu::from(value);
+            let text = snafu.to_string();
+
+            assert_eq!(
+                text.parse::<Snafu>(),
+                Ok(snafu),
+                "{} written {}",
+                value,
+                text
+            );
+            assert_eq!(i128::from(snafu), value);
+        }
+    }
+
+    #[test]
+    fn round_trip_i64() {
+        for value in numbers(25).take(1000).map(|x| x as i64) {
+            let snafu = Snafu::from(value);
+
+            assert_eq!(i64::try_from(snafu), Ok(value));
+            assert_eq!(
+                i64::try_from(snafu.to_string().parse::<Snafu>().unwrap()),
+                Ok(value)
+            );
+        }
+
+        assert!(i64::try_from(Snafu::new(i64::MAX as i128 + 1)).is_err());
+    }
+
+    #[test]
+    fn addition_matches_decimal() {
+        let values: Vec<i64> = numbers(7).take(200).map(|x| x as i64 >> 2).collect();
+
+        for pair in values.windows(2) {
+            let sum = Snafu::from(pair[0]) + Snafu::from(pair[1]);
+
+            assert_eq!(sum, Snafu::from(pair[0] as i128 + pair[1] as i128));
+        }
+
+        let total: Snafu = values.iter().map(|&x| Snafu::from(x)).sum();
+        assert_eq!(total.value(), values.iter().map(|&x| x as i128).sum());
+        assert_eq!(
+            Snafu::checked_sum(values.iter().map(|&x| Snafu::from(x))),
+            Some(total)
+        );
+    }
+
+    #[test]
+    fn addition_overflow() {
+        let max = Snafu::new(i128::MAX);
+
+        assert_eq!(
+            max.checked_add(Snafu::new(-1)),
+            Some(Snafu::new(i128::MAX - 1))
+        );
+        assert_eq!(max.checked_add(Snafu::new(1)), None);
+        assert_eq!(Snafu::new(i128::MIN).checked_add(Snafu::new(-1)), None);
+        assert_eq!(Snafu::checked_sum([max, max, Snafu::new(i128::MIN)]), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow")]
+    fn add_overflow_panics() {
+        let _ = Snafu::new(i128::MAX) + Snafu::new(1);
+    }
+
+    #[test]
+    fn invalid_digit() {
+        let error = "12=3".parse::<Snafu>().unwrap_err();
+
+        assert_eq!(error.column(), 4);
+        assert_eq!(
+            error.to_string(),
+            "expected a SNAFU digit (2, 1, 0, - or =), found '3'"
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        let digits = "2".repeat(56);
+
+        assert_eq!(
+            digits.parse::<Snafu>(),
+            Err(ParseError::Overflow(digits.clone()))
+        );
+        assert_eq!("".parse::<Snafu>(), Err(ParseError::Empty));
+    }
+}
@@ -0,0 +1,36 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading the rucksacks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// An item that isn't a letter
+    InvalidItem(char),
+    /// A rucksack whose items can't be split in two compartments of the same size
+    OddItemCount(usize),
+    /// The rucksacks (or compartments) share more or less than one item type
+    SharedItems(usize),
+    /// The last group of elves has less than three rucksacks
+    IncompleteGroup(usize),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidItem(c) => {
+                write!(f, "expected an item (a-z or A-Z), found '{}'", c)
+            }
+            ParseError::OddItemCount(count) => {
+                write!(f, "expected an even number of items, found {}", count)
+            }
+            ParseError::SharedItems(count) => {
+                write!(f, "expected exactly one shared item, found {}", count)
+            }
+            ParseError::IncompleteGroup(count) => {
+                write!(f, "expected a group of three rucksacks, found {}", count)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
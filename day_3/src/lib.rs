@@ -0,0 +1,3 @@
+pub mod error;
+pub mod part1;
+pub mod part2;
@@ -1,20 +1,23 @@
 use std::collections::HashSet;
-use std::error::Error;
-use std::io;
 
-pub fn main_p1() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
-    let mut priority: u32 = 0;
+use aoc_core::{column, Answer, Locate, Located, Solution, SolveError};
 
-    for line in lines {
-        let l = line.unwrap();
+use crate::error::ParseError;
 
-        priority += rucksacks_priority(l);
-    }
+pub struct PartOne;
+
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut priority: u32 = 0;
 
-    println!("Part1: Total Priority: {}", priority);
+        for (index, line) in input.lines().enumerate() {
+            let line = parse_items(line, index + 1)?;
+
+            priority += rucksacks_priority(line.to_string()).at(index + 1, 1)?;
+        }
 
-    Ok(())
+        Ok(priority.into())
+    }
 }
 
 pub struct Rucksack {
@@ -42,7 +45,7 @@ impl Rucksack {
         self.count += 1;
     }
 
-    fn get_priority(&self) -> u32 {
+    fn get_priority(&self) -> Result<u32, ParseError> {
         let dup: HashSet<char> = self
             .compartment
             .0
@@ -50,26 +53,40 @@ impl Rucksack {
             .cloned()
             .collect();
 
-        assert!(dup.len() == 1);
+        if dup.len() != 1 {
+            return Err(ParseError::SharedItems(dup.len()));
+        }
 
         let value = dup.iter().nth(0).unwrap();
 
-        if value.is_lowercase() {
-            *value as u32 - 'a' as u32 + 1
-        } else if value.is_uppercase() {
-            *value as u32 - 'A' as u32 + 27
+        if value.is_ascii_lowercase() {
+            Ok(*value as u32 - 'a' as u32 + 1)
+        } else if value.is_ascii_uppercase() {
+            Ok(*value as u32 - 'A' as u32 + 27)
         } else {
-            panic!("Character not recognized");
+            Err(ParseError::InvalidItem(*value))
+        }
+    }
+}
+
+/// Check that all the items of the rucksack on `line` are letters
+fn parse_items(line: &str, line_number: usize) -> Result<&str, Located<ParseError>> {
+    match line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+        Some((offset, c)) => {
+            Err(ParseError::InvalidItem(c)).at(line_number, column(line, &line[offset..]))
         }
+        None => Ok(line),
     }
 }
 
-fn rucksacks_priority(input: String) -> u32 {
-    assert!(input.len() % 2 == 0);
+fn rucksacks_priority(input: String) -> Result<u32, ParseError> {
+    if !input.len().is_multiple_of(2) {
+        return Err(ParseError::OddItemCount(input.len()));
+    }
 
     let mut rucksack = Rucksack::new(input.len());
 
-    input.chars().into_iter().for_each(|c| rucksack.fill(c));
+    input.chars().for_each(|c| rucksack.fill(c));
 
     rucksack.get_priority()
 }
@@ -78,31 +95,62 @@ fn rucksacks_priority(input: String) -> u32 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn example() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw\n";
+
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(157u32));
+    }
+
     #[test]
     fn size_two_letter_a() {
         let input = String::from("aa");
 
-        assert_eq!(rucksacks_priority(input), 1);
+        assert_eq!(rucksacks_priority(input), Ok(1));
     }
 
     #[test]
     fn size_four_letter_a_uppercase() {
         let input = String::from("lAdA");
 
-        assert_eq!(rucksacks_priority(input), 27);
+        assert_eq!(rucksacks_priority(input), Ok(27));
     }
 
     #[test]
     fn size_six_letter_v() {
         let input = String::from("lBvdvv");
 
-        assert_eq!(rucksacks_priority(input), 22);
+        assert_eq!(rucksacks_priority(input), Ok(22));
     }
 
     #[test]
     fn size_eight_letter_z_uppercase() {
         let input = String::from("ZZBBdvAZ");
 
-        assert_eq!(rucksacks_priority(input), 52);
+        assert_eq!(rucksacks_priority(input), Ok(52));
+    }
+
+    #[test]
+    fn odd_item_count() {
+        let input = String::from("aab");
+
+        assert_eq!(rucksacks_priority(input), Err(ParseError::OddItemCount(3)));
+    }
+
+    #[test]
+    fn no_shared_item() {
+        let input = String::from("abcd");
+
+        assert_eq!(rucksacks_priority(input), Err(ParseError::SharedItems(0)));
+    }
+
+    #[test]
+    fn invalid_item_line() {
+        let error = PartOne::solve("aa\nab-b\n").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid input: line 2, column 3: expected an item (a-z or A-Z), found '-'"
+        );
     }
 }
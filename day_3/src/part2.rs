@@ -1,61 +1,91 @@
 use std::collections::HashSet;
-use std::error::Error;
-use std::io;
-
-pub fn main_p2() -> Result<(), Box<dyn Error>> {
-    let mut lines = io::stdin().lines();
-    let mut priority: u32 = 0;
-
-    loop {
-        // Break condition
-        let first = lines.next();
-        if first.is_none() {
-            break;
-        }
 
-        let first = first.unwrap().unwrap();
-        let second = lines.next().unwrap().unwrap();
-        let third = lines.next().unwrap().unwrap();
+use aoc_core::{column, Answer, Locate, Located, Solution, SolveError};
 
-        priority += compute(first, second, third);
-    }
+use crate::error::ParseError;
+
+pub struct PartTwo;
+
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut lines = input.lines().enumerate();
+        let mut priority: u32 = 0;
+
+        loop {
+            // Break condition
+            let first = lines.next();
+            if first.is_none() {
+                break;
+            }
+
+            let (index, first) = first.unwrap();
+            let first = parse_items(first, index + 1)?.to_string();
 
-    println!("Part2: Total priority: {}", priority);
+            let mut group = vec![first];
+            for (index, line) in lines.by_ref().take(2) {
+                group.push(parse_items(line, index + 1)?.to_string());
+            }
 
-    Ok(())
+            let [first, second, third] = <[String; 3]>::try_from(group).map_err(|group| {
+                Located::new(index + 1, 1, ParseError::IncompleteGroup(group.len()))
+            })?;
+
+            priority += compute(first, second, third).at(index + 1, 1)?;
+        }
+
+        Ok(priority.into())
+    }
 }
 
-fn compute(first: String, second: String, third: String) -> u32 {
+/// Check that all the items of the rucksack on `line` are letters
+fn parse_items(line: &str, line_number: usize) -> Result<&str, Located<ParseError>> {
+    match line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+        Some((offset, c)) => {
+            Err(ParseError::InvalidItem(c)).at(line_number, column(line, &line[offset..]))
+        }
+        None => Ok(line),
+    }
+}
+
+fn compute(first: String, second: String, third: String) -> Result<u32, ParseError> {
     let inputs = [first, second, third];
 
     let mut rucksacks = [HashSet::new(), HashSet::new(), HashSet::new()];
 
     for i in 0..3 {
-        inputs[i].chars().into_iter().for_each(|c| {
+        inputs[i].chars().for_each(|c| {
             let _ = rucksacks[i].insert(c);
-            ()
         });
     }
 
     let one_two: HashSet<char> = rucksacks[0].intersection(&rucksacks[1]).cloned().collect();
     let two_three: HashSet<char> = one_two.intersection(&rucksacks[2]).cloned().collect();
 
-    assert!(two_three.len() == 1);
+    if two_three.len() != 1 {
+        return Err(ParseError::SharedItems(two_three.len()));
+    }
 
-    let value = two_three.iter().nth(0).unwrap();
+    let value = two_three.iter().next().unwrap();
 
-    if value.is_lowercase() {
-        *value as u32 - 'a' as u32 + 1
-    } else if value.is_uppercase() {
-        *value as u32 - 'A' as u32 + 27
+    if value.is_ascii_lowercase() {
+        Ok(*value as u32 - 'a' as u32 + 1)
+    } else if value.is_ascii_uppercase() {
+        Ok(*value as u32 - 'A' as u32 + 27)
     } else {
-        panic!("Character not recognized");
+        Err(ParseError::InvalidItem(*value))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::compute;
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw\n";
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(70u32));
+    }
 
     #[test]
     fn simple() {
@@ -65,7 +95,7 @@ mod tests {
 
         let value = compute(first, second, third);
 
-        assert_eq!(value, 18);
+        assert_eq!(value, Ok(18));
     }
 
     #[test]
@@ -76,6 +106,16 @@ mod tests {
 
         let value = compute(first, second, third);
 
-        assert_eq!(value, 52);
+        assert_eq!(value, Ok(52));
+    }
+
+    #[test]
+    fn incomplete_group() {
+        let error = PartTwo::solve("aa\nab\nac\nbb\nbc\n").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid input: line 4, column 1: expected a group of three rucksacks, found 2"
+        );
     }
 }
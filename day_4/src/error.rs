@@ -0,0 +1,26 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading the section assignments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A section ID that isn't a number
+    InvalidSection(String),
+    /// The separator of the two ranges (`,`) or of a range bounds (`-`) is missing
+    MissingSeparator(char, String),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidSection(found) => {
+                write!(f, "expected a section ID, found '{}'", found)
+            }
+            ParseError::MissingSeparator(separator, found) => {
+                write!(f, "expected '{}' in '{}'", separator, found)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
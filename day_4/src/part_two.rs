@@ -1,5 +1,8 @@
-use std::error::Error;
-use std::io;
+use std::str::FromStr;
+
+use aoc_core::{column, Answer, Locate, Solution, SolveError};
+
+use crate::error::ParseError;
 
 struct Sections {
     start: u32,
@@ -12,31 +15,71 @@ impl Sections {
     }
 }
 
-pub fn main_p2() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
-    let mut overlap: u32 = 0;
+impl FromStr for Sections {
+    type Err = ParseError;
 
-    for line in lines {
-        let l = line.unwrap();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| ParseError::MissingSeparator('-', s.to_string()))?;
 
-        let elements: Vec<&str> = l.split_terminator(['-', ',']).collect();
-        let first = Sections::new(
-            elements[0].to_string().parse().unwrap(),
-            elements[1].to_string().parse().unwrap(),
-        );
-        let second = Sections::new(
-            elements[2].to_string().parse().unwrap(),
-            elements[3].to_string().parse().unwrap(),
-        );
+        let start = start
+            .parse()
+            .map_err(|_| ParseError::InvalidSection(start.to_string()))?;
+        let end = end
+            .parse()
+            .map_err(|_| ParseError::InvalidSection(end.to_string()))?;
+
+        Ok(Sections::new(start, end))
+    }
+}
+
+pub struct PartTwo;
+
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut overlap: u32 = 0;
+
+        for (index, line) in input.lines().enumerate() {
+            let (first, second) = line
+                .split_once(',')
+                .ok_or_else(|| ParseError::MissingSeparator(',', line.to_string()))
+                .at(index + 1, 1)?;
 
-        overlap += if first.end < second.start || second.end < first.start {
-            0
-        } else {
-            1
+            let first: Sections = first.parse().at(index + 1, column(line, first))?;
+            let second: Sections = second.parse().at(index + 1, column(line, second))?;
+
+            overlap += if first.end < second.start || second.end < first.start {
+                0
+            } else {
+                1
+            }
         }
+
+        Ok(overlap.into())
     }
+}
 
-    println!("Part1: Total overlaps: {}", overlap);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok(())
+    #[test]
+    fn example() {
+        let input = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n";
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(4u32));
+    }
+
+    #[test]
+    fn missing_separator() {
+        assert_eq!(
+            "2".parse::<Sections>().err(),
+            Some(ParseError::MissingSeparator('-', String::from("2")))
+        );
+        assert_eq!(
+            PartTwo::solve("2-4 6-8\n").unwrap_err().to_string(),
+            "Invalid input: line 1, column 1: expected ',' in '2-4 6-8'"
+        );
+    }
 }
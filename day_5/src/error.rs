@@ -0,0 +1,46 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading the stacks of crates and the rearrangement procedure
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A crate that isn't an uppercase letter
+    InvalidCrate(char),
+    /// The empty line between the stacks and the procedure is missing
+    MissingSeparator,
+    /// A word of a move that isn't the expected one
+    UnexpectedWord {
+        expected: &'static str,
+        found: String,
+    },
+    /// A count of crates or a stack number that isn't a number
+    InvalidNumber(String),
+    /// A move from or to a stack that doesn't exist
+    UnknownStack(usize),
+    /// A move of more crates than the stack holds
+    EmptyStack(usize),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidCrate(c) => {
+                write!(f, "expected a crate (A-Z), found '{}'", c)
+            }
+            ParseError::MissingSeparator => {
+                write!(
+                    f,
+                    "expected an empty line after the stacks, found the end of the input"
+                )
+            }
+            ParseError::UnexpectedWord { expected, found } => {
+                write!(f, "expected '{}', found '{}'", expected, found)
+            }
+            ParseError::InvalidNumber(found) => write!(f, "expected a number, found '{}'", found),
+            ParseError::UnknownStack(stack) => write!(f, "stack {} doesn't exist", stack),
+            ParseError::EmptyStack(stack) => write!(f, "stack {} has no crate left", stack),
+        }
+    }
+}
+
+impl Error for ParseError {}
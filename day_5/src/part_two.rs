@@ -1,91 +1,189 @@
-use std::error::Error;
-use std::io;
-
-pub fn main_p2() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
-    let mut containers = Vec::<Vec<char>>::new();
-    let mut lines_it = lines.into_iter();
-
-    // 1. Parse cargo containers
-    loop {
-        let line = lines_it.next().unwrap().unwrap();
-        if line.is_empty() {
-            break; // We have finished parsing the cargo
-        }
+use aoc_core::{column, Answer, Locate, Located, Solution, SolveError};
+
+use crate::error::ParseError;
+
+pub struct PartTwo;
 
-        let mut it = line.chars();
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut containers = Vec::<Vec<char>>::new();
+        let mut lines_it = input.lines().enumerate();
+        let mut line_number = 0;
 
-        let mut row = Vec::<char>::new();
+        // 1. Parse cargo containers
         loop {
-            if it.next().is_none() {
-                // '\n'
-                break;
+            line_number += 1;
+            let (_, line) = lines_it
+                .next()
+                .ok_or(ParseError::MissingSeparator)
+                .at(line_number, 1)?;
+            if line.is_empty() {
+                break; // We have finished parsing the cargo
             }
-            // '['
-            let value = it.next();
-            let value = value.unwrap();
-            if value.is_ascii_uppercase() {
-                row.push(value);
-            } else if value.is_ascii_digit() {
-                break;
-            } else {
-                row.push('\0');
+
+            let mut it = line.chars();
+            let mut col = 1;
+
+            let mut row = Vec::<char>::new();
+            loop {
+                if it.next().is_none() {
+                    // '\n'
+                    break;
+                }
+                // '['
+                let value = it.next().unwrap_or(' ');
+                if value.is_ascii_uppercase() {
+                    row.push(value);
+                } else if value.is_ascii_digit() {
+                    break;
+                } else if value == ' ' {
+                    row.push('\0');
+                } else {
+                    let error = ParseError::InvalidCrate(value);
+                    return Err(Located::new(line_number, col + 1, error).into());
+                }
+                it.next(); // ']'
+                it.next(); // ' '
+                col += 4;
             }
-            it.next(); // ']'
-            it.next(); // ' '
-        }
 
-        if containers.is_empty() {
-            for _ in 0..row.len() {
-                containers.push(Vec::new());
+            // Lines can be shorter than the last stack if their end is trimmed
+            containers.resize(containers.len().max(row.len()), Vec::new());
+
+            for i in 0..row.len() {
+                if row[i] != '\0' {
+                    containers[i].push(row[i]);
+                }
             }
         }
 
-        for i in 0..row.len() {
-            if row[i] != '\0' {
-                containers[i].push(row[i]);
-            }
+        // 1.5 Reverse the containers
+        for container in &mut containers {
+            container.reverse();
         }
-    }
 
-    // 1.5 Reverse the containers
-    for i in 0..containers.len() {
-        containers[i].reverse();
-    }
+        // 2. Parse the move
+        loop {
+            let line = lines_it.next();
+            if line.is_none() {
+                break; // EOF
+            }
+
+            let (index, line) = line.unwrap();
+            let Move {
+                count: moves,
+                from: source,
+                to: dest,
+            } = Move::parse(line, index + 1)?;
+
+            for stack in [source, dest] {
+                if stack == 0 || stack > containers.len() {
+                    let error = ParseError::UnknownStack(stack);
+                    return Err(Located::new(index + 1, 1, error).into());
+                }
+            }
+
+            // 3. Do the move
+            let mut crane = Vec::<char>::new();
+            for _ in 0..moves {
+                let value = containers[source - 1]
+                    .pop()
+                    .ok_or(ParseError::EmptyStack(source))
+                    .at(index + 1, 1)?;
+                crane.push(value);
+            }
 
-    // 2. Parse the move
-    loop {
-        let line = lines_it.next();
-        if line.is_none() {
-            break; // EOF
+            for _ in 0..moves {
+                let value = crane.pop().unwrap();
+                containers[dest - 1].push(value);
+            }
         }
 
-        let line = line.unwrap().unwrap();
-        let data: Vec<&str> = line.split(' ').collect();
+        let top: String = containers
+            .iter_mut()
+            .filter_map(|container| container.pop())
+            .collect();
+
+        Ok(top.into())
+    }
+}
+
+struct Move {
+    count: u32,
+    from: usize,
+    to: usize,
+}
 
-        let moves: u32 = data[1].parse().unwrap();
-        let source: usize = data[3].parse().unwrap();
-        let dest: usize = data[5].parse().unwrap();
+impl Move {
+    /// Parse a `move <count> from <stack> to <stack>` line
+    fn parse(line: &str, line_number: usize) -> Result<Self, Located<ParseError>> {
+        let mut words = line.split(' ');
+        let end_of_line = &line[line.len()..];
+        let mut numbers = [0; 3];
 
-        println!("Perform {moves} moves from {source} to {dest}");
+        for (keyword, number) in ["move", "from", "to"].into_iter().zip(numbers.iter_mut()) {
+            let word = words.next().unwrap_or(end_of_line);
+            if word != keyword {
+                let error = ParseError::UnexpectedWord {
+                    expected: keyword,
+                    found: word.to_string(),
+                };
+                return Err(error).at(line_number, column(line, word));
+            }
 
-        // 3. Do the move
-        let mut crane = Vec::<char>::new();
-        for _ in 0..moves {
-            let value = containers[source - 1].pop().unwrap();
-            crane.push(value);
+            let word = words.next().unwrap_or(end_of_line);
+            *number = word
+                .parse()
+                .map_err(|_| ParseError::InvalidNumber(word.to_string()))
+                .at(line_number, column(line, word))?;
         }
 
-        for _ in 0..moves {
-            let value = crane.pop().unwrap();
-            containers[dest - 1].push(value);
-        }
+        Ok(Move {
+            count: numbers[0] as u32,
+            from: numbers[1],
+            to: numbers[2],
+        })
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = concat!(
+            "    [D]    \n",
+            "[N] [C]    \n",
+            "[Z] [M] [P]\n",
+            " 1   2   3 \n",
+            "\n",
+            "move 1 from 2 to 1\n",
+            "move 3 from 1 to 3\n",
+            "move 2 from 2 to 1\n",
+            "move 1 from 1 to 2\n"
+        );
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from("MCD"));
+    }
+
+    #[test]
+    fn unknown_stack() {
+        let input = "[A] [B]\n 1   2\n\nmove 1 from 1 to 3\n";
 
-    for i in 0..containers.len() {
-        print!("{}", containers[i].pop().unwrap());
+        assert_eq!(
+            PartTwo::solve(input).unwrap_err().to_string(),
+            "Invalid input: line 4, column 1: stack 3 doesn't exist"
+        );
     }
-    println!("");
 
-    Ok(())
+    #[test]
+    fn empty_stack() {
+        let input = "[A] [B]\n 1   2\n\nmove 2 from 1 to 2\n";
+
+        assert_eq!(
+            PartTwo::solve(input).unwrap_err().to_string(),
+            "Invalid input: line 4, column 1: stack 1 has no crate left"
+        );
+    }
 }
@@ -0,0 +1,26 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading the datastream
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The datastream is shorter than a single marker
+    TooShort { expected: usize, found: usize },
+    /// No sequence of the datastream is made of different characters
+    NoMarker,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::TooShort { expected, found } => write!(
+                f,
+                "expected at least {} characters, found {}",
+                expected, found
+            ),
+            ParseError::NoMarker => write!(f, "expected a marker, found none"),
+        }
+    }
+}
+
+impl Error for ParseError {}
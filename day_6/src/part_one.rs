@@ -1,85 +1,116 @@
 use std::collections::VecDeque;
-use std::error::Error;
-use std::io;
 
-pub fn main_p1() -> Result<(), Box<dyn Error>> {
-    let mut lines = io::stdin().lines();
+use aoc_core::{Answer, Locate, Solution, SolveError};
 
-    let line = lines.next().unwrap().unwrap();
+use crate::error::ParseError;
 
-    let marker = marker_index(line);
+pub struct PartOne;
 
-    println!("Marker start at char number {}", marker);
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let line = input.lines().next().unwrap_or_default();
 
-    Ok(())
+        Ok(marker_index(line.to_string()).at(1, 1)?.into())
+    }
 }
 
-fn marker_index(packet: String) -> u32 {
+fn marker_index(packet: String) -> Result<u32, ParseError> {
     let mut chars = packet.chars();
     let mut buffer = VecDeque::<char>::with_capacity(4);
     let mut index = 4;
 
-    buffer.push_back(chars.next().unwrap());
-    buffer.push_back(chars.next().unwrap());
-    buffer.push_back(chars.next().unwrap());
-    buffer.push_back(chars.next().unwrap());
+    for _ in 0..4 {
+        let c = chars.next().ok_or_else(|| ParseError::TooShort {
+            expected: 4,
+            found: packet.chars().count(),
+        })?;
+        buffer.push_back(c);
+    }
 
-    for c in chars {
+    loop {
         // Check if 4 chars aren't the same
-        if buffer.get(0) != buffer.get(1)
-            && buffer.get(0) != buffer.get(2)
-            && buffer.get(0) != buffer.get(3)
+        if buffer.front() != buffer.get(1)
+            && buffer.front() != buffer.get(2)
+            && buffer.front() != buffer.get(3)
             && buffer.get(1) != buffer.get(2)
             && buffer.get(1) != buffer.get(3)
             && buffer.get(2) != buffer.get(3)
         {
-            break;
+            return Ok(index);
         }
 
+        let c = chars.next().ok_or(ParseError::NoMarker)?;
         buffer.push_back(c);
         buffer.pop_front();
         index += 1;
     }
-
-    index
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn example() {
+        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n";
+
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(7u32));
+    }
+
     #[test]
     fn first_four_is_marker() {
         let line = String::from("jlmp");
 
-        assert_eq!(marker_index(line), 4);
+        assert_eq!(marker_index(line), Ok(4));
     }
 
     #[test]
     fn marker_after_five() {
         let line = String::from("bvwbjplbgvbhsrlpgdmjqwftvncz");
 
-        assert_eq!(marker_index(line), 5);
+        assert_eq!(marker_index(line), Ok(5));
     }
 
     #[test]
     fn marker_after_six() {
         let line = String::from("nppdvjthqldpwncqszvftbrmjlhg");
 
-        assert_eq!(marker_index(line), 6);
+        assert_eq!(marker_index(line), Ok(6));
     }
 
     #[test]
     fn marker_after_ten() {
         let line = String::from("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg");
 
-        assert_eq!(marker_index(line), 10);
+        assert_eq!(marker_index(line), Ok(10));
     }
 
     #[test]
     fn marker_after_eleven() {
         let line = String::from("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw");
 
-        assert_eq!(marker_index(line), 11);
+        assert_eq!(marker_index(line), Ok(11));
+    }
+
+    #[test]
+    fn marker_at_the_end() {
+        let line = String::from("aaaabcd");
+
+        assert_eq!(marker_index(line), Ok(7));
+    }
+
+    #[test]
+    fn no_marker() {
+        let line = String::from("abcabcabc");
+
+        assert_eq!(marker_index(line), Err(ParseError::NoMarker));
+    }
+
+    #[test]
+    fn too_short() {
+        assert_eq!(
+            PartOne::solve("abc\n").unwrap_err().to_string(),
+            "Invalid input: line 1, column 1: expected at least 4 characters, found 3"
+        );
     }
 }
@@ -1,29 +1,33 @@
 use std::collections::{HashSet, VecDeque};
-use std::error::Error;
-use std::io;
 
-pub fn main_p2() -> Result<(), Box<dyn Error>> {
-    let mut lines = io::stdin().lines();
+use aoc_core::{Answer, Locate, Solution, SolveError};
 
-    let line = lines.next().unwrap().unwrap();
+use crate::error::ParseError;
 
-    let marker = marker_index(line);
+pub struct PartTwo;
 
-    println!("Marker start at char number {}", marker);
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let line = input.lines().next().unwrap_or_default();
 
-    Ok(())
+        Ok(marker_index(line.to_string()).at(1, 1)?.into())
+    }
 }
 
-fn marker_index(packet: String) -> u32 {
+fn marker_index(packet: String) -> Result<u32, ParseError> {
     let mut chars = packet.chars();
     let mut buffer = VecDeque::<char>::with_capacity(4);
     let mut index = 14;
 
     for _ in 0..14 {
-        buffer.push_back(chars.next().unwrap());
+        let c = chars.next().ok_or_else(|| ParseError::TooShort {
+            expected: 14,
+            found: packet.chars().count(),
+        })?;
+        buffer.push_back(c);
     }
 
-    for c in chars {
+    loop {
         let mut set = HashSet::<char>::new();
 
         let res = buffer
@@ -31,54 +35,73 @@ fn marker_index(packet: String) -> u32 {
             .try_for_each(move |c| if set.insert(*c) { Ok(()) } else { Err(()) });
 
         if res.is_ok() {
-            break;
+            return Ok(index);
         }
 
+        let c = chars.next().ok_or(ParseError::NoMarker)?;
         buffer.push_back(c);
         buffer.pop_front();
 
         index += 1;
     }
-
-    index
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn example() {
+        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n";
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(19u32));
+    }
+
     #[test]
     fn marker_after_nineten() {
         let line = String::from("mjqjpqmgbljsphdztnvjfqwrcgsmlb");
 
-        assert_eq!(marker_index(line), 19);
+        assert_eq!(marker_index(line), Ok(19));
     }
 
     #[test]
     fn marker_after_twentythree() {
         let line = String::from("bvwbjplbgvbhsrlpgdmjqwftvncz");
 
-        assert_eq!(marker_index(line), 23);
+        assert_eq!(marker_index(line), Ok(23));
     }
 
     #[test]
     fn marker_after_twentythree_two() {
         let line = String::from("nppdvjthqldpwncqszvftbrmjlhg");
 
-        assert_eq!(marker_index(line), 23);
+        assert_eq!(marker_index(line), Ok(23));
     }
 
     #[test]
     fn marker_after_twentysix() {
         let line = String::from("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw");
 
-        assert_eq!(marker_index(line), 26);
+        assert_eq!(marker_index(line), Ok(26));
     }
 
     #[test]
     fn marker_after_twentynine() {
         let line = String::from("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg");
 
-        assert_eq!(marker_index(line), 29);
+        assert_eq!(marker_index(line), Ok(29));
+    }
+
+    #[test]
+    fn too_short() {
+        let line = String::from("abcdefghijklm");
+
+        assert_eq!(
+            marker_index(line),
+            Err(ParseError::TooShort {
+                expected: 14,
+                found: 13
+            })
+        );
     }
 }
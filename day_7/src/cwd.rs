@@ -1,26 +1,22 @@
 use std::{error::Error, fmt::Display, path::PathBuf};
 
-#[derive(Debug)]
-pub struct CWDError {}
-
-impl CWDError {
-    pub fn new() -> Self {
-        CWDError {}
-    }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CWDError {
+    /// `cd ..` from the root directory
+    AboveRoot,
 }
 
 impl Display for CWDError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", "..")
+        match self {
+            CWDError::AboveRoot => write!(f, "can't move above the root directory"),
+        }
     }
 }
 
-impl Error for CWDError {
-    fn description(&self) -> &str {
-        return "Error: Going to directory failed.";
-    }
-}
+impl Error for CWDError {}
 
+#[derive(Debug, Default)]
 pub struct CurrentWorkingDirectory {
     path: PathBuf,
 }
@@ -38,12 +34,12 @@ impl CurrentWorkingDirectory {
         }
     }
 
-    pub fn mv(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+    pub fn mv(&mut self, path: &str) -> Result<(), CWDError> {
         match path {
             "/" => self.path = PathBuf::from("/"),
             ".." => {
                 if !self.path.pop() {
-                    return Err(Box::new(CWDError::new()));
+                    return Err(CWDError::AboveRoot);
                 }
             }
             name => self.path.push(name),
@@ -0,0 +1,94 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::cwd::CWDError;
+use crate::tree::TreeError;
+
+/// Errors found while reading the terminal output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A command other than `cd` and `ls`
+    UnknownCommand(String),
+    /// A `cd` without the directory to move to
+    MissingDirectory,
+    /// Something after a command that takes nothing
+    UnexpectedOperand(String),
+    /// An entry of `ls` without its name
+    MissingName,
+    /// A file size that isn't a number
+    InvalidSize(String),
+    /// A line of output while no `ls` is running
+    UnexpectedOutput,
+    /// A name printed twice by the same `ls`
+    DuplicateEntry(String),
+    /// An entry that doesn't match the previous listing of its directory
+    ConflictingEntry(String),
+    /// An entry of the previous listing of the directory that `ls` didn't print again
+    MissingEntry(String),
+    /// Moving to another directory failed
+    Cwd(CWDError),
+    /// Adding a directory or a file to the tree failed
+    Tree(TreeError),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnknownCommand(command) => {
+                write!(f, "expected a command (cd or ls), found '{}'", command)
+            }
+            ParseError::MissingDirectory => {
+                write!(f, "expected a directory, found the end of the line")
+            }
+            ParseError::UnexpectedOperand(operand) => {
+                write!(f, "expected the end of the command, found '{}'", operand)
+            }
+            ParseError::MissingName => write!(f, "expected a name, found the end of the line"),
+            ParseError::InvalidSize(size) => {
+                write!(f, "expected 'dir' or a file size, found '{}'", size)
+            }
+            ParseError::UnexpectedOutput => {
+                write!(f, "expected a command ('$'), found output outside of 'ls'")
+            }
+            ParseError::DuplicateEntry(name) => {
+                write!(f, "'{}' is listed twice in the same directory", name)
+            }
+            ParseError::ConflictingEntry(name) => write!(
+                f,
+                "'{}' doesn't match the previous listing of the directory",
+                name
+            ),
+            ParseError::MissingEntry(name) => {
+                write!(
+                    f,
+                    "'{}' was listed before but is missing from the listing",
+                    name
+                )
+            }
+            ParseError::Cwd(e) => write!(f, "{}", e),
+            ParseError::Tree(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::Cwd(e) => Some(e),
+            ParseError::Tree(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<CWDError> for ParseError {
+    fn from(e: CWDError) -> Self {
+        ParseError::Cwd(e)
+    }
+}
+
+impl From<TreeError> for ParseError {
+    fn from(e: TreeError) -> Self {
+        ParseError::Tree(e)
+    }
+}
@@ -0,0 +1,320 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Component, Path};
+
+use crate::tree::{Dir, File, Node, NodeType, Tree};
+
+/// Size of a tar block, headers and data being padded to it
+const BLOCK: usize = 512;
+
+/// Largest file size written in the 11 octal digits of a tar header
+const MAX_TAR_SIZE: usize = 0o77777777777;
+
+/// Create the directories of `tree` under `target`, and its files as sparse files of their size
+pub fn materialise(tree: &Tree, target: &Path) -> io::Result<()> {
+    fs::create_dir_all(target)?;
+
+    for (path, node) in tree.walk().skip(1) {
+        let path = target.join(relative(&path)?);
+
+        match node.get_type() {
+            NodeType::Directory => fs::create_dir_all(&path)?,
+            NodeType::File => fs::File::create(&path)?.set_len(node.size() as u64)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Write `tree` as an uncompressed POSIX (ustar) archive, its files being full of zeros
+pub fn write_tar<W: Write>(tree: &Tree, mut out: W) -> io::Result<()> {
+    let zeros = [0; BLOCK];
+
+    for (path, node) in tree.walk().skip(1) {
+        let mut name = relative(&path)?.to_string_lossy().to_string();
+
+        match node.get_type() {
+            NodeType::Directory => {
+                name.push('/');
+                out.write_all(&tar_header(&name, 0, b'5')?)?;
+            }
+            NodeType::File => {
+                let size = node.size();
+                out.write_all(&tar_header(&name, size, b'0')?)?;
+
+                let mut left = size.next_multiple_of(BLOCK);
+                while left > 0 {
+                    let len = left.min(BLOCK);
+                    out.write_all(&zeros[..len])?;
+                    left -= len;
+                }
+            }
+        }
+    }
+
+    // The end of the archive is marked by two empty blocks
+    out.write_all(&zeros)?;
+    out.write_all(&zeros)?;
+    out.flush()
+}
+
+/// Rebuild a tree from the directory at `root`, with the size of its files.
+///
+/// Entries are sorted by name, and anything but directories and regular files is left out.
+pub fn scan(root: &Path) -> io::Result<Tree> {
+    let mut tree = Tree::new();
+
+    for child in scan_children(root)? {
+        tree.add_child(child).map_err(invalid)?;
+    }
+
+    Ok(tree)
+}
+
+/// Transcript of a terminal session listing every directory of `tree`, parents first
+pub fn transcript(tree: &Tree) -> String {
+    let mut transcript = String::from("$ cd /\n");
+
+    write_listing(tree.children(), &mut transcript);
+    transcript
+}
+
+fn write_listing(children: &[Box<dyn Node>], transcript: &mut String) {
+    transcript.push_str("$ ls\n");
+
+    for child in children {
+        match child.get_type() {
+            NodeType::Directory => writeln!(transcript, "dir {}", child.name()).unwrap(),
+            NodeType::File => writeln!(transcript, "{} {}", child.size(), child.name()).unwrap(),
+        }
+    }
+
+    for child in children {
+        if child.get_type() == NodeType::Directory {
+            writeln!(transcript, "$ cd {}", child.name()).unwrap();
+            write_listing(child.children(), transcript);
+            transcript.push_str("$ cd ..\n");
+        }
+    }
+}
+
+fn scan_children(dir: &Path) -> io::Result<Vec<Box<dyn Node>>> {
+    let mut entries = fs::read_dir(dir)?.collect::<io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    let mut children: Vec<Box<dyn Node>> = Vec::new();
+
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.contains(char::is_whitespace) {
+            return Err(invalid(format!(
+                "'{}' can't be listed by ls in a transcript",
+                name
+            )));
+        }
+
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            let mut child = Dir::new(name);
+            for grandchild in scan_children(&entry.path())? {
+                child.add_child(grandchild).map_err(invalid)?;
+            }
+            children.push(Box::new(child));
+        } else if file_type.is_file() {
+            let size = entry.metadata()?.len() as usize;
+            children.push(Box::new(File::new(name, size)));
+        }
+    }
+
+    Ok(children)
+}
+
+/// `path` of the tree without its root, checking that no name escapes the directory
+fn relative(path: &Path) -> io::Result<&Path> {
+    let relative = path.strip_prefix("/").unwrap_or(path);
+
+    if relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        Ok(relative)
+    } else {
+        Err(invalid(format!("'{}' isn't a plain path", path.display())))
+    }
+}
+
+/// Header block of a tar entry, as described by POSIX for the ustar format
+fn tar_header(name: &str, size: usize, kind: u8) -> io::Result<[u8; BLOCK]> {
+    let mut header = [0; BLOCK];
+
+    // Long names are split at a '/' between the prefix and the name fields, the prefix
+    // holding at most 155 bytes
+    let (prefix, name) = if name.len() <= 100 {
+        ("", name)
+    } else {
+        let end = (0..=name.len().min(156))
+            .rev()
+            .find(|&end| name.is_char_boundary(end))
+            .unwrap_or(0);
+        let split = name[..end]
+            .rfind('/')
+            .filter(|&split| name.len() - split - 1 <= 100)
+            .ok_or_else(|| invalid(format!("'{}' is too long for a tar archive", name)))?;
+        (&name[..split], &name[split + 1..])
+    };
+
+    if size > MAX_TAR_SIZE {
+        return Err(invalid(format!(
+            "'{}' is too large for a tar archive",
+            name
+        )));
+    }
+
+    let mode = if kind == b'5' { 0o755 } else { 0o644 };
+
+    header[..name.len()].copy_from_slice(name.as_bytes());
+    write_octal(&mut header[100..108], mode);
+    write_octal(&mut header[108..116], 0);
+    write_octal(&mut header[116..124], 0);
+    write_octal(&mut header[124..136], size);
+    write_octal(&mut header[136..148], 0);
+    header[156] = kind;
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+    header[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+
+    // The checksum is computed with its own field full of spaces
+    header[148..156].fill(b' ');
+    let checksum: usize = header.iter().map(|&byte| byte as usize).sum();
+    write_octal(&mut header[148..155], checksum);
+
+    Ok(header)
+}
+
+/// Write `value` in octal, padded with zeros and ended by a NUL byte
+fn write_octal(field: &mut [u8], value: usize) {
+    let digits = format!("{:0width$o}", value, width = field.len() - 1);
+
+    field[..digits.len()].copy_from_slice(digits.as_bytes());
+    field[digits.len()] = 0;
+}
+
+fn invalid<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shell::Shell;
+
+    const EXAMPLE: &str = include_str!("../simple_input.txt");
+
+    /// Temporary directory for `test` alone, removed if a previous run left it
+    fn scratch(test: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("day_7-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn transcript_round_trip() {
+        let tree = Shell::run(EXAMPLE).unwrap();
+        let written = transcript(&tree);
+
+        // Every directory is left once listed, the last one included
+        assert_eq!(written, format!("{}$ cd ..\n", EXAMPLE));
+        assert_eq!(transcript(&Shell::run(&written).unwrap()), written);
+    }
+
+    #[test]
+    fn materialise_and_scan() {
+        let dir = scratch("materialise");
+        let tree = Shell::run(EXAMPLE).unwrap();
+
+        materialise(&tree, &dir).unwrap();
+        assert_eq!(fs::metadata(dir.join("a/e/i")).unwrap().len(), 584);
+
+        let scanned = scan(&dir).unwrap();
+        assert_eq!(scanned.size(), tree.size());
+        assert_eq!(
+            scanned.dirs().collect::<Vec<_>>(),
+            tree.dirs().collect::<Vec<_>>()
+        );
+        assert_eq!(scanned.du("/d/k"), Some(7214296));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn materialise_refuses_escaping_names() {
+        let dir = scratch("escape");
+        let tree = Shell::run("$ ls\ndir ..\n").unwrap();
+
+        assert_eq!(
+            materialise(&tree, &dir).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn tar() {
+        let tree = Shell::run("$ ls\ndir a\n700 b\n$ cd a\n$ ls\n3 c\n").unwrap();
+        let mut archive = Vec::new();
+
+        write_tar(&tree, &mut archive).unwrap();
+
+        // a/, a/c and its block, b and its 2 blocks, then the end of the archive
+        assert_eq!(archive.len(), 8 * BLOCK);
+
+        let header = &archive[..BLOCK];
+        assert_eq!(&header[..3], b"a/\0");
+        assert_eq!(header[156], b'5');
+        assert_eq!(&header[257..263], b"ustar\0");
+
+        assert_eq!(&archive[BLOCK..BLOCK + 4], b"a/c\0");
+
+        let header = &archive[3 * BLOCK..4 * BLOCK];
+        assert_eq!(&header[..2], b"b\0");
+        assert_eq!(&header[124..136], b"00000001274\0");
+
+        let checksum: usize = header[..148]
+            .iter()
+            .chain(b"        ")
+            .chain(&header[156..])
+            .map(|&byte| byte as usize)
+            .sum();
+        assert_eq!(&header[148..155], format!("{:06o}\0", checksum).as_bytes());
+
+        assert!(archive[6 * BLOCK..].iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn tar_long_names() {
+        let long = "x".repeat(60);
+        let name = format!("{}/{}/{}", long, long, long);
+        let header = tar_header(&name, 0, b'0').unwrap();
+
+        assert_eq!(&header[..60], long.as_bytes());
+        assert_eq!(
+            &header[345..345 + 121],
+            format!("{}/{}", long, long).as_bytes()
+        );
+        assert!(tar_header(&"y".repeat(101), 0, b'0').is_err());
+    }
+
+    #[test]
+    fn tar_long_names_with_multibyte_characters() {
+        // 'é' takes bytes 155 and 156, so the prefix can't be cut at 156
+        let name = format!("{}é/{}", "x".repeat(155), "y".repeat(90));
+        assert!(tar_header(&name, 0, b'0').is_err());
+
+        let name = format!("{}/é{}", "x".repeat(154), "y".repeat(90));
+        let header = tar_header(&name, 0, b'0').unwrap();
+        assert_eq!(&header[..92], format!("é{}", "y".repeat(90)).as_bytes());
+        assert_eq!(&header[345..345 + 154], "x".repeat(154).as_bytes());
+    }
+}
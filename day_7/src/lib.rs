@@ -0,0 +1,9 @@
+pub mod cwd;
+pub mod error;
+pub mod export;
+pub mod part_one;
+pub mod part_two;
+pub mod plan;
+pub mod render;
+pub mod shell;
+pub mod tree;
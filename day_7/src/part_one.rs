@@ -1,60 +1,21 @@
-use std::error::Error;
-use std::io;
-use std::path::PathBuf;
+use aoc_core::{Answer, Solution, SolveError};
 
-use crate::cwd::CurrentWorkingDirectory;
-use crate::tree::{
-    Dir, File, Node, Tree, TreeDisplay, TreeMaxDirSize, TreeSizeVisitor, TreeVisitor,
-};
+use crate::shell::Shell;
 
-pub fn main_p1() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
-    let mut cwd = CurrentWorkingDirectory::new();
-    let mut fs = Tree::new();
+pub struct PartOne;
 
-    for line in lines {
-        let line = line.unwrap();
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let fs = Shell::run(input)?;
 
-        if line.starts_with("$") {
-            // Command parsing
-            let command: Vec<&str> = line.split(' ').collect();
-            match command[1] {
-                "cd" => {
-                    cwd.mv(command[2])?;
-                    println!("Updated CurrentWorkingDirectory: {}", cwd);
-                }
-                _ => (),
-            };
-        } else {
-            // Directory and File reading from `ls`
-            let info: Vec<&str> = line.split(' ').collect();
+        let size: usize = fs
+            .dirs()
+            .map(|(_, size)| size)
+            .filter(|&size| size < 100_000)
+            .sum();
 
-            match info[0] {
-                "dir" => {
-                    let dir = Dir::new(String::from(info[1]));
-                    println!("Creating {}", dir);
-                    let path = PathBuf::from(cwd.get());
-                    fs.add(&mut path.components(), Box::new(dir))?;
-                }
-                _ => {
-                    let file = File::new(String::from(info[1]), info[0].parse().unwrap());
-                    println!("Creating {}", file);
-                    let path = PathBuf::from(cwd.get());
-                    fs.add(&mut path.components(), Box::new(file))?;
-                }
-            }
-        }
+        Ok(size.into())
     }
-
-    let mut visitor = TreeDisplay::new();
-    visitor.visit_tree(&fs);
-
-    let mut size_visitor = TreeMaxDirSize::new(100_000);
-    size_visitor.visit_tree(&fs);
-
-    println!("Max directory size: {}", size_visitor.size);
-
-    Ok(())
 }
 
 #[cfg(test)]
@@ -62,7 +23,39 @@ mod tests {
     use super::*;
 
     #[test]
-    fn simple() {
-        let line = String::from("jlmp");
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(95437usize));
+    }
+
+    #[test]
+    fn unknown_command() {
+        let input = "$ cd /\n$ pwd\n";
+
+        assert_eq!(
+            PartOne::solve(input).unwrap_err().to_string(),
+            "Invalid input: line 2, column 3: expected a command (cd or ls), found 'pwd'"
+        );
+    }
+
+    #[test]
+    fn cd_above_root() {
+        let input = "$ cd /\n$ ls\ndir a\n$ cd a\n$ cd ..\n$ cd ..\n";
+
+        assert_eq!(
+            PartOne::solve(input).unwrap_err().to_string(),
+            "Invalid input: line 6, column 6: can't move above the root directory"
+        );
+    }
+
+    #[test]
+    fn invalid_size() {
+        let input = "$ cd /\n$ ls\n12k a.txt\n";
+
+        assert_eq!(
+            PartOne::solve(input).unwrap_err().to_string(),
+            "Invalid input: line 3, column 1: expected 'dir' or a file size, found '12k'"
+        );
     }
 }
@@ -1,68 +1,26 @@
-use std::error::Error;
-use std::io;
-use std::path::PathBuf;
-
-use crate::cwd::CurrentWorkingDirectory;
-use crate::tree::{
-    Dir, File, Node, Tree, TreeDisplay, TreeMaxDirSize, TreeSizeVisitor, TreeVisitor,
-};
-
-pub fn main_p2() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
-    let mut cwd = CurrentWorkingDirectory::new();
-    let mut fs = Tree::new();
-
-    for line in lines {
-        let line = line.unwrap();
-
-        if line.starts_with("$") {
-            // Command parsing
-            let command: Vec<&str> = line.split(' ').collect();
-            match command[1] {
-                "cd" => {
-                    cwd.mv(command[2])?;
-                    println!("Updated CurrentWorkingDirectory: {}", cwd);
-                }
-                _ => (),
-            };
-        } else {
-            // Directory and File reading from `ls`
-            let info: Vec<&str> = line.split(' ').collect();
-
-            match info[0] {
-                "dir" => {
-                    let dir = Dir::new(String::from(info[1]));
-                    println!("Creating {}", dir);
-                    let path = PathBuf::from(cwd.get());
-                    fs.add(&mut path.components(), Box::new(dir))?;
-                }
-                _ => {
-                    let file = File::new(String::from(info[1]), info[0].parse().unwrap());
-                    println!("Creating {}", file);
-                    let path = PathBuf::from(cwd.get());
-                    fs.add(&mut path.components(), Box::new(file))?;
-                }
-            }
-        }
-    }
+use aoc_core::{Answer, Solution, SolveError};
+
+use crate::plan::Planner;
+use crate::shell::Shell;
 
-    let mut visitor = TreeDisplay::new();
-    visitor.visit_tree(&fs);
+const DISK: usize = 70_000_000;
 
-    let mut size_visitor = TreeMaxDirSize::new(0);
-    let total_size = size_visitor.visit_tree(&fs);
+/// Free space needed by the update
+const REQUIRED: usize = 30_000_000;
 
-    println!("Total size: {}", total_size);
-    let free_size = 70_000_000 - total_size;
-    println!("Free size: {}", free_size);
-    let size_to_free = 30_000_000 - free_size;
-    println!("Size to free: {}", size_to_free);
+pub struct PartTwo;
 
-    size_visitor = TreeMaxDirSize::new(size_to_free);
-    size_visitor.visit_tree(&fs);
-    println!("Size freed: {}", size_visitor.dir_size);
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let fs = Shell::run(input)?;
 
-    Ok(())
+        // A single directory is deleted
+        let plan = Planner::new(DISK, REQUIRED, 1).plan(&fs).ok_or_else(|| {
+            SolveError::NoSolution(String::from("the disk is too small for the update"))
+        })?;
+
+        Ok(plan.freed.into())
+    }
 }
 
 #[cfg(test)]
@@ -70,7 +28,62 @@ mod tests {
     use super::*;
 
     #[test]
-    fn simple() {
-        let line = String::from("jlmp");
+    fn example() {
+        let input = include_str!("../simple_input.txt");
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(24933642usize));
+    }
+
+    #[test]
+    fn unknown_directory() {
+        let input = "$ cd /\n$ cd a\n$ ls\n10 b.txt\n";
+
+        assert_eq!(
+            PartTwo::solve(input).unwrap_err().to_string(),
+            "Invalid input: line 2, column 6: no directory named 'a'"
+        );
+    }
+
+    #[test]
+    fn missing_directory() {
+        let input = "$ cd\n";
+
+        assert_eq!(
+            PartTwo::solve(input).unwrap_err().to_string(),
+            "Invalid input: line 1, column 5: expected a directory, found the end of the line"
+        );
+    }
+
+    /// 500 directories of 200 files next to a chain of 1000 nested directories
+    fn synthetic_transcript() -> String {
+        let mut transcript = String::from("$ cd /\n$ ls\ndir chain\n");
+
+        for dir in 0..500 {
+            transcript.push_str(&format!("dir d{}\n", dir));
+        }
+        for dir in 0..500 {
+            transcript.push_str(&format!("$ cd /d{}\n$ ls\n", dir));
+            for file in 0..200 {
+                transcript.push_str(&format!("{} f{}\n", 1000 * (dir + 1), file));
+            }
+        }
+
+        transcript.push_str("$ cd /chain\n");
+        for _ in 0..1000 {
+            transcript.push_str("$ ls\n1000 file\ndir chain\n$ cd chain\n");
+        }
+
+        transcript
+    }
+
+    #[test]
+    fn synthetic() {
+        let input = synthetic_transcript();
+
+        // Only d149 and the following directories are large enough, d149 holding 30000000
+        assert_eq!(
+            PartTwo::solve(&input).unwrap(),
+            Answer::from(30_000_000usize)
+        );
     }
 }
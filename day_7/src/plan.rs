@@ -0,0 +1,254 @@
+use std::path::PathBuf;
+
+use crate::tree::{Node, Tree, TreeError};
+
+/// Directories to delete to make room on the disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    /// Directories deleted, none of them inside another one
+    pub dirs: Vec<PathBuf>,
+    /// Total size of the files deleted
+    pub freed: usize,
+}
+
+impl Plan {
+    /// Delete the directories of the plan from `tree`
+    pub fn apply(&self, tree: &mut Tree) -> Result<(), TreeError> {
+        for dir in &self.dirs {
+            tree.remove(dir)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Plans which directories to delete so that a disk has enough free space
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Planner {
+    /// Size of the disk
+    disk: usize,
+    /// Free space required on the disk
+    required: usize,
+    /// Largest number of directories a plan may delete
+    max_dirs: usize,
+}
+
+impl Planner {
+    pub fn new(disk: usize, required: usize, max_dirs: usize) -> Self {
+        Planner {
+            disk,
+            required,
+            max_dirs,
+        }
+    }
+
+    /// Space missing on the disk holding `tree`
+    pub fn needed(&self, tree: &Tree) -> usize {
+        let free = self.disk.saturating_sub(tree.size());
+
+        self.required.saturating_sub(free)
+    }
+
+    /// Plan deleting the least data to free the space needed, using at most `max_dirs`
+    /// directories and as few as possible among the plans freeing the same space.
+    /// `None` if even deleting everything isn't enough.
+    ///
+    /// The search is exact, so its cost quickly grows with `max_dirs`.
+    pub fn plan(&self, tree: &Tree) -> Option<Plan> {
+        let needed = self.needed(tree);
+
+        if needed == 0 {
+            return Some(Plan {
+                dirs: Vec::new(),
+                freed: 0,
+            });
+        }
+
+        // No plan can beat the smallest directory large enough on its own,
+        // and the root directory is always large enough if anything is
+        let single = tree
+            .dirs()
+            .map(|(_, size)| size)
+            .filter(|&size| size >= needed)
+            .min()?;
+
+        let mut candidates: Vec<(PathBuf, usize)> =
+            tree.dirs().filter(|&(_, size)| size <= single).collect();
+        candidates.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let mut search = Search {
+            candidates: &candidates,
+            needed,
+            max_dirs: self.max_dirs.max(1),
+            chosen: Vec::new(),
+            best: None,
+        };
+        search.explore(0, 0);
+
+        search.best
+    }
+}
+
+/// Branch and bound over the sets of directories, the largest ones first
+struct Search<'a> {
+    candidates: &'a [(PathBuf, usize)],
+    needed: usize,
+    max_dirs: usize,
+    /// Indexes of the candidates in the current set
+    chosen: Vec<usize>,
+    best: Option<Plan>,
+}
+
+impl Search<'_> {
+    fn explore(&mut self, start: usize, freed: usize) {
+        let slots = self.max_dirs - self.chosen.len();
+
+        for index in start..self.candidates.len() {
+            let (path, size) = &self.candidates[index];
+
+            // The next candidates are smaller, they can't free enough either
+            if freed.saturating_add(size.saturating_mul(slots)) < self.needed {
+                break;
+            }
+
+            let overlaps = self.chosen.iter().any(|&chosen| {
+                let other = &self.candidates[chosen].0;
+                path.starts_with(other) || other.starts_with(path)
+            });
+            if overlaps {
+                continue;
+            }
+
+            // Adding directories only frees more, so this set is as far as it can be worth it
+            let total = freed + size;
+            let dirs = self.chosen.len() + 1;
+            if self.best.as_ref().is_some_and(|best| {
+                total > best.freed || (total == best.freed && dirs >= best.dirs.len())
+            }) {
+                continue;
+            }
+
+            self.chosen.push(index);
+            if total >= self.needed {
+                self.best = Some(Plan {
+                    dirs: self
+                        .chosen
+                        .iter()
+                        .map(|&chosen| self.candidates[chosen].0.clone())
+                        .collect(),
+                    freed: total,
+                });
+            } else if slots > 1 {
+                self.explore(index + 1, total);
+            }
+            self.chosen.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shell::Shell;
+
+    /// Three directories of 6, 5 and 4 next to a file of 3
+    fn tree() -> Tree {
+        Shell::run(concat!(
+            "$ ls\n",
+            "dir x\n",
+            "dir y\n",
+            "dir z\n",
+            "3 file\n",
+            "$ cd x\n",
+            "$ ls\n",
+            "6 a\n",
+            "$ cd ../y\n",
+            "$ ls\n",
+            "5 b\n",
+            "$ cd ../z\n",
+            "$ ls\n",
+            "4 c\n"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn single_directory() {
+        let plan = Planner::new(25, 15, 1).plan(&tree()).unwrap();
+
+        assert_eq!(plan.dirs, [PathBuf::from("/")]);
+        assert_eq!(plan.freed, 18);
+    }
+
+    #[test]
+    fn several_directories() {
+        let tree = tree();
+        let planner = Planner::new(25, 15, 3);
+
+        assert_eq!(planner.needed(&tree), 8);
+        assert_eq!(
+            planner.plan(&tree),
+            Some(Plan {
+                dirs: vec![PathBuf::from("/y"), PathBuf::from("/z")],
+                freed: 9
+            })
+        );
+        assert_eq!(
+            Planner::new(22, 10, 3).plan(&tree).unwrap().dirs,
+            [PathBuf::from("/x")]
+        );
+    }
+
+    #[test]
+    fn fewer_directories_on_ties() {
+        let mut transcript = String::from("$ ls\n");
+        for (name, _) in [("p", 6), ("q", 5), ("r", 4), ("s", 2), ("t", 1)] {
+            transcript.push_str(&format!("dir {}\n", name));
+        }
+        for (name, size) in [("p", 6), ("q", 5), ("r", 4), ("s", 2), ("t", 1)] {
+            transcript.push_str(&format!("$ cd /{}\n$ ls\n{} file\n", name, size));
+        }
+        let tree = Shell::run(&transcript).unwrap();
+
+        // {p, s, t} frees 9 as well, but with one more directory
+        assert_eq!(
+            Planner::new(20, 11, 3).plan(&tree).unwrap().dirs,
+            [PathBuf::from("/q"), PathBuf::from("/r")]
+        );
+    }
+
+    #[test]
+    fn unlimited_directories() {
+        assert_eq!(
+            Planner::new(25, 15, usize::MAX).plan(&tree()).unwrap().dirs,
+            [PathBuf::from("/y"), PathBuf::from("/z")]
+        );
+    }
+
+    #[test]
+    fn nothing_to_free() {
+        assert_eq!(
+            Planner::new(100, 10, 3).plan(&tree()),
+            Some(Plan {
+                dirs: Vec::new(),
+                freed: 0
+            })
+        );
+    }
+
+    #[test]
+    fn not_enough_space() {
+        assert_eq!(Planner::new(10, 30, 3).plan(&tree()), None);
+    }
+
+    #[test]
+    fn apply() {
+        let mut tree = tree();
+        let planner = Planner::new(25, 15, 3);
+
+        planner.plan(&tree).unwrap().apply(&mut tree).unwrap();
+
+        assert_eq!(tree.size(), 9);
+        assert_eq!(planner.needed(&tree), 0);
+    }
+}
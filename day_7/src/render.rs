@@ -0,0 +1,480 @@
+use std::fmt::{self, Write};
+use std::io;
+
+use crate::tree::{Dir, File, Node, NodeType, Tree, TreeVisitor};
+
+/// Draws a tree like `tree(1)`, with the size of every node
+pub struct TreeView<W> {
+    out: W,
+    /// Lines drawn on the left of the children of the current directory
+    prefix: String,
+    /// Whether the node being visited is the last of its directory, `None` for the root
+    last: Option<bool>,
+    dirs: usize,
+    files: usize,
+    result: fmt::Result,
+}
+
+impl<W: Write> TreeView<W> {
+    pub fn new(out: W) -> Self {
+        TreeView {
+            out,
+            prefix: String::new(),
+            last: None,
+            dirs: 0,
+            files: 0,
+            result: Ok(()),
+        }
+    }
+
+    /// Draw `tree`, followed by the number of directories and files below the root
+    pub fn render(&mut self, tree: &Tree) -> fmt::Result {
+        self.visit_tree(tree);
+
+        let (dirs, files) = (self.dirs, self.files);
+        self.write(format_args!("\n{} directories, {} files\n", dirs, files));
+        self.result
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    /// First error met while writing, if any
+    pub fn result(&self) -> fmt::Result {
+        self.result
+    }
+
+    fn line(&mut self, node: &dyn Node) {
+        let connector = match self.last {
+            None => "",
+            Some(false) => "├── ",
+            Some(true) => "└── ",
+        };
+        let prefix = std::mem::take(&mut self.prefix);
+
+        self.write(format_args!(
+            "{}{}{} ({})\n",
+            prefix,
+            connector,
+            node.name(),
+            node.size()
+        ));
+        self.prefix = prefix;
+    }
+
+    fn write(&mut self, args: fmt::Arguments) {
+        if self.result.is_ok() {
+            self.result = self.out.write_fmt(args);
+        }
+    }
+}
+
+impl<W: Write> TreeVisitor for TreeView<W> {
+    fn visit_tree(&mut self, t: &Tree) {
+        self.last = None;
+        t.visit(self)
+    }
+
+    fn visit(&mut self, t: &dyn Node) {
+        t.visit(self)
+    }
+
+    fn visit_file(&mut self, f: &File) {
+        self.files += 1;
+        self.line(f);
+    }
+
+    fn visit_dir(&mut self, d: &Dir) {
+        self.line(d);
+
+        let len = self.prefix.len();
+        match self.last {
+            None => (),
+            Some(last) => {
+                self.dirs += 1;
+                self.prefix.push_str(if last { "    " } else { "│   " });
+            }
+        }
+
+        let children = d.children();
+        for (index, child) in children.iter().enumerate() {
+            self.last = Some(index + 1 == children.len());
+            self.visit(child.as_ref());
+        }
+
+        self.prefix.truncate(len);
+    }
+}
+
+/// Writes a tree as a JSON document, each node being an object with its name, type and size,
+/// and the nodes inside it for a directory
+pub struct Json<W> {
+    out: W,
+    result: fmt::Result,
+}
+
+impl<W: Write> Json<W> {
+    pub fn new(out: W) -> Self {
+        Json {
+            out,
+            result: Ok(()),
+        }
+    }
+
+    pub fn render(&mut self, tree: &Tree) -> fmt::Result {
+        self.visit_tree(tree);
+        self.result
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    /// Write the fields common to files and directories, leaving the object open
+    fn fields(&mut self, node: &dyn Node) {
+        let kind = match node.get_type() {
+            NodeType::Directory => "directory",
+            NodeType::File => "file",
+        };
+
+        self.write(format_args!(
+            "{{\"name\":\"{}\",\"type\":\"{}\",\"size\":{}",
+            Escaped(&node.name()),
+            kind,
+            node.size()
+        ));
+    }
+
+    fn write(&mut self, args: fmt::Arguments) {
+        if self.result.is_ok() {
+            self.result = self.out.write_fmt(args);
+        }
+    }
+}
+
+impl<W: Write> TreeVisitor for Json<W> {
+    fn visit_tree(&mut self, t: &Tree) {
+        t.visit(self)
+    }
+
+    fn visit(&mut self, t: &dyn Node) {
+        t.visit(self)
+    }
+
+    fn visit_file(&mut self, f: &File) {
+        self.fields(f);
+        self.write(format_args!("}}"));
+    }
+
+    fn visit_dir(&mut self, d: &Dir) {
+        self.fields(d);
+        self.write(format_args!(",\"children\":["));
+
+        for (index, child) in d.children().iter().enumerate() {
+            if index > 0 {
+                self.write(format_args!(","));
+            }
+            self.visit(child.as_ref());
+        }
+
+        self.write(format_args!("]}}"));
+    }
+}
+
+/// A string written as the inside of a JSON string
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+                c => f.write_char(c)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Width of the bars of `SizeReport`
+const BAR: usize = 10;
+
+/// Lists the nodes of a tree like ncdu: the largest first in each directory, with their share
+/// of the directory and a bar relative to the largest node next to them
+pub struct SizeReport<W> {
+    out: W,
+    depth: usize,
+    /// Size of the directory of the node being visited
+    parent: usize,
+    /// Size of the largest node next to the one being visited
+    largest: usize,
+    result: fmt::Result,
+}
+
+impl<W: Write> SizeReport<W> {
+    pub fn new(out: W) -> Self {
+        SizeReport {
+            out,
+            depth: 0,
+            parent: 0,
+            largest: 0,
+            result: Ok(()),
+        }
+    }
+
+    pub fn render(&mut self, tree: &Tree) -> fmt::Result {
+        self.visit_tree(tree);
+        self.result
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn line(&mut self, node: &dyn Node) {
+        let size = node.size();
+        let share = |total: usize| {
+            if total == 0 {
+                0.0
+            } else {
+                size as f64 / total as f64
+            }
+        };
+        let filled = (share(self.largest) * BAR as f64).round() as usize;
+        let suffix = match node.get_type() {
+            NodeType::Directory if self.depth > 0 => "/",
+            _ => "",
+        };
+
+        self.write(format_args!(
+            "{:>10} {:>5.1}% [{:<bar$}] {:indent$}{}{}\n",
+            Human(size),
+            share(self.parent) * 100.0,
+            "#".repeat(filled),
+            "",
+            node.name(),
+            suffix,
+            bar = BAR,
+            indent = 2 * self.depth
+        ));
+    }
+
+    fn write(&mut self, args: fmt::Arguments) {
+        if self.result.is_ok() {
+            self.result = self.out.write_fmt(args);
+        }
+    }
+}
+
+impl<W: Write> TreeVisitor for SizeReport<W> {
+    fn visit_tree(&mut self, t: &Tree) {
+        // The root is the whole disk used
+        self.depth = 0;
+        self.parent = t.size();
+        self.largest = t.size();
+        t.visit(self)
+    }
+
+    fn visit(&mut self, t: &dyn Node) {
+        t.visit(self)
+    }
+
+    fn visit_file(&mut self, f: &File) {
+        self.line(f);
+    }
+
+    fn visit_dir(&mut self, d: &Dir) {
+        self.line(d);
+
+        let mut children: Vec<&dyn Node> = d.children().iter().map(|c| c.as_ref()).collect();
+        children.sort_by(|a, b| {
+            b.size()
+                .cmp(&a.size())
+                .then_with(|| a.name().cmp(&b.name()))
+        });
+
+        self.depth += 1;
+        for child in children.iter() {
+            self.parent = d.size();
+            self.largest = children[0].size();
+            self.visit(*child);
+        }
+        self.depth -= 1;
+    }
+}
+
+/// A size in bytes, written with binary prefixes like "23.8 MiB"
+struct Human(usize);
+
+impl fmt::Display for Human {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+
+        if self.0 < 1024 {
+            return f.pad(&format!("{} B", self.0));
+        }
+
+        let mut size = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while size >= 1024.0 && unit + 1 < UNITS.len() {
+            size /= 1024.0;
+            unit += 1;
+        }
+
+        f.pad(&format!("{:.1} {}", size, UNITS[unit]))
+    }
+}
+
+/// Lets the renderers write to an `io::Write`, keeping the error that stopped them
+pub struct Io<W> {
+    out: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> Io<W> {
+    pub fn new(out: W) -> Self {
+        Io { out, error: None }
+    }
+
+    /// Turn the result of a renderer into the I/O error behind it, if any
+    pub fn check(&mut self, result: fmt::Result) -> io::Result<()> {
+        match (self.error.take(), result) {
+            (Some(error), _) => Err(error),
+            (None, Err(_)) => Err(io::Error::other("formatting failed")),
+            (None, Ok(())) => self.out.flush(),
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+impl<W: io::Write> Write for Io<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.out.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shell::Shell;
+
+    fn example() -> Tree {
+        Shell::run(include_str!("../simple_input.txt")).unwrap()
+    }
+
+    #[test]
+    fn display() {
+        let tree = Shell::run("$ ls\ndir a\n3 b\n$ cd a\n$ ls\n2 c\n").unwrap();
+
+        assert_eq!(
+            tree.to_string(),
+            "/ (5)\n├── a (2)\n│   └── c (2)\n└── b (3)\n"
+        );
+    }
+
+    #[test]
+    fn tree_view() {
+        let mut view = TreeView::new(String::new());
+
+        view.render(&example()).unwrap();
+
+        assert_eq!(
+            view.into_inner(),
+            concat!(
+                "/ (48381165)\n",
+                "├── a (94853)\n",
+                "│   ├── e (584)\n",
+                "│   │   └── i (584)\n",
+                "│   ├── f (29116)\n",
+                "│   ├── g (2557)\n",
+                "│   └── h.lst (62596)\n",
+                "├── b.txt (14848514)\n",
+                "├── c.dat (8504156)\n",
+                "└── d (24933642)\n",
+                "    ├── j (4060174)\n",
+                "    ├── d.log (8033020)\n",
+                "    ├── d.ext (5626152)\n",
+                "    └── k (7214296)\n",
+                "\n",
+                "3 directories, 10 files\n"
+            )
+        );
+    }
+
+    #[test]
+    fn json() {
+        let tree = Shell::run("$ ls\ndir a\n12 \"b\\\n$ cd a\n$ ls\n").unwrap();
+        let mut json = Json::new(String::new());
+
+        json.render(&tree).unwrap();
+
+        assert_eq!(
+            json.into_inner(),
+            concat!(
+                r#"{"name":"/","type":"directory","size":12,"children":["#,
+                r#"{"name":"a","type":"directory","size":0,"children":[]},"#,
+                r#"{"name":"\"b\\","type":"file","size":12}]}"#
+            )
+        );
+    }
+
+    #[test]
+    fn size_report() {
+        let mut report = SizeReport::new(String::new());
+
+        report.render(&example()).unwrap();
+
+        assert_eq!(
+            report.into_inner(),
+            concat!(
+                "  46.1 MiB 100.0% [##########] /\n",
+                "  23.8 MiB  51.5% [##########]   d/\n",
+                "   7.7 MiB  32.2% [##########]     d.log\n",
+                "   6.9 MiB  28.9% [######### ]     k\n",
+                "   5.4 MiB  22.6% [#######   ]     d.ext\n",
+                "   3.9 MiB  16.3% [#####     ]     j\n",
+                "  14.2 MiB  30.7% [######    ]   b.txt\n",
+                "   8.1 MiB  17.6% [###       ]   c.dat\n",
+                "  92.6 KiB   0.2% [          ]   a/\n",
+                "  61.1 KiB  66.0% [##########]     h.lst\n",
+                "  28.4 KiB  30.7% [#####     ]     f\n",
+                "   2.5 KiB   2.7% [          ]     g\n",
+                "     584 B   0.6% [          ]     e/\n",
+                "     584 B 100.0% [##########]       i\n"
+            )
+        );
+    }
+
+    #[test]
+    fn io_errors() {
+        let mut out = Io::new(Vec::new());
+        let result = TreeView::new(&mut out).render(&example());
+
+        out.check(result).unwrap();
+        assert!(String::from_utf8(out.into_inner())
+            .unwrap()
+            .starts_with("/ (48381165)\n├── a (94853)\n"));
+
+        let mut buffer = [0; 16];
+        let mut full = Io::new(&mut buffer[..]);
+        let result = Json::new(&mut full).render(&example());
+
+        assert_eq!(
+            full.check(result).unwrap_err().kind(),
+            io::ErrorKind::WriteZero
+        );
+    }
+}
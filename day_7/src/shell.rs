@@ -0,0 +1,422 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use aoc_core::{column, Locate, Located};
+
+use crate::cwd::CurrentWorkingDirectory;
+use crate::error::ParseError;
+use crate::tree::{Dir, File, Node, NodeType, Tree, TreeError};
+
+/// An entry printed by `ls`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Entry {
+    Dir(String),
+    File(String, usize),
+}
+
+impl Entry {
+    pub fn name(&self) -> &str {
+        match self {
+            Entry::Dir(name) | Entry::File(name, _) => name,
+        }
+    }
+
+    /// Whether `node` is this entry, of the same kind and size
+    fn matches(&self, node: &dyn Node) -> bool {
+        match self {
+            Entry::Dir(_) => node.get_type() == NodeType::Directory,
+            Entry::File(_, size) => node.get_type() == NodeType::File && node.size() == *size,
+        }
+    }
+
+    fn node(&self) -> Box<dyn Node> {
+        match self {
+            Entry::Dir(name) => Box::new(Dir::new(name.clone())),
+            Entry::File(name, size) => Box::new(File::new(name.clone(), *size)),
+        }
+    }
+}
+
+/// Output of an `ls` being read
+#[derive(Debug)]
+struct Listing {
+    /// Line of the `ls` command
+    line: usize,
+    /// Directory listed
+    dir: String,
+    /// Names printed so far
+    names: HashSet<String>,
+    /// Whether the directory was already listed before
+    again: bool,
+}
+
+/// Replays a transcript of `cd` and `ls` commands to rebuild the filesystem they explored.
+///
+/// The entries listed are looked up in the tree being rebuilt, so that moving into a directory
+/// that was never seen or listing a directory differently from the last time is reported on
+/// the faulty line.
+#[derive(Debug)]
+pub struct Shell {
+    cwd: CurrentWorkingDirectory,
+    tree: Tree,
+    /// Paths of the directories listed
+    listed: HashSet<String>,
+    /// The `ls` whose output is being read, if any
+    listing: Option<Listing>,
+}
+
+impl Shell {
+    pub fn new() -> Self {
+        let mut cwd = CurrentWorkingDirectory::new();
+        cwd.mv("/").unwrap();
+
+        Shell {
+            cwd,
+            tree: Tree::new(),
+            listed: HashSet::new(),
+            listing: None,
+        }
+    }
+
+    /// Replay a whole transcript, returning the filesystem it shows
+    pub fn run(input: &str) -> Result<Tree, Located<ParseError>> {
+        let mut shell = Shell::new();
+
+        for (index, line) in input.lines().enumerate() {
+            shell.execute(line, index + 1)?;
+        }
+
+        shell.finish()
+    }
+
+    /// Current working directory
+    pub fn cwd(&self) -> &str {
+        self.cwd.get()
+    }
+
+    /// Interpret a line of the transcript `line_number`, either a command or the output of `ls`
+    pub fn execute(&mut self, line: &str, line_number: usize) -> Result<(), Located<ParseError>> {
+        let end_of_line = line.chars().count() + 1;
+
+        if !line.starts_with('$') {
+            return self.read_entry(line, line_number);
+        }
+
+        // Any command ends the output of the previous one
+        self.end_listing()?;
+
+        // "$ <command> <operand>", the operand being the rest of the line as names can hold spaces
+        let command = line
+            .split_once(' ')
+            .map_or(&line[line.len()..], |(_, command)| command);
+        let (command, operand) = match command.split_once(' ') {
+            Some((command, operand)) => (command, Some(operand).filter(|o| !o.is_empty())),
+            None => (command, None),
+        };
+
+        match command {
+            "cd" => {
+                let path = operand
+                    .ok_or(ParseError::MissingDirectory)
+                    .at(line_number, end_of_line)?;
+                self.cd(line, line_number, path)
+            }
+            "ls" => {
+                if let Some(operand) = operand {
+                    let error = ParseError::UnexpectedOperand(operand.to_string());
+                    return Err(Located::new(line_number, column(line, operand), error));
+                }
+
+                let dir = self.cwd.get().to_string();
+                let again = !self.listed.insert(dir.clone());
+
+                self.listing = Some(Listing {
+                    line: line_number,
+                    dir,
+                    names: HashSet::new(),
+                    again,
+                });
+                Ok(())
+            }
+            command => {
+                let error = ParseError::UnknownCommand(command.to_string());
+                Err(Located::new(line_number, column(line, command), error))
+            }
+        }
+    }
+
+    /// End of the transcript, returning the filesystem rebuilt
+    pub fn finish(mut self) -> Result<Tree, Located<ParseError>> {
+        self.end_listing()?;
+
+        Ok(self.tree)
+    }
+
+    /// Move along `path`, one component after the other, each directory having to be listed
+    fn cd(
+        &mut self,
+        line: &str,
+        line_number: usize,
+        path: &str,
+    ) -> Result<(), Located<ParseError>> {
+        let relative = match path.strip_prefix('/') {
+            Some(relative) => {
+                self.cwd
+                    .mv("/")
+                    .map_err(ParseError::from)
+                    .at(line_number, 1)?;
+                relative
+            }
+            None => path,
+        };
+
+        for component in relative.split('/').filter(|c| !c.is_empty()) {
+            let col = column(line, component);
+
+            match component {
+                "." => (),
+                ".." => self
+                    .cwd
+                    .mv(component)
+                    .map_err(ParseError::from)
+                    .at(line_number, col)?,
+                name => {
+                    // Only the directories listed have children in the tree
+                    let child = self
+                        .tree
+                        .get(self.cwd.get())
+                        .and_then(|dir| dir.child(name));
+
+                    let error = match child.map(|child| child.get_type()) {
+                        Some(NodeType::Directory) => None,
+                        Some(NodeType::File) => Some(TreeError::NotADirectory(name.to_string())),
+                        None => Some(TreeError::NotFound(name.to_string())),
+                    };
+                    if let Some(error) = error {
+                        return Err(Located::new(line_number, col, error.into()));
+                    }
+
+                    self.cwd
+                        .mv(name)
+                        .map_err(ParseError::from)
+                        .at(line_number, col)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read an entry printed by `ls`, adding it to the tree the first time its directory is listed
+    fn read_entry(&mut self, line: &str, line_number: usize) -> Result<(), Located<ParseError>> {
+        let Some(listing) = &mut self.listing else {
+            return Err(Located::new(line_number, 1, ParseError::UnexpectedOutput));
+        };
+
+        let entry = parse_entry(line, line_number)?;
+        let name = entry.name();
+        let col = line
+            .split_once(' ')
+            .map_or(1, |(_, name)| column(line, name));
+
+        if !listing.names.insert(name.to_string()) {
+            let error = ParseError::DuplicateEntry(name.to_string());
+            return Err(Located::new(line_number, col, error));
+        }
+
+        // Listed again, nothing may have changed since
+        if listing.again {
+            let same = self
+                .tree
+                .get(&listing.dir)
+                .and_then(|dir| dir.child(name))
+                .is_some_and(|node| entry.matches(node));
+            if !same {
+                let error = ParseError::ConflictingEntry(name.to_string());
+                return Err(Located::new(line_number, col, error));
+            }
+            return Ok(());
+        }
+
+        let path = PathBuf::from(&listing.dir);
+        self.tree
+            .add(&mut path.components(), entry.node())
+            .map_err(ParseError::from)
+            .at(line_number, 1)?;
+
+        Ok(())
+    }
+
+    /// Check that a directory listed again showed all of its entries
+    fn end_listing(&mut self) -> Result<(), Located<ParseError>> {
+        let Some(listing) = self.listing.take().filter(|listing| listing.again) else {
+            return Ok(());
+        };
+
+        let dir = self
+            .tree
+            .get(&listing.dir)
+            .expect("a directory is in the tree as soon as it is listed");
+
+        if let Some(missing) = dir
+            .children()
+            .iter()
+            .find(|child| !listing.names.contains(&child.name()))
+        {
+            let error = ParseError::MissingEntry(missing.name());
+            return Err(Located::new(listing.line, 3, error));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Shell {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Read a line printed by `ls`: "dir <name>" or "<size> <name>", the name being the rest of
+/// the line
+fn parse_entry(line: &str, line_number: usize) -> Result<Entry, Located<ParseError>> {
+    let end_of_line = line.chars().count() + 1;
+    let (kind, name) = line
+        .split_once(' ')
+        .filter(|(_, name)| !name.is_empty())
+        .ok_or(ParseError::MissingName)
+        .at(line_number, end_of_line)?;
+
+    match kind {
+        "dir" => Ok(Entry::Dir(name.to_string())),
+        size => {
+            let size = size
+                .parse()
+                .map_err(|_| ParseError::InvalidSize(size.to_string()))
+                .at(line_number, 1)?;
+            Ok(Entry::File(name.to_string(), size))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(input: &str) -> Result<Tree, String> {
+        Shell::run(input).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn multi_component_cd() {
+        let mut shell = Shell::new();
+        let transcript = [
+            "$ ls",
+            "dir a",
+            "dir c",
+            "$ cd a",
+            "$ ls",
+            "dir b",
+            "$ cd /c",
+            "$ ls",
+            "1 f",
+            "$ cd /",
+            "$ cd a/b/../../c/.",
+        ];
+
+        for (index, line) in transcript.iter().enumerate() {
+            shell.execute(line, index + 1).unwrap();
+        }
+
+        assert_eq!(shell.cwd(), "/c");
+        assert_eq!(shell.finish().unwrap().size(), 1);
+    }
+
+    #[test]
+    fn cd_into_unlisted_directory() {
+        assert_eq!(
+            run("$ cd /\n$ ls\ndir a\n$ cd a/b").unwrap_err(),
+            "line 4, column 8: no directory named 'b'"
+        );
+        assert_eq!(
+            run("$ cd /\n$ cd a").unwrap_err(),
+            "line 2, column 6: no directory named 'a'"
+        );
+    }
+
+    #[test]
+    fn cd_into_file() {
+        assert_eq!(
+            run("$ ls\n12 a\n$ cd a").unwrap_err(),
+            "line 3, column 6: 'a' is a file, not a directory"
+        );
+    }
+
+    #[test]
+    fn output_without_ls() {
+        assert_eq!(
+            run("$ cd /\ndir a").unwrap_err(),
+            "line 2, column 1: expected a command ('$'), found output outside of 'ls'"
+        );
+    }
+
+    #[test]
+    fn duplicate_entry() {
+        assert_eq!(
+            run("$ ls\ndir a\n12 a").unwrap_err(),
+            "line 3, column 4: 'a' is listed twice in the same directory"
+        );
+    }
+
+    #[test]
+    fn listed_again() {
+        let tree = run("$ ls\ndir a\n12 b\n$ cd a\n$ cd ..\n$ ls\n12 b\ndir a\n").unwrap();
+
+        assert_eq!(tree.size(), 12);
+        assert_eq!(
+            run("$ ls\ndir a\n12 b\n$ ls\ndir a\n13 b").unwrap_err(),
+            "line 6, column 4: 'b' doesn't match the previous listing of the directory"
+        );
+        assert_eq!(
+            run("$ ls\ndir a\n12 b\n$ ls\n12 b\n$ cd a").unwrap_err(),
+            "line 4, column 3: 'a' was listed before but is missing from the listing"
+        );
+    }
+
+    #[test]
+    fn large_directory() {
+        let mut listing = String::from("$ ls\n");
+        for file in 0..100_000 {
+            listing.push_str(&format!("1 f{}\n", file));
+        }
+        listing.push_str("dir last\n");
+
+        // Listed twice and left for its last entry, each name being looked up once
+        let transcript = format!("{}{}$ cd last\n", listing, listing);
+        assert_eq!(run(&transcript).unwrap().size(), 100_000);
+    }
+
+    #[test]
+    fn names_with_spaces() {
+        let tree = run("$ ls\n123 my file\ndir my dir\n$ cd my dir\n$ ls\n5 x y\n").unwrap();
+
+        assert_eq!(tree.du("/my file"), Some(123));
+        assert_eq!(tree.du("/my dir/x y"), Some(5));
+        assert_eq!(
+            run("$ ls\ndir a\n$ cd a b").unwrap_err(),
+            "line 3, column 6: no directory named 'a b'"
+        );
+        assert_eq!(
+            run("$ ls\ndir ").unwrap_err(),
+            "line 2, column 5: expected a name, found the end of the line"
+        );
+    }
+
+    #[test]
+    fn unexpected_operand() {
+        assert_eq!(
+            run("$ ls\ndir a\n$ ls a").unwrap_err(),
+            "line 3, column 6: expected the end of the command, found 'a'"
+        );
+    }
+}
@@ -1,17 +1,24 @@
 use std::{
+    collections::HashMap,
     error::Error,
     fmt::{Debug, Display},
-    path::Components,
+    path::{Component, Components, Path, PathBuf},
 };
 
+use crate::render::TreeView;
+
 #[derive(Debug)]
 pub struct Tree {
     root: Box<dyn Node>,
 }
 
 impl Display for Tree {
+    /// Draw the tree like `tree(1)`, with sizes
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.root)
+        let mut view = TreeView::new(f);
+
+        view.visit_tree(self);
+        view.result()
     }
 }
 
@@ -25,11 +32,11 @@ impl Node for Tree {
     }
 
     fn get_type(&self) -> NodeType {
-        NodeType::DIRECTORY
+        NodeType::Directory
     }
 
-    fn add_child(&mut self, _: Box<dyn Node>) -> Result<(), TreeError> {
-        Err(TreeError::new())
+    fn add_child(&mut self, child: Box<dyn Node>) -> Result<(), TreeError> {
+        self.root.add_child(child)
     }
 
     fn add(&mut self, components: &mut Components, node: Box<dyn Node>) -> Result<(), TreeError> {
@@ -37,12 +44,29 @@ impl Node for Tree {
         self.root.add(components, node)
     }
 
+    fn take(&mut self, components: &mut Components) -> Result<Box<dyn Node>, TreeError> {
+        components.next(); // Skip "/"
+
+        if components.clone().next().is_none() {
+            return Err(TreeError::RootDirectory);
+        }
+        self.root.take(components)
+    }
+
+    fn set_name(&mut self, _: String) {
+        // The root directory is always "/"
+    }
+
+    fn children(&self) -> &[Box<dyn Node>] {
+        self.root.children()
+    }
+
     fn visit(&self, visitor: &mut dyn TreeVisitor) {
-        visitor.visit(&self.root)
+        visitor.visit(self.root.as_ref())
     }
 
-    fn visit_size(&self, visitor: &mut dyn TreeSizeVisitor) -> usize {
-        visitor.visit(&self.root)
+    fn child(&self, name: &str) -> Option<&dyn Node> {
+        self.root.child(name)
     }
 }
 
@@ -52,29 +76,175 @@ impl Tree {
             root: Box::new(Dir::new(String::from("/"))),
         }
     }
+
+    /// Node at `path`, relative paths starting from the root as well
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> Option<&dyn Node> {
+        let mut node = self.root.as_ref();
+
+        for component in path.as_ref().components() {
+            match component {
+                Component::Normal(name) => {
+                    node = node.child(&name.to_string_lossy())?;
+                }
+                Component::RootDir | Component::CurDir => (),
+                Component::ParentDir | Component::Prefix(_) => return None,
+            }
+        }
+
+        Some(node)
+    }
+
+    /// Total size of the files at or under `path`
+    pub fn du<P: AsRef<Path>>(&self, path: P) -> Option<usize> {
+        self.get(path).map(|node| node.size())
+    }
+
+    /// Every node of the tree with its path, parents before their children
+    pub fn walk(&self) -> Walk<'_> {
+        Walk {
+            stack: vec![(PathBuf::from("/"), self.root.as_ref())],
+        }
+    }
+
+    /// Nodes matching `predicate`, with their path
+    pub fn find<'a, F>(&'a self, predicate: F) -> impl Iterator<Item = (PathBuf, &'a dyn Node)>
+    where
+        F: Fn(&dyn Node) -> bool + 'a,
+    {
+        self.walk().filter(move |(_, node)| predicate(*node))
+    }
+
+    /// Every directory, the root included, with its path and total size
+    pub fn dirs(&self) -> impl Iterator<Item = (PathBuf, usize)> + '_ {
+        self.find(|node| node.get_type() == NodeType::Directory)
+            .map(|(path, node)| (path, node.size()))
+    }
+
+    /// Take the node at `path` out of the tree, like `rm -r`
+    pub fn remove<P: AsRef<Path>>(&mut self, path: P) -> Result<Box<dyn Node>, TreeError> {
+        let path = absolute(path.as_ref());
+
+        Node::take(self, &mut path.components())
+    }
+
+    /// Move the node at `from` to `to`, or inside `to` if it is a directory, like `mv`
+    pub fn mv<P: AsRef<Path>, Q: AsRef<Path>>(&mut self, from: P, to: Q) -> Result<(), TreeError> {
+        let (from, to) = (absolute(from.as_ref()), absolute(to.as_ref()));
+        let Some(name) = from.file_name() else {
+            return Err(TreeError::RootDirectory);
+        };
+
+        let (parent, name) = match self.get(&to) {
+            Some(node) if node.get_type() == NodeType::Directory => (to.as_path(), name),
+            _ => match (to.parent(), to.file_name()) {
+                (Some(parent), Some(name)) => (parent, name),
+                _ => return Err(TreeError::RootDirectory),
+            },
+        };
+        let name = name.to_string_lossy().to_string();
+
+        if self.get(&from).is_none() {
+            return Err(TreeError::NoEntry(from.to_string_lossy().to_string()));
+        }
+        // Already there, under the same name
+        if parent.join(&name) == from {
+            return Ok(());
+        }
+        if parent.starts_with(&from) {
+            return Err(TreeError::IntoItself(from.to_string_lossy().to_string()));
+        }
+        match self.get(parent) {
+            Some(node) if node.get_type() == NodeType::Directory => (),
+            Some(node) => return Err(TreeError::NotADirectory(node.name())),
+            None => return Err(TreeError::NotFound(parent.to_string_lossy().to_string())),
+        }
+        if self.get(parent.join(&name)).is_some() {
+            return Err(TreeError::AlreadyExists(name));
+        }
+
+        let mut node = self.remove(&from)?;
+        node.set_name(name);
+        Node::add(self, &mut parent.components(), node)
+    }
+
+    /// Give a new name to the node at `path`, in the same directory
+    pub fn rename<P: AsRef<Path>>(&mut self, path: P, name: &str) -> Result<(), TreeError> {
+        let path = absolute(path.as_ref());
+        let to = path.with_file_name(name);
+
+        if to != path && self.get(&to).is_some() {
+            return Err(TreeError::AlreadyExists(name.to_string()));
+        }
+        self.mv(path, to)
+    }
 }
 
-#[derive(Debug)]
-pub struct TreeError {}
+/// `path` from the root, relative paths starting at the root as well
+fn absolute(path: &Path) -> PathBuf {
+    Path::new("/").join(path)
+}
 
-impl TreeError {
-    pub fn new() -> Self {
-        TreeError {}
+/// Depth first iterator over the nodes of a `Tree`
+pub struct Walk<'a> {
+    stack: Vec<(PathBuf, &'a dyn Node)>,
+}
+
+impl<'a> Iterator for Walk<'a> {
+    type Item = (PathBuf, &'a dyn Node);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, node) = self.stack.pop()?;
+
+        // Reversed so that the children come out in the order they were listed
+        for child in node.children().iter().rev() {
+            self.stack.push((path.join(child.name()), child.as_ref()));
+        }
+
+        Some((path, node))
     }
 }
 
+impl Default for Tree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeError {
+    /// A directory of the path doesn't exist
+    NotFound(String),
+    /// A file is used as a directory
+    NotADirectory(String),
+    /// Nothing at the end of the path, neither a file nor a directory
+    NoEntry(String),
+    /// A node with the same name is already in the directory
+    AlreadyExists(String),
+    /// The root directory can't be removed or moved
+    RootDirectory,
+    /// A directory moved somewhere inside itself
+    IntoItself(String),
+}
+
 impl Display for TreeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", 0)
+        match self {
+            TreeError::NotFound(name) => write!(f, "no directory named '{}'", name),
+            TreeError::NotADirectory(name) => write!(f, "'{}' is a file, not a directory", name),
+            TreeError::NoEntry(path) => write!(f, "no file or directory at '{}'", path),
+            TreeError::AlreadyExists(name) => write!(f, "'{}' already exists", name),
+            TreeError::RootDirectory => write!(f, "the root directory can't be removed or moved"),
+            TreeError::IntoItself(path) => write!(f, "can't move '{}' inside itself", path),
+        }
     }
 }
 
 impl Error for TreeError {}
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NodeType {
-    DIRECTORY,
-    FILE,
+    Directory,
+    File,
 }
 
 pub trait Node: Display {
@@ -83,8 +253,14 @@ pub trait Node: Display {
     fn get_type(&self) -> NodeType;
     fn add(&mut self, components: &mut Components, node: Box<dyn Node>) -> Result<(), TreeError>;
     fn add_child(&mut self, child: Box<dyn Node>) -> Result<(), TreeError>;
+    /// Take out the node at the end of `components`, below this one
+    fn take(&mut self, components: &mut Components) -> Result<Box<dyn Node>, TreeError>;
+    fn set_name(&mut self, name: String);
+    /// Nodes directly inside this one, none for a file
+    fn children(&self) -> &[Box<dyn Node>];
+    /// Node directly inside this one with this name, none for a file
+    fn child(&self, name: &str) -> Option<&dyn Node>;
     fn visit(&self, visitor: &mut dyn TreeVisitor);
-    fn visit_size(&self, visitor: &mut dyn TreeSizeVisitor) -> usize;
 }
 
 impl Debug for dyn Node {
@@ -97,6 +273,11 @@ impl Debug for dyn Node {
 pub struct Dir {
     name: String,
     childs: Vec<Box<dyn Node>>,
+    /// Position of each child in `childs` by name, the first one for a name added twice
+    index: HashMap<String, usize>,
+    /// Total size of the files under the directory, updated by every directory on the way
+    /// when a node is added, so that it never has to be computed again
+    size: usize,
 }
 
 impl Dir {
@@ -104,20 +285,15 @@ impl Dir {
         Dir {
             name,
             childs: Vec::new(),
+            index: HashMap::new(),
+            size: 0,
         }
     }
 }
 
 impl Display for Dir {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Dir {}", self.name)?;
-
-        /*
-        for child in &self.childs {
-            write!(f, "{}", child)?;
-        }*/
-
-        Ok(())
+        write!(f, "Dir {}", self.name)
     }
 }
 
@@ -127,17 +303,11 @@ impl Node for Dir {
     }
 
     fn size(&self) -> usize {
-        let mut size: usize = 0;
-
-        for node in &self.childs {
-            size += node.size()
-        }
-
-        size
+        self.size
     }
 
     fn get_type(&self) -> NodeType {
-        NodeType::DIRECTORY
+        NodeType::Directory
     }
 
     fn add(&mut self, components: &mut Components, node: Box<dyn Node>) -> Result<(), TreeError> {
@@ -145,28 +315,77 @@ impl Node for Dir {
 
         match component {
             Some(path) => {
-                for child in &mut self.childs {
-                    if child.name() == path.as_os_str().to_str().unwrap() {
-                        return child.add(components, node);
-                    }
-                }
+                let name = path.as_os_str().to_string_lossy();
+                let size = node.size();
+
+                let child = self
+                    .index
+                    .get(name.as_ref())
+                    .ok_or_else(|| TreeError::NotFound(name.to_string()))?;
 
-                Err(TreeError::new())
+                self.childs[*child].add(components, node)?;
+                self.size += size;
+                Ok(())
             }
             None => self.add_child(node),
         }
     }
 
     fn add_child(&mut self, child: Box<dyn Node>) -> Result<(), TreeError> {
-        Ok(self.childs.push(child))
+        self.size += child.size();
+        self.index.entry(child.name()).or_insert(self.childs.len());
+        self.childs.push(child);
+        Ok(())
+    }
+
+    fn take(&mut self, components: &mut Components) -> Result<Box<dyn Node>, TreeError> {
+        let Some(component) = components.next() else {
+            return Err(TreeError::NoEntry(self.name.clone()));
+        };
+        let name = component.as_os_str().to_string_lossy();
+        let last = components.clone().next().is_none();
+
+        let Some(&child) = self.index.get(name.as_ref()) else {
+            return Err(if last {
+                TreeError::NoEntry(name.to_string())
+            } else {
+                TreeError::NotFound(name.to_string())
+            });
+        };
+
+        let node = if last {
+            let node = self.childs.remove(child);
+
+            // Children after it moved one place to the left
+            self.index.clear();
+            for (position, child) in self.childs.iter().enumerate() {
+                self.index.entry(child.name()).or_insert(position);
+            }
+            node
+        } else {
+            self.childs[child].take(components)?
+        };
+
+        self.size -= node.size();
+        Ok(node)
+    }
+
+    fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    fn children(&self) -> &[Box<dyn Node>] {
+        &self.childs
     }
 
     fn visit(&self, visitor: &mut dyn TreeVisitor) {
         visitor.visit_dir(self)
     }
 
-    fn visit_size(&self, visitor: &mut dyn TreeSizeVisitor) -> usize {
-        visitor.visit_dir(self)
+    fn child(&self, name: &str) -> Option<&dyn Node> {
+        self.index
+            .get(name)
+            .map(|&child| self.childs[child].as_ref())
     }
 }
 
@@ -198,128 +417,223 @@ impl Node for File {
     }
 
     fn get_type(&self) -> NodeType {
-        NodeType::FILE
+        NodeType::File
     }
 
     fn add(&mut self, _: &mut Components, _: Box<dyn Node>) -> Result<(), TreeError> {
-        Err(TreeError::new())
+        Err(TreeError::NotADirectory(self.name.clone()))
     }
 
     fn add_child(&mut self, _: Box<dyn Node>) -> Result<(), TreeError> {
-        Err(TreeError::new())
+        Err(TreeError::NotADirectory(self.name.clone()))
     }
 
-    fn visit(&self, visitor: &mut dyn TreeVisitor) {
-        visitor.visit_file(self)
+    fn take(&mut self, _: &mut Components) -> Result<Box<dyn Node>, TreeError> {
+        Err(TreeError::NotADirectory(self.name.clone()))
     }
 
-    fn visit_size(&self, visitor: &mut dyn TreeSizeVisitor) -> usize {
-        visitor.visit_file(self)
+    fn set_name(&mut self, name: String) {
+        self.name = name;
     }
-}
 
-pub struct TreeDisplay {
-    indent: u32,
-}
+    fn children(&self) -> &[Box<dyn Node>] {
+        &[]
+    }
 
-impl TreeDisplay {
-    pub fn new() -> Self {
-        TreeDisplay { indent: 0 }
+    fn visit(&self, visitor: &mut dyn TreeVisitor) {
+        visitor.visit_file(self)
     }
 
-    pub fn print_ident(&self) {
-        for _ in 0..self.indent {
-            print!(" ");
-        }
+    fn child(&self, _: &str) -> Option<&dyn Node> {
+        None
     }
 }
 
 pub trait TreeVisitor {
     fn visit_tree(&mut self, t: &Tree);
-    fn visit(&mut self, t: &Box<dyn Node>);
+    fn visit(&mut self, t: &dyn Node);
     fn visit_file(&mut self, f: &File);
     fn visit_dir(&mut self, d: &Dir);
 }
 
-impl TreeVisitor for TreeDisplay {
-    fn visit_tree(&mut self, t: &Tree) {
-        self.visit(&t.root)
-    }
-
-    fn visit(&mut self, t: &Box<dyn Node>) {
-        t.visit(self)
-    }
-
-    fn visit_file(&mut self, f: &File) {
-        self.print_ident();
-        println!("{}", f);
-    }
-
-    fn visit_dir(&mut self, d: &Dir) {
-        self.print_ident();
-        println!("{}", d);
-
-        self.indent += 1;
-
-        for child in &d.childs {
-            self.visit(child);
-        }
-
-        self.indent -= 1;
-    }
-}
-
-pub struct TreeMaxDirSize {
-    pub size: usize,
-    max_size: usize,
-    pub dir_size: usize,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shell::Shell;
 
-impl TreeMaxDirSize {
-    pub fn new(max_size: usize) -> Self {
-        TreeMaxDirSize {
-            size: 0,
-            max_size,
-            dir_size: usize::MAX,
-        }
-    }
-}
-
-pub trait TreeSizeVisitor {
-    fn visit_tree(&mut self, t: &Tree) -> usize;
-    fn visit(&mut self, t: &Box<dyn Node>) -> usize;
-    fn visit_file(&mut self, f: &File) -> usize;
-    fn visit_dir(&mut self, d: &Dir) -> usize;
-}
-
-impl TreeSizeVisitor for TreeMaxDirSize {
-    fn visit_tree(&mut self, t: &Tree) -> usize {
-        self.visit(&t.root)
-    }
-
-    fn visit(&mut self, t: &Box<dyn Node>) -> usize {
-        t.visit_size(self)
-    }
+    fn example() -> Tree {
+        Shell::run(include_str!("../simple_input.txt")).unwrap()
+    }
+
+    #[test]
+    fn get() {
+        let tree = example();
+
+        assert_eq!(tree.get("/").unwrap().name(), "/");
+        assert_eq!(tree.get("/a/e/i").unwrap().size(), 584);
+        assert_eq!(tree.get("a/h.lst").unwrap().get_type(), NodeType::File);
+        assert!(tree.get("/a/x").is_none());
+        assert!(tree.get("/b.txt/x").is_none());
+    }
 
-    fn visit_file(&mut self, f: &File) -> usize {
-        f.size()
-    }
-
-    fn visit_dir(&mut self, d: &Dir) -> usize {
-        let mut size: usize = 0;
-
-        for child in &d.childs {
-            size += self.visit(child);
-        }
-
-        if size < self.dir_size && size >= self.max_size {
-            self.dir_size = size;
-        }
-
-        if size < self.max_size {
-            self.size += size;
-        }
+    #[test]
+    fn du() {
+        let tree = example();
 
-        size
+        assert_eq!(tree.du("/a/e"), Some(584));
+        assert_eq!(tree.du("/a"), Some(94853));
+        assert_eq!(tree.du("/d"), Some(24933642));
+        assert_eq!(tree.du("/"), Some(48381165));
+        assert_eq!(tree.du("/z"), None);
+    }
+
+    #[test]
+    fn sizes_follow_additions() {
+        let mut tree = example();
+        let mut e = Dir::new(String::from("f"));
+        e.add_child(Box::new(File::new(String::from("g"), 16)))
+            .unwrap();
+
+        tree.add(&mut Path::new("/a/e").components(), Box::new(e))
+            .unwrap();
+
+        assert_eq!(tree.du("/a/e/f"), Some(16));
+        assert_eq!(tree.du("/a/e"), Some(600));
+        assert_eq!(tree.du("/a"), Some(94869));
+        assert_eq!(tree.du("/d"), Some(24933642));
+        assert_eq!(tree.size(), 48381181);
+    }
+
+    #[test]
+    fn remove() {
+        let mut tree = example();
+        let e = tree.remove("/a/e").unwrap();
+
+        assert_eq!(e.name(), "e");
+        assert_eq!(e.size(), 584);
+        assert_eq!(tree.du("/a"), Some(94269));
+        assert_eq!(tree.size(), 48380581);
+        assert!(tree.get("/a/e").is_none());
+
+        tree.remove("b.txt").unwrap();
+        assert_eq!(tree.get("/c.dat").unwrap().size(), 8504156);
+        assert_eq!(tree.size(), 33532067);
+    }
+
+    #[test]
+    fn remove_errors() {
+        let mut tree = example();
+
+        assert_eq!(tree.remove("/").unwrap_err(), TreeError::RootDirectory);
+        assert_eq!(
+            tree.remove("/a/x").unwrap_err(),
+            TreeError::NoEntry(String::from("x"))
+        );
+        assert_eq!(
+            tree.remove("/x/e").unwrap_err(),
+            TreeError::NotFound(String::from("x"))
+        );
+        assert_eq!(
+            tree.remove("/b.txt/e").unwrap_err(),
+            TreeError::NotADirectory(String::from("b.txt"))
+        );
+        assert_eq!(tree.size(), 48381165);
+    }
+
+    #[test]
+    fn mv() {
+        let mut tree = example();
+
+        // Into an existing directory
+        tree.mv("/a/e", "/d").unwrap();
+        assert_eq!(tree.du("/d/e/i"), Some(584));
+        assert_eq!(tree.du("/a"), Some(94269));
+        assert_eq!(tree.du("/d"), Some(24934226));
+
+        // To a new path
+        tree.mv("/b.txt", "/d/e/b").unwrap();
+        assert_eq!(tree.du("/d/e"), Some(14849098));
+        assert_eq!(tree.size(), 48381165);
+
+        tree.rename("/d/e", "f").unwrap();
+        assert_eq!(tree.du("/d/f/b"), Some(14848514));
+        assert!(tree.get("/d/e").is_none());
+    }
+
+    #[test]
+    fn mv_to_the_same_place() {
+        let mut tree = example();
+        let names = |tree: &Tree| -> Vec<PathBuf> { tree.walk().map(|(path, _)| path).collect() };
+        let before = names(&tree);
+
+        tree.mv("/a/f", "/a").unwrap();
+        tree.mv("/a/h.lst", "/a/h.lst").unwrap();
+        tree.mv("/d", "/").unwrap();
+        tree.rename("/a/g", "g").unwrap();
+
+        // Nothing moved, not even in the order of the children
+        assert_eq!(names(&tree), before);
+        assert_eq!(tree.du("/a"), Some(94853));
+    }
+
+    #[test]
+    fn mv_errors() {
+        let mut tree = example();
+
+        assert_eq!(
+            tree.mv("/a", "/a/e").unwrap_err(),
+            TreeError::IntoItself(String::from("/a"))
+        );
+        assert_eq!(
+            tree.mv("/d/j", "/a/h.lst").unwrap_err(),
+            TreeError::AlreadyExists(String::from("h.lst"))
+        );
+        assert_eq!(
+            tree.mv("/d/j", "/x/j").unwrap_err(),
+            TreeError::NotFound(String::from("/x"))
+        );
+        assert_eq!(
+            tree.mv("/x", "/a").unwrap_err(),
+            TreeError::NoEntry(String::from("/x"))
+        );
+        assert_eq!(
+            tree.rename("/a", "d").unwrap_err(),
+            TreeError::AlreadyExists(String::from("d"))
+        );
+        assert_eq!(tree.mv("/", "/a").unwrap_err(), TreeError::RootDirectory);
+        assert_eq!(tree.size(), 48381165);
+    }
+
+    #[test]
+    fn find() {
+        let tree = example();
+        let large: Vec<PathBuf> = tree
+            .find(|node| node.get_type() == NodeType::File && node.size() > 8_000_000)
+            .map(|(path, _)| path)
+            .collect();
+
+        assert_eq!(
+            large,
+            [
+                PathBuf::from("/b.txt"),
+                PathBuf::from("/c.dat"),
+                PathBuf::from("/d/d.log")
+            ]
+        );
+    }
+
+    #[test]
+    fn dirs() {
+        let dirs: Vec<(PathBuf, usize)> = example().dirs().collect();
+
+        assert_eq!(
+            dirs,
+            [
+                (PathBuf::from("/"), 48381165),
+                (PathBuf::from("/a"), 94853),
+                (PathBuf::from("/a/e"), 584),
+                (PathBuf::from("/d"), 24933642)
+            ]
+        );
     }
 }
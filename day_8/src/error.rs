@@ -0,0 +1,41 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use aoc_grid::error::GridError;
+
+/// Errors found while reading the map of the trees
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A tree height that isn't a digit
+    InvalidHeight(char),
+    /// A row that isn't as wide as the first one
+    InvalidWidth { expected: usize, found: usize },
+    /// The map has no tree
+    Empty,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidHeight(c) => write!(f, "expected a height (0-9), found '{}'", c),
+            ParseError::InvalidWidth { expected, found } => {
+                write!(f, "expected {} trees in the row, found {}", expected, found)
+            }
+            ParseError::Empty => write!(f, "expected a map of trees, found nothing"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<GridError<ParseError>> for ParseError {
+    fn from(e: GridError<ParseError>) -> Self {
+        match e {
+            GridError::Cell(e) => e,
+            GridError::InvalidWidth { expected, found } => {
+                ParseError::InvalidWidth { expected, found }
+            }
+            GridError::Empty => ParseError::Empty,
+        }
+    }
+}
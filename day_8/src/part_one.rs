@@ -1,108 +1,45 @@
-use std::error::Error;
 use std::fmt::Display;
-use std::io;
 
-pub fn main_p1() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
+use aoc_core::{Answer, Solution, SolveError};
+use aoc_grid::{Grid, Pos, DIRECTIONS4};
 
-    let mut index = 0;
-    let mut matrix = Vec::<Vec<Tree>>::new();
+use crate::error::ParseError;
 
-    // 1. Create a 2D array with all the values
-    for line in lines {
-        let line = line.unwrap();
-        matrix.push(Vec::new());
+pub struct PartOne;
 
-        for c in line.chars() {
-            matrix[index].push(Tree::new(c.to_digit(10).unwrap() as u8));
-        }
-
-        index += 1;
-    }
-
-    // 2. Set edge trees
-    let mut r = 0;
-    let matrix_len = matrix.len();
-    let row_len = matrix[0].len();
-    for row in &mut matrix {
-        let mut c = 0;
-        for col in row {
-            if r == 0 || r == (matrix_len - 1) || c == 0 || c == (row_len - 1) {
-                col.set_edge();
-            }
-            c += 1;
-        }
-        r += 1;
-    }
-
-    for row in 1..(matrix.len() - 1) {
-        for col in 1..(matrix[row].len() - 1) {
-            let height = matrix[row][col].get_height();
-
-            // Check looking up
-            let mut visible_up = true;
-            let mut up = row - 1;
-            while visible_up {
-                visible_up = matrix[up][col].get_height() < height;
-                if up == 0 {
-                    break;
-                }
-                up -= 1;
-            }
-
-            // Check looking right
-            let mut visible_right = true;
-            let mut right = col + 1;
-            while visible_right {
-                visible_right = matrix[row][right].get_height() < height;
-                if right == matrix[row].len() - 1 {
-                    break;
-                }
-                right += 1;
-            }
-
-            // Check looking down
-            let mut visible_down = true;
-            let mut down = row + 1;
-            while visible_down {
-                visible_down = matrix[down][col].get_height() < height;
-                if down == matrix.len() - 1 {
-                    break;
-                }
-                down += 1;
-            }
-
-            // Check looking left
-            let mut visible_left = true;
-            let mut left = col - 1;
-            while visible_left {
-                visible_left = matrix[row][left].get_height() < height;
-                if left == 0 {
-                    break;
-                }
-                left -= 1;
-            }
-
-            matrix[row][col]
-                .set_visibility(visible_up || visible_right || visible_down || visible_left);
-        }
-    }
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        // 1. Create a 2D grid with all the trees
+        let mut trees =
+            Grid::parse(input, |_, c| Tree::try_from(c)).map_err(|e| e.map(ParseError::from))?;
 
-    let number_of_tree_visible = matrix.iter().fold(0, |acc, row| {
-        acc + row
+        // 2. A tree is visible when all the trees up to an edge are shorter, in any direction.
+        // Trees on the edges have nothing in front of them and are always visible.
+        let visible: Vec<Pos> = trees
             .iter()
-            .fold(0, |acc, tree| acc + if tree.is_visible() { 1 } else { 0 })
-    });
+            .filter(|&(pos, tree)| {
+                DIRECTIONS4.iter().any(|&dir| {
+                    trees
+                        .ray(pos, dir)
+                        .all(|(_, other)| other.get_height() < tree.get_height())
+                })
+            })
+            .map(|(pos, _)| pos)
+            .collect();
+
+        for pos in visible {
+            trees[pos].set_visibility(true);
+        }
 
-    println!("Number of tree visible: {}", number_of_tree_visible);
+        let number_of_tree_visible = trees.iter().filter(|(_, tree)| tree.is_visible()).count();
 
-    Ok(())
+        Ok(number_of_tree_visible.into())
+    }
 }
 
 pub struct Tree {
     height: u8,
     visible: bool,
-    edge: bool,
 }
 
 impl Tree {
@@ -110,19 +47,9 @@ impl Tree {
         Tree {
             height,
             visible: false,
-            edge: false,
         }
     }
 
-    pub fn set_edge(&mut self) {
-        self.edge = true;
-        self.visible = true;
-    }
-
-    pub fn is_edge(&self) -> bool {
-        self.edge
-    }
-
     pub fn get_height(&self) -> u8 {
         self.height
     }
@@ -136,6 +63,17 @@ impl Tree {
     }
 }
 
+impl TryFrom<char> for Tree {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c.to_digit(10) {
+            Some(height) => Ok(Tree::new(height as u8)),
+            None => Err(ParseError::InvalidHeight(c)),
+        }
+    }
+}
+
 impl Display for Tree {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{{height:{}, visible{}}}", self.height, self.visible)
@@ -147,5 +85,25 @@ mod tests {
     use super::*;
 
     #[test]
-    fn simple() {}
+    fn example() {
+        let input = "30373\n25512\n65332\n33549\n35390\n";
+
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(21u32));
+    }
+
+    #[test]
+    fn invalid_height() {
+        assert_eq!(
+            PartOne::solve("303\n2x5\n653\n").unwrap_err().to_string(),
+            "Invalid input: line 2, column 2: expected a height (0-9), found 'x'"
+        );
+    }
+
+    #[test]
+    fn invalid_width() {
+        assert_eq!(
+            PartOne::solve("303\n25\n653\n").unwrap_err().to_string(),
+            "Invalid input: line 2, column 1: expected 3 trees in the row, found 2"
+        );
+    }
 }
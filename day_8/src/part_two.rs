@@ -1,101 +1,57 @@
-use std::error::Error;
 use std::fmt::Display;
-use std::io;
 
-pub fn main_p2() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
+use aoc_core::{Answer, Solution, SolveError};
+use aoc_grid::{Grid, Pos, DIRECTIONS4};
 
-    let mut index = 0;
-    let mut matrix = Vec::<Vec<Tree>>::new();
+use crate::error::ParseError;
 
-    // 1. Create a 2D array with all the values
-    for line in lines {
-        let line = line.unwrap();
-        matrix.push(Vec::new());
+pub struct PartTwo;
 
-        for c in line.chars() {
-            matrix[index].push(Tree::new(c.to_digit(10).unwrap() as u8));
-        }
-
-        index += 1;
-    }
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        // 1. Create a 2D grid with all the trees
+        let mut trees =
+            Grid::parse(input, |_, c| Tree::try_from(c)).map_err(|e| e.map(ParseError::from))?;
 
-    // 2. Set edge trees
-    let mut r = 0;
-    let matrix_len = matrix.len();
-    let row_len = matrix[0].len();
-    for row in &mut matrix {
-        let mut c = 0;
-        for col in row {
-            if r == 0 || r == (matrix_len - 1) || c == 0 || c == (row_len - 1) {
-                col.set_edge();
-            }
-            c += 1;
+        // 2. Count the trees seen in each direction, up to the first one at least as tall.
+        // Trees on the edges see nothing in one direction and get a score of 0.
+        let scores: Vec<(Pos, u32)> = trees
+            .iter()
+            .map(|(pos, tree)| {
+                let score = DIRECTIONS4
+                    .iter()
+                    .map(|&dir| {
+                        let mut seen = 0;
+                        for (_, other) in trees.ray(pos, dir) {
+                            seen += 1;
+                            if other.get_height() >= tree.get_height() {
+                                break;
+                            }
+                        }
+                        seen
+                    })
+                    .product();
+
+                (pos, score)
+            })
+            .collect();
+
+        for (pos, score) in scores {
+            trees[pos].set_scenic_score(score);
         }
-        r += 1;
-    }
 
-    for row in 1..(matrix.len() - 1) {
-        for col in 1..(matrix[row].len() - 1) {
-            let height = matrix[row][col].get_height();
-
-            // Check looking up
-            let mut visible_up = 1;
-            let mut up = row - 1;
-            while !matrix[up][col].is_edge() && matrix[up][col].get_height() < height {
-                visible_up += 1;
-                up -= 1;
-            }
-
-            // Check looking right
-            let mut visible_right = 1;
-            let mut right = col + 1;
-            while !matrix[row][right].is_edge() && matrix[row][right].get_height() < height {
-                visible_right += 1;
-                right += 1;
-            }
-
-            // Check looking down
-            let mut visible_down = 1;
-            let mut down = row + 1;
-            while !matrix[down][col].is_edge() && matrix[down][col].get_height() < height {
-                visible_down += 1;
-                down += 1;
-            }
-
-            // Check looking left
-            let mut visible_left = 1;
-            let mut left = col - 1;
-            while !matrix[row][left].is_edge() && matrix[row][left].get_height() < height {
-                visible_left += 1;
-                left -= 1;
-            }
-
-            matrix[row][col]
-                .set_scenic_score(visible_up * visible_right * visible_down * visible_left);
-        }
-    }
-
-    let mut max_scenic_score = u32::MIN;
-
-    for row in matrix {
-        let cur_max_scenic_score = row
+        let max_scenic_score = trees
             .iter()
-            .max_by_key(|tree| tree.get_scenic_score())
-            .unwrap()
-            .get_scenic_score();
+            .map(|(_, tree)| tree.get_scenic_score())
+            .max()
+            .unwrap_or(0);
 
-        max_scenic_score = max_scenic_score.max(cur_max_scenic_score);
+        Ok(max_scenic_score.into())
     }
-
-    println!("Max scenic_score: {}", max_scenic_score);
-
-    Ok(())
 }
 
 pub struct Tree {
     height: u8,
-    edge: bool,
     scenic_score: u32,
 }
 
@@ -103,19 +59,10 @@ impl Tree {
     pub fn new(height: u8) -> Self {
         Tree {
             height,
-            edge: false,
             scenic_score: 0,
         }
     }
 
-    pub fn set_edge(&mut self) {
-        self.edge = true;
-    }
-
-    pub fn is_edge(&self) -> bool {
-        self.edge
-    }
-
     pub fn get_height(&self) -> u8 {
         self.height
     }
@@ -129,6 +76,17 @@ impl Tree {
     }
 }
 
+impl TryFrom<char> for Tree {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c.to_digit(10) {
+            Some(height) => Ok(Tree::new(height as u8)),
+            None => Err(ParseError::InvalidHeight(c)),
+        }
+    }
+}
+
 impl Display for Tree {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{{height:{}}}", self.height)
@@ -140,5 +98,17 @@ mod tests {
     use super::*;
 
     #[test]
-    fn simple() {}
+    fn example() {
+        let input = "30373\n25512\n65332\n33549\n35390\n";
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(8u32));
+    }
+
+    #[test]
+    fn empty_map() {
+        assert_eq!(
+            PartTwo::solve("").unwrap_err().to_string(),
+            "Invalid input: line 1, column 1: expected a map of trees, found nothing"
+        );
+    }
 }
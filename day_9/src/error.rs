@@ -0,0 +1,27 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Errors found while reading the motions of the head
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A direction other than U, D, L or R
+    InvalidDirection(String),
+    /// A motion without its number of steps
+    MissingSteps,
+    /// A number of steps that isn't a positive integer
+    InvalidSteps(String),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidDirection(s) => {
+                write!(f, "expected a direction (U, D, L or R), found '{}'", s)
+            }
+            ParseError::MissingSteps => write!(f, "expected a number of steps, found nothing"),
+            ParseError::InvalidSteps(s) => write!(f, "expected a number of steps, found '{}'", s),
+        }
+    }
+}
+
+impl Error for ParseError {}
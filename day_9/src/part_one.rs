@@ -1,126 +1,78 @@
 use std::collections::HashSet;
-use std::error::Error;
-use std::io;
-
-pub fn main_p1() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
-
-    let mut head = Position::default();
-    let mut tail = Position::default();
-
-    let mut visited = HashSet::<Position>::new();
-    visited.insert(tail.clone());
-
-    for line in lines {
-        let line = line.unwrap();
-
-        let command: Vec<&str> = line.split(' ').collect();
-        assert_eq!(command.len(), 2);
-
-        let dir = Direction::from(command[0].chars().nth(0).unwrap());
-        let steps: u32 = command[1].parse()?;
-
-        for _ in 0..steps {
-            head.mv(&dir);
-
-            if head.distance(&tail) > 1 {
-                if head.y == tail.y || head.x == tail.x {
-                    // Still horizontally/vertically aligned
-                    // Do the same movement as the head
-                    tail.mv(&dir);
-                } else {
-                    // We need to make 2 moves to catch up to the head
-                    // 1. Perform the same move as the head
-                    tail.mv(&dir);
-
-                    // Now we need to align the tail with the head on one dimension
-                    match dir {
-                        Direction::Up | Direction::Down => {
-                            if head.x < tail.x {
-                                tail.mv(&Direction::Left);
-                            } else {
-                                tail.mv(&Direction::Right);
+use std::str::FromStr;
+
+use aoc_core::{column, Answer, Locate, Located, Solution, SolveError};
+use aoc_point::Point2;
+
+use crate::error::ParseError;
+
+pub struct PartOne;
+
+impl Solution for PartOne {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut head = Position::new(0, 0);
+        let mut tail = Position::new(0, 0);
+
+        let mut visited = HashSet::<Position>::new();
+        visited.insert(tail);
+
+        for (index, line) in input.lines().enumerate() {
+            let (dir, steps) = line.split_once(' ').ok_or(Located::new(
+                index + 1,
+                line.len() + 1,
+                ParseError::MissingSteps,
+            ))?;
+
+            let dir = Direction::from_str(dir).at(index + 1, 1)?;
+            let steps: u32 = steps
+                .parse()
+                .map_err(|_| ParseError::InvalidSteps(steps.to_string()))
+                .at(index + 1, column(line, steps))?;
+
+            for _ in 0..steps {
+                head += dir.step();
+
+                if head.chebyshev(&tail) > 1 {
+                    if head.y == tail.y || head.x == tail.x {
+                        // Still horizontally/vertically aligned
+                        // Do the same movement as the head
+                        tail += dir.step();
+                    } else {
+                        // We need to make 2 moves to catch up to the head
+                        // 1. Perform the same move as the head
+                        tail += dir.step();
+
+                        // Now we need to align the tail with the head on one dimension
+                        match dir {
+                            Direction::Up | Direction::Down => {
+                                if head.x < tail.x {
+                                    tail += Direction::Left.step();
+                                } else {
+                                    tail += Direction::Right.step();
+                                }
                             }
-                        }
-                        Direction::Left | Direction::Right => {
-                            if head.y < tail.y {
-                                tail.mv(&Direction::Down);
-                            } else {
-                                tail.mv(&Direction::Up);
+                            Direction::Left | Direction::Right => {
+                                if head.y < tail.y {
+                                    tail += Direction::Down.step();
+                                } else {
+                                    tail += Direction::Up.step();
+                                }
                             }
                         }
                     }
+
+                    visited.insert(tail);
                 }
 
-                visited.insert(tail.clone());
+                assert!(head.chebyshev(&tail) < 2);
             }
-
-            assert!(head.distance(&tail) < 2);
-        }
-    }
-
-    println!("Number of position visited: {}", visited.len());
-
-    Ok(())
-}
-
-#[derive(Debug, Hash)]
-struct Position {
-    x: i32,
-    y: i32,
-}
-
-impl Position {
-    fn new(x: i32, y: i32) -> Self {
-        Position { x, y }
-    }
-
-    fn distance(&self, p: &Self) -> usize {
-        let mut distance = 0;
-
-        if self.x != p.x {
-            distance = (self.x - p.x).abs() as usize;
-        }
-
-        if self.y != p.y {
-            distance = distance.max((self.y - p.y).abs() as usize);
-        }
-
-        distance
-    }
-
-    fn mv(&mut self, dir: &Direction) {
-        match dir {
-            Direction::Up => self.y += 1,
-            Direction::Down => self.y -= 1,
-            Direction::Left => self.x -= 1,
-            Direction::Right => self.x += 1,
-        }
-    }
-}
-
-impl Default for Position {
-    fn default() -> Self {
-        Position { x: 0, y: 0 }
-    }
-}
-
-impl Clone for Position {
-    fn clone(&self) -> Self {
-        Position {
-            x: self.x,
-            y: self.y,
         }
-    }
-}
 
-impl PartialEq for Position {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y
+        Ok(visited.len().into())
     }
 }
 
-impl Eq for Position {}
+type Position = Point2<i32>;
 
 enum Direction {
     Up,
@@ -129,14 +81,28 @@ enum Direction {
     Right,
 }
 
-impl From<char> for Direction {
-    fn from(c: char) -> Self {
-        match c {
-            'U' => Direction::Up,
-            'D' => Direction::Down,
-            'L' => Direction::Left,
-            'R' => Direction::Right,
-            _ => panic!("Char not recognized."),
+impl Direction {
+    /// Move of one step in this direction, `y` growing upward
+    fn step(&self) -> Position {
+        match self {
+            Direction::Up => Position::new(0, 1),
+            Direction::Down => Position::new(0, -1),
+            Direction::Left => Position::new(-1, 0),
+            Direction::Right => Position::new(1, 0),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "U" => Ok(Direction::Up),
+            "D" => Ok(Direction::Down),
+            "L" => Ok(Direction::Left),
+            "R" => Ok(Direction::Right),
+            _ => Err(ParseError::InvalidDirection(s.to_string())),
         }
     }
 }
@@ -145,12 +111,35 @@ impl From<char> for Direction {
 mod tests {
     use super::*;
 
+    #[test]
+    fn example() {
+        let input = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n";
+
+        assert_eq!(PartOne::solve(input).unwrap(), Answer::from(13usize));
+    }
+
+    #[test]
+    fn invalid_direction() {
+        assert_eq!(
+            PartOne::solve("R 4\nX 4\n").unwrap_err().to_string(),
+            "Invalid input: line 2, column 1: expected a direction (U, D, L or R), found 'X'"
+        );
+    }
+
+    #[test]
+    fn missing_steps() {
+        assert_eq!(
+            PartOne::solve("R 4\nU\n").unwrap_err().to_string(),
+            "Invalid input: line 2, column 2: expected a number of steps, found nothing"
+        );
+    }
+
     #[test]
     fn position_distance_zero() {
         let a = Position::default();
         let b = Position::default();
 
-        assert_eq!(a.distance(&b), 0);
+        assert_eq!(a.chebyshev(&b), 0);
     }
 
     #[test]
@@ -158,7 +147,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(1, 2);
 
-        assert_eq!(a.distance(&b), 1);
+        assert_eq!(a.chebyshev(&b), 1);
     }
 
     #[test]
@@ -166,7 +155,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(3, 2);
 
-        assert_eq!(a.distance(&b), 1);
+        assert_eq!(a.chebyshev(&b), 1);
     }
 
     #[test]
@@ -174,7 +163,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(2, 1);
 
-        assert_eq!(a.distance(&b), 1);
+        assert_eq!(a.chebyshev(&b), 1);
     }
 
     #[test]
@@ -182,7 +171,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(2, 3);
 
-        assert_eq!(a.distance(&b), 1);
+        assert_eq!(a.chebyshev(&b), 1);
     }
 
     #[test]
@@ -190,7 +179,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(0, 2);
 
-        assert_eq!(a.distance(&b), 2);
+        assert_eq!(a.chebyshev(&b), 2);
     }
 
     #[test]
@@ -198,7 +187,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(4, 2);
 
-        assert_eq!(a.distance(&b), 2);
+        assert_eq!(a.chebyshev(&b), 2);
     }
 
     #[test]
@@ -206,7 +195,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(2, 0);
 
-        assert_eq!(a.distance(&b), 2);
+        assert_eq!(a.chebyshev(&b), 2);
     }
 
     #[test]
@@ -214,7 +203,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(2, 4);
 
-        assert_eq!(a.distance(&b), 2);
+        assert_eq!(a.chebyshev(&b), 2);
     }
 
     #[test]
@@ -222,7 +211,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(1, 3);
 
-        assert_eq!(a.distance(&b), 1);
+        assert_eq!(a.chebyshev(&b), 1);
     }
 
     #[test]
@@ -230,7 +219,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(3, 3);
 
-        assert_eq!(a.distance(&b), 1);
+        assert_eq!(a.chebyshev(&b), 1);
     }
 
     #[test]
@@ -238,7 +227,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(1, 1);
 
-        assert_eq!(a.distance(&b), 1);
+        assert_eq!(a.chebyshev(&b), 1);
     }
 
     #[test]
@@ -246,7 +235,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(3, 1);
 
-        assert_eq!(a.distance(&b), 1);
+        assert_eq!(a.chebyshev(&b), 1);
     }
 
     #[test]
@@ -254,7 +243,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(0, 4);
 
-        assert_eq!(a.distance(&b), 2);
+        assert_eq!(a.chebyshev(&b), 2);
     }
 
     #[test]
@@ -262,7 +251,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(4, 4);
 
-        assert_eq!(a.distance(&b), 2);
+        assert_eq!(a.chebyshev(&b), 2);
     }
 
     #[test]
@@ -270,7 +259,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(0, 0);
 
-        assert_eq!(a.distance(&b), 2);
+        assert_eq!(a.chebyshev(&b), 2);
     }
 
     #[test]
@@ -278,6 +267,6 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(4, 0);
 
-        assert_eq!(a.distance(&b), 2);
+        assert_eq!(a.chebyshev(&b), 2);
     }
 }
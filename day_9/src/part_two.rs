@@ -1,144 +1,119 @@
 use std::collections::HashSet;
-use std::error::Error;
-use std::io;
+use std::str::FromStr;
 
-const HEAD: usize = 0;
-const TAIL: usize = 9;
-
-pub fn main_p2() -> Result<(), Box<dyn Error>> {
-    let lines = io::stdin().lines();
-
-    let mut rope: [Position; 10] = [Position::default(); 10];
-    let mut rope_dirs: [Direction; 10] = [Direction::Undefined; 10];
-
-    let mut visited = HashSet::<Position>::new();
-    visited.insert(rope[TAIL]);
-
-    for line in lines {
-        let line = line.unwrap();
-
-        let command: Vec<&str> = line.split(' ').collect();
-        assert_eq!(command.len(), 2);
-
-        let dir = Direction::from(command[0].chars().nth(0).unwrap());
-        let steps: u32 = command[1].parse()?;
+use aoc_core::{column, Answer, Locate, Located, Solution, SolveError};
+use aoc_point::Point2;
 
-        for _ in 0..steps {
-            // 1. Move the head
-            rope[HEAD].mv(dir);
-            rope_dirs[HEAD] = dir;
+use crate::error::ParseError;
 
-            // 2. Makes every child to follow
-            for child in (HEAD + 1)..(TAIL + 1) {
-                // 2.5 Every part of the rope can be seen as (head, tail)
-                // Thus, we can apply the follow function to each of them
-                // Each part will follow the part in front of her
-                let head = rope[child - 1];
-                let mut tail = rope[child];
-
-                let child_dir = tail.follow(&head, rope_dirs[child - 1]);
-                rope[child] = tail;
-                rope_dirs[child] = child_dir;
-            }
+const HEAD: usize = 0;
+const TAIL: usize = 9;
 
-            // 3. Insert the position of the TAIL
-            visited.insert(rope[TAIL]);
+pub struct PartTwo;
+
+impl Solution for PartTwo {
+    fn solve(input: &str) -> Result<Answer, SolveError> {
+        let mut rope: [Position; 10] = [Position::new(0, 0); 10];
+        let mut rope_dirs: [Direction; 10] = [Direction::Undefined; 10];
+
+        let mut visited = HashSet::<Position>::new();
+        visited.insert(rope[TAIL]);
+
+        for (index, line) in input.lines().enumerate() {
+            let (dir, steps) = line.split_once(' ').ok_or(Located::new(
+                index + 1,
+                line.len() + 1,
+                ParseError::MissingSteps,
+            ))?;
+
+            let dir = Direction::from_str(dir).at(index + 1, 1)?;
+            let steps: u32 = steps
+                .parse()
+                .map_err(|_| ParseError::InvalidSteps(steps.to_string()))
+                .at(index + 1, column(line, steps))?;
+
+            for _ in 0..steps {
+                // 1. Move the head
+                rope[HEAD] += dir.step();
+                rope_dirs[HEAD] = dir;
+
+                // 2. Makes every child to follow
+                for child in (HEAD + 1)..(TAIL + 1) {
+                    // 2.5 Every part of the rope can be seen as (head, tail)
+                    // Thus, we can apply the follow function to each of them
+                    // Each part will follow the part in front of her
+                    let head = rope[child - 1];
+                    let mut tail = rope[child];
+
+                    let child_dir = tail.follow(&head, rope_dirs[child - 1]);
+                    rope[child] = tail;
+                    rope_dirs[child] = child_dir;
+                }
 
-            print!("Rope: [");
-            for part in rope {
-                print!("{:?},", part);
-            }
-            println!("]\n");
+                // 3. Insert the position of the TAIL
+                visited.insert(rope[TAIL]);
 
-            // 4. Check if all the parts are close to each other
-            for part in HEAD..TAIL {
-                assert!(rope[part].distance(&rope[part + 1]) < 2);
+                // 4. Check if all the parts are close to each other
+                for part in HEAD..TAIL {
+                    assert!(rope[part].chebyshev(&rope[part + 1]) < 2);
+                }
             }
         }
-    }
-
-    println!("Number of position visited: {}", visited.len());
-
-    Ok(())
-}
 
-#[derive(Debug, Hash, Copy)]
-struct Position {
-    x: i32,
-    y: i32,
-}
-
-impl Position {
-    fn new(x: i32, y: i32) -> Self {
-        Position { x, y }
+        Ok(visited.len().into())
     }
+}
 
-    fn distance(&self, p: &Self) -> usize {
-        let mut distance = 0;
-
-        if self.x != p.x {
-            distance = (self.x - p.x).abs() as usize;
-        }
-
-        if self.y != p.y {
-            distance = distance.max((self.y - p.y).abs() as usize);
-        }
+type Position = Point2<i32>;
 
-        distance
-    }
-
-    fn mv(&mut self, dir: Direction) {
-        match dir {
-            Direction::Up => self.y += 1,
-            Direction::Down => self.y -= 1,
-            Direction::Left => self.x -= 1,
-            Direction::Right => self.x += 1,
-            Direction::Undefined => panic!("Direction is Undefined"),
-        }
-    }
+/// A knot of the rope, pulled by the knot in front of it
+trait Knot {
+    fn follow(&mut self, other: &Self, dir: Direction) -> Direction;
+}
 
+impl Knot for Position {
     fn follow(&mut self, other: &Self, dir: Direction) -> Direction {
-        if self.distance(&other) > 1 {
+        if self.chebyshev(other) > 1 {
             if other.y == self.y {
                 // Horizontally aligned
                 if other.x > self.x {
-                    self.mv(Direction::Right);
+                    *self += Direction::Right.step();
                     Direction::Right
                 } else {
-                    self.mv(Direction::Left);
+                    *self += Direction::Left.step();
                     Direction::Left
                 }
             } else if other.x == self.x {
                 // vertically aligned
                 if other.y > self.y {
-                    self.mv(Direction::Up);
+                    *self += Direction::Up.step();
                     Direction::Up
                 } else {
-                    self.mv(Direction::Down);
+                    *self += Direction::Down.step();
                     Direction::Down
                 }
             } else {
                 // We need to make 2 moves to catch up to the head
                 // 1. Perform the same move as the head
-                self.mv(dir);
+                *self += dir.step();
 
                 // Now we need to align the tail with the head on one dimension
                 match dir {
                     Direction::Up | Direction::Down => {
                         if other.x < self.x {
-                            self.mv(Direction::Left);
+                            *self += Direction::Left.step();
                             Direction::Left
                         } else {
-                            self.mv(Direction::Right);
+                            *self += Direction::Right.step();
                             Direction::Right
                         }
                     }
                     Direction::Left | Direction::Right => {
                         if other.y < self.y {
-                            self.mv(Direction::Down);
+                            *self += Direction::Down.step();
                             Direction::Down
                         } else {
-                            self.mv(Direction::Up);
+                            *self += Direction::Up.step();
                             Direction::Up
                         }
                     }
@@ -151,29 +126,6 @@ impl Position {
     }
 }
 
-impl Default for Position {
-    fn default() -> Self {
-        Position { x: 0, y: 0 }
-    }
-}
-
-impl Clone for Position {
-    fn clone(&self) -> Self {
-        Position {
-            x: self.x,
-            y: self.y,
-        }
-    }
-}
-
-impl PartialEq for Position {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y
-    }
-}
-
-impl Eq for Position {}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Direction {
     Up,
@@ -183,14 +135,29 @@ enum Direction {
     Undefined,
 }
 
-impl From<char> for Direction {
-    fn from(c: char) -> Self {
-        match c {
-            'U' => Direction::Up,
-            'D' => Direction::Down,
-            'L' => Direction::Left,
-            'R' => Direction::Right,
-            _ => panic!("Char not recognized."),
+impl Direction {
+    /// Move of one step in this direction, `y` growing upward
+    fn step(&self) -> Position {
+        match self {
+            Direction::Up => Position::new(0, 1),
+            Direction::Down => Position::new(0, -1),
+            Direction::Left => Position::new(-1, 0),
+            Direction::Right => Position::new(1, 0),
+            Direction::Undefined => panic!("Direction is Undefined"),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "U" => Ok(Direction::Up),
+            "D" => Ok(Direction::Down),
+            "L" => Ok(Direction::Left),
+            "R" => Ok(Direction::Right),
+            _ => Err(ParseError::InvalidDirection(s.to_string())),
         }
     }
 }
@@ -199,12 +166,34 @@ impl From<char> for Direction {
 mod tests {
     use super::*;
 
+    #[test]
+    fn example() {
+        let input = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n";
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(1usize));
+    }
+
+    #[test]
+    fn larger_example() {
+        let input = "R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20\n";
+
+        assert_eq!(PartTwo::solve(input).unwrap(), Answer::from(36usize));
+    }
+
+    #[test]
+    fn invalid_steps() {
+        assert_eq!(
+            PartTwo::solve("R 4\nU four\n").unwrap_err().to_string(),
+            "Invalid input: line 2, column 3: expected a number of steps, found 'four'"
+        );
+    }
+
     #[test]
     fn position_distance_zero() {
         let a = Position::default();
         let b = Position::default();
 
-        assert_eq!(a.distance(&b), 0);
+        assert_eq!(a.chebyshev(&b), 0);
     }
 
     #[test]
@@ -212,7 +201,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(1, 2);
 
-        assert_eq!(a.distance(&b), 1);
+        assert_eq!(a.chebyshev(&b), 1);
     }
 
     #[test]
@@ -220,7 +209,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(3, 2);
 
-        assert_eq!(a.distance(&b), 1);
+        assert_eq!(a.chebyshev(&b), 1);
     }
 
     #[test]
@@ -228,7 +217,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(2, 1);
 
-        assert_eq!(a.distance(&b), 1);
+        assert_eq!(a.chebyshev(&b), 1);
     }
 
     #[test]
@@ -236,7 +225,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(2, 3);
 
-        assert_eq!(a.distance(&b), 1);
+        assert_eq!(a.chebyshev(&b), 1);
     }
 
     #[test]
@@ -244,7 +233,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(0, 2);
 
-        assert_eq!(a.distance(&b), 2);
+        assert_eq!(a.chebyshev(&b), 2);
     }
 
     #[test]
@@ -252,7 +241,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(4, 2);
 
-        assert_eq!(a.distance(&b), 2);
+        assert_eq!(a.chebyshev(&b), 2);
     }
 
     #[test]
@@ -260,7 +249,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(2, 0);
 
-        assert_eq!(a.distance(&b), 2);
+        assert_eq!(a.chebyshev(&b), 2);
     }
 
     #[test]
@@ -268,7 +257,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(2, 4);
 
-        assert_eq!(a.distance(&b), 2);
+        assert_eq!(a.chebyshev(&b), 2);
     }
 
     #[test]
@@ -276,7 +265,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(1, 3);
 
-        assert_eq!(a.distance(&b), 1);
+        assert_eq!(a.chebyshev(&b), 1);
     }
 
     #[test]
@@ -284,7 +273,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(3, 3);
 
-        assert_eq!(a.distance(&b), 1);
+        assert_eq!(a.chebyshev(&b), 1);
     }
 
     #[test]
@@ -292,7 +281,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(1, 1);
 
-        assert_eq!(a.distance(&b), 1);
+        assert_eq!(a.chebyshev(&b), 1);
     }
 
     #[test]
@@ -300,7 +289,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(3, 1);
 
-        assert_eq!(a.distance(&b), 1);
+        assert_eq!(a.chebyshev(&b), 1);
     }
 
     #[test]
@@ -308,7 +297,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(0, 4);
 
-        assert_eq!(a.distance(&b), 2);
+        assert_eq!(a.chebyshev(&b), 2);
     }
 
     #[test]
@@ -316,7 +305,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(4, 4);
 
-        assert_eq!(a.distance(&b), 2);
+        assert_eq!(a.chebyshev(&b), 2);
     }
 
     #[test]
@@ -324,7 +313,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(0, 0);
 
-        assert_eq!(a.distance(&b), 2);
+        assert_eq!(a.chebyshev(&b), 2);
     }
 
     #[test]
@@ -332,7 +321,7 @@ mod tests {
         let a = Position::new(2, 2);
         let b = Position::new(4, 0);
 
-        assert_eq!(a.distance(&b), 2);
+        assert_eq!(a.chebyshev(&b), 2);
     }
 
     #[test]
@@ -344,7 +333,7 @@ mod tests {
         let dir_actual = tail.follow(&head, dir_expected);
 
         assert_eq!(dir_actual, dir_expected);
-        assert_eq!(head.distance(&tail), 1);
+        assert_eq!(head.chebyshev(&tail), 1);
     }
 
     #[test]
@@ -356,7 +345,7 @@ mod tests {
         let dir_actual = tail.follow(&head, dir_expected);
 
         assert_eq!(dir_actual, dir_expected);
-        assert_eq!(head.distance(&tail), 1);
+        assert_eq!(head.chebyshev(&tail), 1);
     }
 
     #[test]
@@ -368,7 +357,7 @@ mod tests {
         let dir_actual = tail.follow(&head, dir_expected);
 
         assert_eq!(dir_actual, dir_expected);
-        assert_eq!(head.distance(&tail), 1);
+        assert_eq!(head.chebyshev(&tail), 1);
     }
 
     #[test]
@@ -380,7 +369,7 @@ mod tests {
         let dir_actual = tail.follow(&head, dir_expected);
 
         assert_eq!(dir_actual, dir_expected);
-        assert_eq!(head.distance(&tail), 1);
+        assert_eq!(head.chebyshev(&tail), 1);
     }
 
     #[test]
@@ -392,7 +381,7 @@ mod tests {
         let dir_actual = tail.follow(&head, dir_expected);
 
         assert_eq!(dir_actual, dir_expected);
-        assert_eq!(head.distance(&tail), 0);
+        assert_eq!(head.chebyshev(&tail), 0);
     }
 
     #[test]
@@ -404,7 +393,7 @@ mod tests {
         let dir_actual = tail.follow(&head, Direction::Left);
 
         assert_eq!(dir_actual, dir_expected);
-        assert_eq!(head.distance(&tail), 1);
+        assert_eq!(head.chebyshev(&tail), 1);
     }
 
     #[test]
@@ -416,7 +405,7 @@ mod tests {
         let dir_actual = tail.follow(&head, Direction::Right);
 
         assert_eq!(dir_actual, dir_expected);
-        assert_eq!(head.distance(&tail), 1);
+        assert_eq!(head.chebyshev(&tail), 1);
     }
 
     #[test]
@@ -428,7 +417,7 @@ mod tests {
         let dir_actual = tail.follow(&head, Direction::Up);
 
         assert_eq!(dir_actual, dir_expected);
-        assert_eq!(head.distance(&tail), 1);
+        assert_eq!(head.chebyshev(&tail), 1);
     }
 
     #[test]
@@ -440,7 +429,7 @@ mod tests {
         let dir_actual = tail.follow(&head, Direction::Down);
 
         assert_eq!(dir_actual, dir_expected);
-        assert_eq!(head.distance(&tail), 1);
+        assert_eq!(head.chebyshev(&tail), 1);
     }
 
     #[test]
@@ -451,7 +440,7 @@ mod tests {
         let dir = tail.follow(&head, Direction::Left);
 
         assert_eq!(dir, Direction::Up);
-        assert_eq!(head.distance(&tail), 1);
+        assert_eq!(head.chebyshev(&tail), 1);
     }
 
     #[test]
@@ -462,7 +451,7 @@ mod tests {
         let dir = tail.follow(&head, Direction::Left);
 
         assert_eq!(dir, Direction::Down);
-        assert_eq!(head.distance(&tail), 1);
+        assert_eq!(head.chebyshev(&tail), 1);
     }
 
     #[test]
@@ -473,7 +462,7 @@ mod tests {
         let dir = tail.follow(&head, Direction::Right);
 
         assert_eq!(dir, Direction::Up);
-        assert_eq!(head.distance(&tail), 1);
+        assert_eq!(head.chebyshev(&tail), 1);
     }
 
     #[test]
@@ -484,7 +473,7 @@ mod tests {
         let dir = tail.follow(&head, Direction::Right);
 
         assert_eq!(dir, Direction::Down);
-        assert_eq!(head.distance(&tail), 1);
+        assert_eq!(head.chebyshev(&tail), 1);
     }
 
     #[test]
@@ -495,7 +484,7 @@ mod tests {
         let dir = tail.follow(&head, Direction::Up);
 
         assert_eq!(dir, Direction::Left);
-        assert_eq!(head.distance(&tail), 1);
+        assert_eq!(head.chebyshev(&tail), 1);
     }
 
     #[test]
@@ -506,7 +495,7 @@ mod tests {
         let dir = tail.follow(&head, Direction::Up);
 
         assert_eq!(dir, Direction::Right);
-        assert_eq!(head.distance(&tail), 1);
+        assert_eq!(head.chebyshev(&tail), 1);
     }
 
     #[test]
@@ -517,7 +506,7 @@ mod tests {
         let dir = tail.follow(&head, Direction::Down);
 
         assert_eq!(dir, Direction::Left);
-        assert_eq!(head.distance(&tail), 1);
+        assert_eq!(head.chebyshev(&tail), 1);
     }
 
     #[test]
@@ -528,6 +517,6 @@ mod tests {
         let dir = tail.follow(&head, Direction::Down);
 
         assert_eq!(dir, Direction::Right);
-        assert_eq!(head.distance(&tail), 1);
+        assert_eq!(head.chebyshev(&tail), 1);
     }
 }